@@ -6,7 +6,8 @@ use rand::{rngs::OsRng, seq::SliceRandom};
 use smallvec::SmallVec;
 
 use quickdna::{
-    BaseSequence, DnaSequence, Nucleotide, NucleotideIter, NucleotideLike, TranslationTable,
+    rc_position, BaseSequence, DnaSequence, Nucleotide, NucleotideIter, NucleotideLike, Strand,
+    TranslationTable,
 };
 
 static PROTEIN_WINDOW_LEN: usize = 20;
@@ -107,27 +108,21 @@ pub struct IterBasedSequenceWindows {
     protein_window_len: usize,
     dna: String,
     dna_rc: String,
-    aas: SmallVec<[String; 6]>,
+    aas: SmallVec<[((Strand, usize), String); 6]>,
 }
 
 impl IterBasedSequenceWindows {
     fn from_dna(dna: &[Nucleotide], dna_window_len: usize, protein_window_len: usize) -> Self {
-        let mut aas = SmallVec::new();
         let ncbi1 = TranslationTable::Ncbi1.to_fn();
-        aas.extend(dna.iter().self_reading_frames().into_iter().map(|codons| {
-            let translated: Vec<_> = codons.map(ncbi1).collect();
-            String::from_utf8(translated).unwrap()
-        }));
-        aas.extend(
-            dna.iter()
-                .reverse_complement()
-                .self_reading_frames()
-                .into_iter()
-                .map(|codons| {
-                    let translated: Vec<_> = codons.map(ncbi1).collect();
-                    String::from_utf8(translated).unwrap()
-                }),
-        );
+        let aas = dna
+            .iter()
+            .enumerate_all_reading_frames()
+            .into_iter()
+            .map(|(frame, codons)| {
+                let translated: Vec<_> = codons.map(ncbi1).collect();
+                (frame, String::from_utf8(translated).unwrap())
+            })
+            .collect();
 
         let dna_rc = dna
             .iter()
@@ -164,10 +159,10 @@ impl IterBasedSequenceWindows {
     }
 
     fn dna_rc_windows(&self) -> impl ExactSizeIterator<Item = (usize, &str)> {
-        let first_dna_rc_idx = Self::num_windows(self.dna_rc.len(), self.dna_window_len) - 1;
-        Self::ascii_str_windows(&self.dna_rc, self.dna_window_len)
+        let (dna_len, window_len) = (self.dna_rc.len(), self.dna_window_len);
+        Self::ascii_str_windows(&self.dna_rc, window_len)
             .enumerate()
-            .map(move |(i, w)| (first_dna_rc_idx - i, w))
+            .map(move |(i, w)| (rc_position(dna_len, i, window_len), w))
     }
 
     fn aa_windows(
@@ -177,26 +172,25 @@ impl IterBasedSequenceWindows {
         // fewer than 6 reading frames. That can only happen if the dna sequence is <5 bases
         // long, in which case the aas would be less than the protein window length and
         // therefore lack any windows, making the exact logic of window generation moot.
-        (0..self.aas.len()).map(|frame_idx| {
-            let indices = self.aa_window_indices(frame_idx);
-            let windows = Self::ascii_str_windows(&self.aas[frame_idx], self.protein_window_len);
+        self.aas.iter().map(|&((strand, offset), ref aas)| {
+            let indices = self.aa_window_indices(strand, offset);
+            let windows = Self::ascii_str_windows(aas, self.protein_window_len);
             indices.zip(windows)
         })
     }
 
     // Note: Imitates original logic so I can verify the iter-based window code is correct.
-    fn aa_window_indices(&self, frame_idx: usize) -> impl ExactSizeIterator<Item = usize> {
+    fn aa_window_indices(
+        &self,
+        strand: Strand,
+        offset: usize,
+    ) -> impl ExactSizeIterator<Item = usize> {
         let dna_len = self.dna.len();
         let protein_window_len = self.protein_window_len;
         let num_windows = Self::num_windows(dna_len, protein_window_len);
-        let is_reverse = frame_idx >= 3;
-        let frame_offset = frame_idx % 3;
-        (0..num_windows).map(move |i| {
-            if is_reverse {
-                dna_len - ((i + protein_window_len) * 3 + frame_offset)
-            } else {
-                3 * i + frame_offset
-            }
+        (0..num_windows).map(move |i| match strand {
+            Strand::Forward => 3 * i + offset,
+            Strand::ReverseComplement => dna_len - ((i + protein_window_len) * 3 + offset),
         })
     }
 
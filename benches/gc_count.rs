@@ -0,0 +1,43 @@
+// Copyright 2021-2024 SecureDNA Stiftung (SecureDNA Foundation) <licensing@securedna.org>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rand::{rngs::OsRng, seq::SliceRandom};
+
+use quickdna::Nucleotide;
+
+fn scalar_gc_count(ascii: &[u8]) -> usize {
+    ascii
+        .iter()
+        .filter(|&&b| matches!(b, b'G' | b'C' | b'g' | b'c'))
+        .count()
+}
+
+pub fn criterion_benchmark(c: &mut Criterion) {
+    const DNA_LEN: usize = 1_000_000;
+    let ascii: Vec<u8> = (0..DNA_LEN)
+        .map(|_| *b"ATCGatcg".choose(&mut OsRng).unwrap())
+        .collect();
+    assert_eq!(Nucleotide::gc_count_simd(&ascii), scalar_gc_count(&ascii));
+
+    let dna_len_desc = format!("{DNA_LEN} nucleotides");
+
+    let mut group = c.benchmark_group("GC counting");
+    group.throughput(Throughput::Bytes(DNA_LEN as u64));
+    group.bench_with_input(
+        BenchmarkId::new("scalar", &dna_len_desc),
+        &ascii,
+        |b, ascii| b.iter(|| black_box(scalar_gc_count(ascii))),
+    );
+    group.bench_with_input(
+        BenchmarkId::new("simd", &dna_len_desc),
+        &ascii,
+        |b, ascii| b.iter(|| black_box(Nucleotide::gc_count_simd(ascii))),
+    );
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,49 @@
+// Copyright 2021-2024 SecureDNA Stiftung (SecureDNA Foundation) <licensing@securedna.org>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rand::{rngs::OsRng, seq::SliceRandom};
+
+use quickdna::{Nucleotide, NucleotideAmbiguous, NucleotideLike, TranslationError};
+
+fn per_byte_pack<T: NucleotideLike>(ascii: &[u8]) -> Result<Vec<T>, TranslationError> {
+    ascii.iter().map(|&b| T::try_from(b)).collect()
+}
+
+pub fn criterion_benchmark(c: &mut Criterion) {
+    const DNA_LEN: usize = 100_000;
+    let ascii: Vec<u8> = (0..DNA_LEN)
+        .map(|_| *b"ATCG".choose(&mut OsRng).unwrap())
+        .collect();
+
+    let dna_len_desc = format!("{DNA_LEN} nucleotides");
+
+    let mut group = c.benchmark_group("ASCII packing");
+    group.throughput(Throughput::Bytes(DNA_LEN as u64));
+    group.bench_with_input(
+        BenchmarkId::new("per-byte strict", &dna_len_desc),
+        &ascii,
+        |b, ascii| b.iter(|| black_box(per_byte_pack::<Nucleotide>(ascii))),
+    );
+    group.bench_with_input(
+        BenchmarkId::new("bulk strict", &dna_len_desc),
+        &ascii,
+        |b, ascii| b.iter(|| black_box(Nucleotide::try_pack_ascii(ascii))),
+    );
+    group.bench_with_input(
+        BenchmarkId::new("per-byte ambiguous", &dna_len_desc),
+        &ascii,
+        |b, ascii| b.iter(|| black_box(per_byte_pack::<NucleotideAmbiguous>(ascii))),
+    );
+    group.bench_with_input(
+        BenchmarkId::new("bulk ambiguous", &dna_len_desc),
+        &ascii,
+        |b, ascii| b.iter(|| black_box(NucleotideAmbiguous::try_pack_ascii(ascii))),
+    );
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
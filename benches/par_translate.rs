@@ -0,0 +1,31 @@
+// Copyright 2021-2024 SecureDNA Stiftung (SecureDNA Foundation) <licensing@securedna.org>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rand::{rngs::OsRng, seq::SliceRandom};
+
+use quickdna::{DnaSequenceStrict, Nucleotide, TranslationTable};
+
+pub fn criterion_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("translation");
+    for dna_len in [10_000, 100_000, 1_000_000, 10_000_000] {
+        let dna: Vec<_> = (0..dna_len)
+            .map(|_| *Nucleotide::ALL.choose(&mut OsRng).unwrap())
+            .collect();
+        let dna = DnaSequenceStrict::new(dna);
+
+        group.throughput(Throughput::Elements(dna_len as u64));
+        group.bench_with_input(BenchmarkId::new("serial", dna_len), &dna, |b, dna| {
+            b.iter(|| black_box(dna.translate(TranslationTable::Ncbi1)))
+        });
+        group.bench_with_input(BenchmarkId::new("parallel", dna_len), &dna, |b, dna| {
+            b.iter(|| black_box(dna.par_translate(TranslationTable::Ncbi1)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
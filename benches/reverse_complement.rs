@@ -0,0 +1,37 @@
+// Copyright 2021-2024 SecureDNA Stiftung (SecureDNA Foundation) <licensing@securedna.org>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rand::{rngs::OsRng, seq::SliceRandom};
+
+use quickdna::{twobit::PackedDnaSequence, DnaSequenceStrict, Nucleotide};
+
+pub fn criterion_benchmark(c: &mut Criterion) {
+    const DNA_LEN: usize = 1_000_000;
+    let dna: Vec<_> = (0..DNA_LEN)
+        .map(|_| *Nucleotide::ALL.choose(&mut OsRng).unwrap())
+        .collect();
+    let dna = DnaSequenceStrict::new(dna);
+    let packed = PackedDnaSequence::from(&dna);
+
+    let dna_len_desc = format!("{DNA_LEN} nucleotides");
+
+    let mut group = c.benchmark_group("reverse complement");
+    group.throughput(Throughput::Elements(DNA_LEN as u64));
+    group.bench_with_input(
+        BenchmarkId::new("Vec<Nucleotide>", &dna_len_desc),
+        &dna,
+        |b, dna| b.iter(|| black_box(dna.reverse_complement())),
+    );
+    group.bench_with_input(
+        BenchmarkId::new("2-bit packed", &dna_len_desc),
+        &packed,
+        |b, packed| b.iter(|| black_box(packed.reverse_complement())),
+    );
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
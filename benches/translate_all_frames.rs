@@ -0,0 +1,55 @@
+// Copyright 2021-2024 SecureDNA Stiftung (SecureDNA Foundation) <licensing@securedna.org>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rand::{rngs::OsRng, seq::SliceRandom};
+use smallvec::SmallVec;
+
+use quickdna::{DnaSequenceStrict, Nucleotide, ProteinSequence, TranslationTable};
+
+/// An alternative that translates an owned reverse complement, for comparison.
+fn translate_all_frames_owned_rc(
+    dna: &DnaSequenceStrict,
+    table: TranslationTable,
+) -> SmallVec<[ProteinSequence; 6]> {
+    let mut result = SmallVec::with_capacity(6);
+    result.append(&mut dna.translate_self_frames(table));
+    result.append(&mut dna.reverse_complement().translate_self_frames(table));
+    result
+}
+
+pub fn criterion_benchmark(c: &mut Criterion) {
+    const DNA_LEN: usize = 100_000;
+    let dna: Vec<_> = (0..DNA_LEN)
+        .map(|_| *Nucleotide::ALL.choose(&mut OsRng).unwrap())
+        .collect();
+    let dna = DnaSequenceStrict::new(dna);
+    let table = TranslationTable::Ncbi1;
+
+    // Sanity check that both implementations agree.
+    assert_eq!(
+        dna.translate_all_frames(table),
+        translate_all_frames_owned_rc(&dna, table)
+    );
+
+    let dna_len_desc = format!("{DNA_LEN} nucleotides");
+
+    let mut group = c.benchmark_group("translate all frames");
+    group.throughput(Throughput::Elements(DNA_LEN as u64));
+    group.bench_with_input(
+        BenchmarkId::new("owned reverse complement", &dna_len_desc),
+        &dna,
+        |b, dna| b.iter(|| black_box(translate_all_frames_owned_rc(dna, table))),
+    );
+    group.bench_with_input(
+        BenchmarkId::new("reverse complement iterator", &dna_len_desc),
+        &dna,
+        |b, dna| b.iter(|| black_box(dna.translate_all_frames(table))),
+    );
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
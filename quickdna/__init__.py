@@ -3,7 +3,19 @@
 
 import typing as ty
 
-from .quickdna import _translate, _translate_strict, _reverse_complement, _reverse_complement_strict  # type: ignore
+from .quickdna import (  # type: ignore
+    _translate,
+    _translate_strict,
+    _translate_self_frames,
+    _translate_self_frames_strict,
+    _translate_all_frames,
+    _translate_all_frames_strict,
+    _reverse_complement,
+    _reverse_complement_strict,
+    _gc_content,
+    _base_counts,
+    _parse_fasta,
+)
 
 T = ty.TypeVar("T", bound="BaseSequence")
 
@@ -142,23 +154,12 @@ class DnaSequence(BaseSequence):
         ambiguous nucleotides. Otherwise, a ValueError is raised.
         """
 
-        if len(self) >= 5:
-            return [
-                self.translate(table, strict),
-                self[1:].translate(table, strict),
-                self[2:].translate(table, strict),
-            ]
-        elif len(self) == 4:
-            return [
-                self.translate(table, strict),
-                self[1:].translate(table, strict),
-            ]
-        elif len(self) == 3:
-            return [
-                self.translate(table, strict),
-            ]
+        if strict:
+            seqs = _translate_self_frames_strict(table, self._seq)
         else:
-            return []
+            seqs = _translate_self_frames(table, self._seq)
+
+        return [ProteinSequence(seq) for seq in seqs]
 
     def translate_all_frames(
         self, table: int = 1, strict: bool = False
@@ -177,12 +178,12 @@ class DnaSequence(BaseSequence):
         ambiguous nucleotides. Otherwise, a ValueError is raised.
         """
 
-        return [
-            *self.translate_self_frames(table=table, strict=strict),
-            *self.reverse_complement().translate_self_frames(
-                table=table, strict=strict
-            ),
-        ]
+        if strict:
+            seqs = _translate_all_frames_strict(table, self._seq)
+        else:
+            seqs = _translate_all_frames(table, self._seq)
+
+        return [ProteinSequence(seq) for seq in seqs]
 
     def reverse_complement(self, strict: bool = False) -> "DnaSequence":
         """
@@ -200,5 +201,64 @@ class DnaSequence(BaseSequence):
             seq = _reverse_complement(self._seq)
         return DnaSequence(seq)
 
+    def gc_content(self) -> float:
+        """
+        Returns the fraction of this DNA sequence that is G or C, or 0 if it's empty.
+
+        Ambiguous nucleotides count fractionally by the share of their possibilities
+        that are G or C; for example, `S` counts as 1 and `N` as 0.5.
+
+        Raises ValueError if any character in this sequence is an invalid nucleotide.
+        """
+
+        return _gc_content(self._seq)
+
+    def base_counts(self) -> ty.Dict[str, int]:
+        """
+        Counts occurrences of each nucleotide (or IUPAC ambiguity code) in this sequence.
+
+        Raises ValueError if any character in this sequence is an invalid nucleotide.
+        """
+
+        return _base_counts(self._seq)
+
+
+class FastaRecord(ty.NamedTuple):
+    """A record parsed from a FASTA file."""
+
+    header: str
+    """The header of this record, without the leading '>' (or ';')."""
+    contents: bytes
+    """The contents of this record, with line breaks removed."""
+    line_range: ty.Tuple[int, int]
+    """The 1-indexed lines this record spans, start inclusive, end exclusive."""
+
+
+def parse_fasta(
+    data: ty.Union[str, bytes],
+    concatenate_headers: bool = True,
+    allow_preceding_comment: bool = False,
+) -> ty.List[FastaRecord]:
+    """
+    Parse FASTA-formatted data into a list of records.
+
+    If `concatenate_headers` is true, successive headers are joined with newlines
+    instead of producing empty records. If `allow_preceding_comment` is true, content
+    before the first header is ignored; otherwise it becomes a record with an empty header.
+
+    Raises ValueError if the data can't be parsed.
+    """
+
+    records = _parse_fasta(
+        ensure_bytes(data), concatenate_headers, allow_preceding_comment
+    )
+    return [FastaRecord(*record) for record in records]
+
 
-__all__ = ["BaseSequence", "DnaSequence", "ProteinSequence"]
+__all__ = [
+    "BaseSequence",
+    "DnaSequence",
+    "FastaRecord",
+    "ProteinSequence",
+    "parse_fasta",
+]
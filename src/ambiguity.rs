@@ -0,0 +1,141 @@
+// Copyright 2021-2024 SecureDNA Stiftung (SecureDNA Foundation) <licensing@securedna.org>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Policies for translating codons that could code for more than one amino acid.
+//!
+//! [`DnaSequence::translate`](crate::DnaSequence::translate) translates an ambiguous codon
+//! like `GCN` to the amino acid shared by all its expansions (here `A`), but collapses a
+//! codon whose expansions disagree to an ambiguity code: `B` for `D`/`N` (like `RAY`), `Z`
+//! for `E`/`Q`, `J` for `I`/`L`, and `X` for anything else (like `NNN`).
+//! [`DnaSequenceAmbiguous::translate_with`] lets callers choose what happens instead.
+
+use smallvec::SmallVec;
+
+use crate::{
+    BaseSequence, CodonAmbiguous, DnaSequenceAmbiguous, Positioned, ProteinSequence,
+    TranslationError, TranslationTable,
+};
+
+/// How [`DnaSequenceAmbiguous::translate_with`] handles ambiguous codons, and what it
+/// returns.
+pub trait AmbiguityPolicy {
+    type Output;
+
+    /// Translate the first reading frame of `dna` with `table` under this policy.
+    fn translate(&self, dna: &DnaSequenceAmbiguous, table: TranslationTable) -> Self::Output;
+}
+
+/// Translate codons whose expansions disagree to `B`, `Z`, `J` or `X`, like
+/// [`DnaSequence::translate`](crate::DnaSequence::translate).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CollapseToX;
+
+/// Fail on the first codon whose expansions disagree, with
+/// [`TranslationError::AmbiguousCodon`] positioned at the start of that codon.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Reject;
+
+/// Return every amino acid each codon could code for, in ascending ASCII order.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllPossible;
+
+impl AmbiguityPolicy for CollapseToX {
+    type Output = ProteinSequence;
+
+    fn translate(&self, dna: &DnaSequenceAmbiguous, table: TranslationTable) -> Self::Output {
+        dna.translate(table)
+    }
+}
+
+impl AmbiguityPolicy for Reject {
+    type Output = Result<ProteinSequence, Positioned<TranslationError>>;
+
+    fn translate(&self, dna: &DnaSequenceAmbiguous, table: TranslationTable) -> Self::Output {
+        let protein = dna.translate(table);
+        // Unambiguous codons never translate to an ambiguity code.
+        let ambiguous = protein
+            .as_slice()
+            .iter()
+            .position(|aa| matches!(aa, b'B' | b'Z' | b'J' | b'X'));
+        match ambiguous {
+            Some(i) => {
+                let codon = &dna.as_slice()[3 * i..3 * i + 3];
+                let codon = CodonAmbiguous([codon[0], codon[1], codon[2]]);
+                Err(Positioned {
+                    position: 3 * i,
+                    error: TranslationError::AmbiguousCodon(codon.to_string()),
+                })
+            }
+            None => Ok(protein),
+        }
+    }
+}
+
+impl AmbiguityPolicy for AllPossible {
+    type Output = Vec<SmallVec<[u8; 3]>>;
+
+    fn translate(&self, dna: &DnaSequenceAmbiguous, table: TranslationTable) -> Self::Output {
+        let translate = table.to_fn();
+        dna.codons()
+            .map(|codon| {
+                let mut residues: SmallVec<[u8; 3]> =
+                    codon.possibilities().map(&translate).collect();
+                residues.sort_unstable();
+                residues.dedup();
+                residues
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dna(dna: &str) -> DnaSequenceAmbiguous {
+        dna.parse().unwrap()
+    }
+
+    #[test]
+    fn test_policies() {
+        let table = TranslationTable::Ncbi1;
+        let seq = dna("ATGGCNTRGCTNCA");
+
+        let collapsed = seq.translate_with(table, CollapseToX);
+        assert_eq!(collapsed.to_string(), "MAXL");
+
+        let err = seq.translate_with(table, Reject).unwrap_err();
+        assert_eq!(err.position, 6);
+        assert!(matches!(err.error, TranslationError::AmbiguousCodon(c) if c == "TRG"));
+        let err = dna("GCNRAY").translate_with(table, Reject).unwrap_err();
+        assert_eq!(err.position, 3);
+        let unambiguous = dna("ATGGCNCTN");
+        assert_eq!(
+            unambiguous.translate_with(table, Reject).unwrap(),
+            unambiguous.translate(table)
+        );
+
+        let all = seq.translate_with(table, AllPossible);
+        let all: Vec<&[u8]> = all.iter().map(|r| &r[..]).collect();
+        assert_eq!(all, [&b"M"[..], b"A", b"*W", b"L"]);
+    }
+
+    #[test]
+    #[cfg(not(feature = "minimal-tables"))]
+    fn test_all_possible_agrees_with_collapse() {
+        let table = TranslationTable::Ncbi11;
+        let seq = dna("NNNRAYMGNKGGSTABHDVAA");
+        let collapsed = seq.translate_with(table, CollapseToX);
+        let all = seq.translate_with(table, AllPossible);
+        assert_eq!(collapsed.len(), all.len());
+        for (&aa, residues) in collapsed.as_slice().iter().zip(&all) {
+            match residues.as_slice() {
+                [single] => assert_eq!(*single, aa),
+                b"DN" => assert_eq!(aa, b'B'),
+                b"EQ" => assert_eq!(aa, b'Z'),
+                b"IL" => assert_eq!(aa, b'J'),
+                _ => assert_eq!(aa, b'X'),
+            }
+        }
+    }
+}
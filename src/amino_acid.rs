@@ -0,0 +1,262 @@
+// Copyright 2021-2024 SecureDNA Stiftung (SecureDNA Foundation) <licensing@securedna.org>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+use std::fmt::{self, Write};
+
+use crate::errors::TranslationError;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// One of the 20 standard amino acids, named by its one-letter code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, std::hash::Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[repr(u8)]
+pub enum AminoAcid {
+    A = b'A',
+    C = b'C',
+    D = b'D',
+    E = b'E',
+    F = b'F',
+    G = b'G',
+    H = b'H',
+    I = b'I',
+    K = b'K',
+    L = b'L',
+    M = b'M',
+    N = b'N',
+    P = b'P',
+    Q = b'Q',
+    R = b'R',
+    S = b'S',
+    T = b'T',
+    V = b'V',
+    W = b'W',
+    Y = b'Y',
+}
+
+/// Broad chemical class of an amino acid's side chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, std::hash::Hash)]
+pub enum AminoAcidClass {
+    Nonpolar,
+    Polar,
+    Acidic,
+    Basic,
+}
+
+const fn ascii_to_amino_acid_table() -> [Option<AminoAcid>; 256] {
+    let mut table = [None; 256];
+    let mut i = 0;
+    while i < AminoAcid::ALL.len() {
+        let aa = AminoAcid::ALL[i];
+        table[aa as usize] = Some(aa);
+        table[(aa as u8).to_ascii_lowercase() as usize] = Some(aa);
+        i += 1;
+    }
+    table
+}
+
+const ASCII_TO_AMINO_ACID: [Option<AminoAcid>; 256] = ascii_to_amino_acid_table();
+
+impl AminoAcid {
+    pub const ALL: [Self; 20] = [
+        Self::A,
+        Self::C,
+        Self::D,
+        Self::E,
+        Self::F,
+        Self::G,
+        Self::H,
+        Self::I,
+        Self::K,
+        Self::L,
+        Self::M,
+        Self::N,
+        Self::P,
+        Self::Q,
+        Self::R,
+        Self::S,
+        Self::T,
+        Self::V,
+        Self::W,
+        Self::Y,
+    ];
+
+    /// The uppercase one-letter code of this amino acid.
+    pub fn to_ascii(self) -> u8 {
+        self as u8
+    }
+
+    /// The three-letter code of this amino acid, e.g. `"Ala"`.
+    pub fn three_letter(&self) -> &'static str {
+        self.names().0
+    }
+
+    /// The full name of this amino acid, e.g. `"alanine"`.
+    pub fn full_name(&self) -> &'static str {
+        self.names().1
+    }
+
+    /// Parse a three-letter code, ignoring case.
+    ///
+    /// ```
+    /// use quickdna::AminoAcid;
+    ///
+    /// assert_eq!(AminoAcid::from_three_letter("Trp"), Some(AminoAcid::W));
+    /// assert_eq!(AminoAcid::from_three_letter("TRP"), Some(AminoAcid::W));
+    /// assert_eq!(AminoAcid::from_three_letter("Xaa"), None);
+    /// ```
+    pub fn from_three_letter(code: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|aa| aa.three_letter().eq_ignore_ascii_case(code))
+    }
+
+    /// Whether this amino acid is hydrophobic, i.e. has a positive Kyte-Doolittle
+    /// hydropathy index: `I`, `V`, `L`, `F`, `C`, `M` or `A`.
+    pub fn is_hydrophobic(&self) -> bool {
+        matches!(
+            self,
+            Self::I | Self::V | Self::L | Self::F | Self::C | Self::M | Self::A
+        )
+    }
+
+    /// Net charge of the side chain at pH 7: `-1` for `D` and `E`, `+1` for `K` and `R`,
+    /// and `0` otherwise (including `H`, which is mostly uncharged at pH 7).
+    pub fn charge_at_ph7(&self) -> i8 {
+        match self {
+            Self::D | Self::E => -1,
+            Self::K | Self::R => 1,
+            _ => 0,
+        }
+    }
+
+    /// The chemical class of this amino acid's side chain.
+    pub fn class(&self) -> AminoAcidClass {
+        match self {
+            Self::D | Self::E => AminoAcidClass::Acidic,
+            Self::K | Self::R | Self::H => AminoAcidClass::Basic,
+            Self::S | Self::T | Self::C | Self::Y | Self::N | Self::Q => AminoAcidClass::Polar,
+            Self::G
+            | Self::A
+            | Self::V
+            | Self::L
+            | Self::I
+            | Self::M
+            | Self::F
+            | Self::W
+            | Self::P => AminoAcidClass::Nonpolar,
+        }
+    }
+
+    fn names(&self) -> (&'static str, &'static str) {
+        match self {
+            Self::A => ("Ala", "alanine"),
+            Self::C => ("Cys", "cysteine"),
+            Self::D => ("Asp", "aspartic acid"),
+            Self::E => ("Glu", "glutamic acid"),
+            Self::F => ("Phe", "phenylalanine"),
+            Self::G => ("Gly", "glycine"),
+            Self::H => ("His", "histidine"),
+            Self::I => ("Ile", "isoleucine"),
+            Self::K => ("Lys", "lysine"),
+            Self::L => ("Leu", "leucine"),
+            Self::M => ("Met", "methionine"),
+            Self::N => ("Asn", "asparagine"),
+            Self::P => ("Pro", "proline"),
+            Self::Q => ("Gln", "glutamine"),
+            Self::R => ("Arg", "arginine"),
+            Self::S => ("Ser", "serine"),
+            Self::T => ("Thr", "threonine"),
+            Self::V => ("Val", "valine"),
+            Self::W => ("Trp", "tryptophan"),
+            Self::Y => ("Tyr", "tyrosine"),
+        }
+    }
+}
+
+impl TryFrom<u8> for AminoAcid {
+    type Error = TranslationError;
+
+    #[inline(always)]
+    fn try_from(u: u8) -> Result<Self, Self::Error> {
+        if u >= 128 {
+            return Err(TranslationError::NonAsciiByte(u));
+        }
+
+        ASCII_TO_AMINO_ACID[u as usize].ok_or(TranslationError::BadAminoAcid(u.into()))
+    }
+}
+
+impl From<AminoAcid> for u8 {
+    fn from(aa: AminoAcid) -> Self {
+        aa.to_ascii()
+    }
+}
+
+impl From<AminoAcid> for char {
+    fn from(aa: AminoAcid) -> Self {
+        aa.to_ascii() as char
+    }
+}
+
+impl fmt::Display for AminoAcid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_char((*self).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_three_letter_round_trip() {
+        for aa in AminoAcid::ALL {
+            assert_eq!(AminoAcid::from_three_letter(aa.three_letter()), Some(aa));
+            assert_eq!(
+                AminoAcid::from_three_letter(&aa.three_letter().to_lowercase()),
+                Some(aa)
+            );
+        }
+        assert_eq!(AminoAcid::G.full_name(), "glycine");
+        assert_eq!(AminoAcid::from_three_letter("Gl"), None);
+    }
+
+    #[test]
+    fn test_properties() {
+        let net_charge: i8 = AminoAcid::ALL.iter().map(|aa| aa.charge_at_ph7()).sum();
+        assert_eq!(net_charge, 0);
+        for aa in AminoAcid::ALL {
+            if aa.charge_at_ph7() < 0 {
+                assert_eq!(aa.class(), AminoAcidClass::Acidic);
+            }
+            if aa.charge_at_ph7() > 0 {
+                assert_eq!(aa.class(), AminoAcidClass::Basic);
+            }
+        }
+        assert!(AminoAcid::L.is_hydrophobic());
+        assert!(!AminoAcid::K.is_hydrophobic());
+    }
+
+    #[test]
+    fn test_ascii_round_trip() {
+        for aa in AminoAcid::ALL {
+            assert_eq!(AminoAcid::try_from(aa.to_ascii()).unwrap(), aa);
+            assert_eq!(
+                AminoAcid::try_from(aa.to_ascii().to_ascii_lowercase()).unwrap(),
+                aa
+            );
+        }
+        for invalid in [b'B', b'J', b'O', b'U', b'X', b'Z', b'*', b'-'] {
+            assert!(matches!(
+                AminoAcid::try_from(invalid),
+                Err(TranslationError::BadAminoAcid(c)) if c == invalid as char
+            ));
+        }
+        assert!(matches!(
+            AminoAcid::try_from(200),
+            Err(TranslationError::NonAsciiByte(200))
+        ));
+    }
+}
@@ -0,0 +1,333 @@
+// Copyright 2021-2024 SecureDNA Stiftung (SecureDNA Foundation) <licensing@securedna.org>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! DNA sequences stored as validated ASCII.
+//!
+//! A [`DnaSequence`] stores one enum per nucleotide, so pipelines that read ASCII and write
+//! ASCII pay for converting every byte in and back out. An [`AsciiDna`] is validated once, on
+//! construction, and then keeps its bytes as they are: displaying or serializing it is a
+//! plain copy. Convert to a [`DnaSequence`] when you need nucleotide semantics.
+
+use std::fmt;
+use std::ops::Index;
+use std::str::FromStr;
+
+use crate::{
+    DnaSequence, Nucleotide, NucleotideAmbiguous, NucleotideLike, Positioned, TranslationError,
+    TranslationTable,
+};
+
+/// Each uppercase IUPAC code, with the code of its complement and its
+/// [`bits`](NucleotideLike::bits), so that [`AsciiDna`] can work on bytes directly.
+const IUPAC_CODES: [(u8, u8, NucleotideAmbiguous); 15] = [
+    (b'A', b'T', NucleotideAmbiguous::A),
+    (b'T', b'A', NucleotideAmbiguous::T),
+    (b'W', b'W', NucleotideAmbiguous::W),
+    (b'C', b'G', NucleotideAmbiguous::C),
+    (b'M', b'K', NucleotideAmbiguous::M),
+    (b'Y', b'R', NucleotideAmbiguous::Y),
+    (b'H', b'D', NucleotideAmbiguous::H),
+    (b'G', b'C', NucleotideAmbiguous::G),
+    (b'R', b'Y', NucleotideAmbiguous::R),
+    (b'K', b'M', NucleotideAmbiguous::K),
+    (b'D', b'H', NucleotideAmbiguous::D),
+    (b'S', b'S', NucleotideAmbiguous::S),
+    (b'V', b'B', NucleotideAmbiguous::V),
+    (b'B', b'V', NucleotideAmbiguous::B),
+    (b'N', b'N', NucleotideAmbiguous::N),
+];
+
+// Like the packing tables in `nucleotide.rs`, these have 256 entries so that indexing them
+// with any byte needs no bounds check. Bytes that can't be in an `AsciiDna` map to 0.
+
+const fn ascii_complement_table() -> [u8; 256] {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < IUPAC_CODES.len() {
+        let (code, complement, _) = IUPAC_CODES[i];
+        table[code as usize] = complement;
+        i += 1;
+    }
+    table
+}
+
+const ASCII_COMPLEMENT: [u8; 256] = ascii_complement_table();
+
+const fn ascii_bits_table() -> [u8; 256] {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < IUPAC_CODES.len() {
+        let (code, _, nucleotide) = IUPAC_CODES[i];
+        table[code as usize] = nucleotide as u8;
+        i += 1;
+    }
+    table
+}
+
+const ASCII_BITS: [u8; 256] = ascii_bits_table();
+
+/// A DNA sequence of uppercase IUPAC nucleotide codes, stored as ASCII.
+///
+/// Lowercase codes are uppercased on construction, and anything else (including whitespace)
+/// is rejected.
+///
+/// # Examples
+///
+/// ```
+/// use quickdna::{ascii::AsciiDna, DnaSequenceStrict, TranslationTable};
+///
+/// let dna: AsciiDna = "gattacaNN".parse().unwrap();
+/// assert_eq!(dna.as_bytes(), b"GATTACANN");
+/// assert_eq!(dna.reverse_complement().as_str(), "NNTGTAATC");
+/// assert_eq!(dna.translate(TranslationTable::Ncbi1), b"DYX");
+///
+/// // Converting to a strict sequence fails on the ambiguous codes.
+/// let err = DnaSequenceStrict::try_from(&dna).unwrap_err();
+/// assert_eq!(err.position, 7);
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, std::hash::Hash)]
+pub struct AsciiDna {
+    bytes: Vec<u8>,
+}
+
+impl AsciiDna {
+    /// Validate `bytes` as IUPAC nucleotide codes, uppercasing them in place.
+    ///
+    /// On error, the position is the byte offset of the offending byte in `bytes`.
+    pub fn new(mut bytes: Vec<u8>) -> Result<Self, Positioned<TranslationError>> {
+        for (position, &byte) in bytes.iter().enumerate() {
+            NucleotideAmbiguous::try_from(byte).map_err(|error| Positioned { position, error })?;
+        }
+        bytes.make_ascii_uppercase();
+        Ok(Self { bytes })
+    }
+
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    pub fn as_str(&self) -> &str {
+        std::str::from_utf8(&self.bytes).expect("AsciiDna holds only ASCII")
+    }
+
+    /// Iterate over the nucleotides, as ASCII bytes.
+    pub fn iter(&self) -> impl Iterator<Item = u8> + '_ {
+        self.bytes.iter().copied()
+    }
+
+    /// The subsequence in `range`, which is still valid so needs no checking.
+    pub fn slice(&self, range: std::ops::Range<usize>) -> Self {
+        Self {
+            bytes: self.bytes[range].to_vec(),
+        }
+    }
+
+    /// The reverse complement of this sequence, with ambiguity codes complemented too (e.g.
+    /// `R` becomes `Y`).
+    pub fn reverse_complement(&self) -> Self {
+        let bytes = self
+            .bytes
+            .iter()
+            .rev()
+            .map(|&b| ASCII_COMPLEMENT[b as usize])
+            .collect();
+        Self { bytes }
+    }
+
+    /// Translate this sequence in its first reading frame, into ASCII amino acids. Ambiguous
+    /// codons translate to `X` (or a more specific code where all possibilities agree), and a
+    /// trailing partial codon is ignored.
+    pub fn translate(&self, table: TranslationTable) -> Vec<u8> {
+        let codons = table.codon_lookup();
+        self.bytes
+            .chunks_exact(3)
+            .map(|codon| {
+                let [n0, n1, n2] = [0, 1, 2].map(|i| usize::from(ASCII_BITS[codon[i] as usize]));
+                codons[(n0 << 8) | (n1 << 4) | n2]
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for AsciiDna {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl TryFrom<Vec<u8>> for AsciiDna {
+    type Error = Positioned<TranslationError>;
+
+    fn try_from(value: Vec<u8>) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl TryFrom<&[u8]> for AsciiDna {
+    type Error = Positioned<TranslationError>;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        Self::new(value.to_vec())
+    }
+}
+
+impl FromStr for AsciiDna {
+    type Err = Positioned<TranslationError>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s.as_bytes())
+    }
+}
+
+impl AsRef<[u8]> for AsciiDna {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl Index<usize> for AsciiDna {
+    type Output = u8;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.bytes[index]
+    }
+}
+
+impl From<AsciiDna> for String {
+    fn from(dna: AsciiDna) -> Self {
+        String::from_utf8(dna.bytes).expect("AsciiDna holds only ASCII")
+    }
+}
+
+impl From<&DnaSequence<Nucleotide>> for AsciiDna {
+    fn from(dna: &DnaSequence<Nucleotide>) -> Self {
+        Self {
+            bytes: dna.iter().map(u8::from).collect(),
+        }
+    }
+}
+
+impl From<&DnaSequence<NucleotideAmbiguous>> for AsciiDna {
+    fn from(dna: &DnaSequence<NucleotideAmbiguous>) -> Self {
+        Self {
+            bytes: dna.iter().map(u8::from).collect(),
+        }
+    }
+}
+
+/// Fails if some nucleotide isn't representable as a `T`, e.g. an ambiguity code in a
+/// [`DnaSequenceStrict`](crate::DnaSequenceStrict).
+impl<T: NucleotideLike> TryFrom<&AsciiDna> for DnaSequence<T> {
+    type Error = Positioned<TranslationError>;
+
+    fn try_from(value: &AsciiDna) -> Result<Self, Self::Error> {
+        Ok(Self::new(T::try_pack_ascii(&value.bytes)?))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for AsciiDna {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use crate::serde_utils::FromStrVisitor;
+        deserializer.deserialize_str(FromStrVisitor(std::marker::PhantomData))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for AsciiDna {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use quickcheck::quickcheck;
+
+    use crate::{DnaSequenceAmbiguous, DnaSequenceStrict};
+
+    #[test]
+    fn test_ascii_dna_validation() {
+        let dna = AsciiDna::try_from(&b"acgtRYkmBDHVNsw"[..]).unwrap();
+        assert_eq!(dna.to_string(), "ACGTRYKMBDHVNSW");
+        assert_eq!(dna.len(), 15);
+        assert_eq!(dna[4], b'R');
+        assert_eq!(AsciiDna::default().as_str(), "");
+
+        let err = "GAT TACA".parse::<AsciiDna>().unwrap_err();
+        assert_eq!(err.position, 3);
+        assert_eq!(err.error, TranslationError::BadNucleotide(' '));
+        let err = AsciiDna::new(b"GA-T".to_vec()).unwrap_err();
+        assert_eq!(err.position, 2);
+        let err = AsciiDna::new(b"GA\xe9".to_vec()).unwrap_err();
+        assert_eq!(err.error, TranslationError::NonAsciiByte(0xe9));
+    }
+
+    #[test]
+    fn test_ascii_dna_conversions() {
+        let dna: AsciiDna = "ACGTN".parse().unwrap();
+        assert_eq!(
+            DnaSequenceAmbiguous::try_from(&dna).unwrap().to_string(),
+            "ACGTN"
+        );
+        let err = DnaSequenceStrict::try_from(&dna).unwrap_err();
+        assert_eq!(err.position, 4);
+        assert_eq!(
+            err.error,
+            TranslationError::UnexpectedAmbiguousNucleotide('N')
+        );
+        assert_eq!(dna.slice(1..3).as_str(), "CG");
+        assert_eq!(String::from(dna), "ACGTN");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_json() {
+        let dna: AsciiDna = "acgtn".parse().unwrap();
+        assert_eq!(
+            serde_json::to_value(&dna).unwrap(),
+            serde_json::json!("ACGTN")
+        );
+        assert_eq!(
+            serde_json::from_value::<AsciiDna>(serde_json::json!("acgtn")).unwrap(),
+            dna
+        );
+        assert!(serde_json::from_value::<AsciiDna>(serde_json::json!("ACGU")).is_err());
+    }
+
+    quickcheck! {
+        fn ascii_dna_matches_dna_sequence(dna: DnaSequenceAmbiguous, table: u8) -> bool {
+            let tables = TranslationTable::AVAILABLE;
+            let table = tables[table as usize % tables.len()];
+            let ascii = AsciiDna::from(&dna);
+            ascii.as_str() == dna.to_string()
+                && DnaSequenceAmbiguous::try_from(&ascii).unwrap() == dna
+                && AsciiDna::from(&dna.reverse_complement()) == ascii.reverse_complement()
+                && ascii.translate(table) == dna.translate(table).to_string().into_bytes()
+        }
+
+        fn ascii_dna_strict_round_trips(dna: DnaSequenceStrict) -> bool {
+            DnaSequenceStrict::try_from(&AsciiDna::from(&dna)).unwrap() == dna
+        }
+    }
+}
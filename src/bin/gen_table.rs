@@ -153,6 +153,20 @@ fn gen_translation_tables() -> [u8; TranslationTable::LOOKUP_SIZE] {
         }
     }
 
+    // setup gapped codons: a gap has no nucleotide bits, so any codon with a zero nibble
+    // contains one, and translates to a gap
+    for codon_idx in 0..TranslationTable::CODONS_PER_TABLE {
+        if [8, 4, 0]
+            .iter()
+            .any(|shift| (codon_idx >> shift) & 0xf == 0)
+        {
+            for table_idx in 0..TranslationTable::N_TRANS_TABLES {
+                translation_tables[table_idx * TranslationTable::CODONS_PER_TABLE + codon_idx] =
+                    b'-';
+            }
+        }
+    }
+
     translation_tables
 }
 
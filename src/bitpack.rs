@@ -0,0 +1,84 @@
+// Copyright 2021-2024 SecureDNA Stiftung (SecureDNA Foundation) <licensing@securedna.org>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Packing nucleotides into bytes, shared by the binary serialization formats.
+//!
+//! [`Nucleotide`]s take 2 bits each (in the order of [`Nucleotide::ALL`]), and
+//! [`NucleotideAmbiguous`]s take 4 bits each (their [`NucleotideLike::bits`]). The first
+//! nucleotide is stored in the most significant bits of the first byte, and any unused
+//! trailing bits are zero.
+
+use crate::kmer::{decode, encode};
+use crate::{Nucleotide, NucleotideAmbiguous, NucleotideLike};
+
+/// A nucleotide type with a fixed-width packed form.
+pub(crate) trait BitPacked: Copy {
+    /// Bits per nucleotide, which divides 8.
+    const WIDTH: usize;
+
+    fn pack(self) -> u8;
+    fn unpack(bits: u8) -> Option<Self>;
+}
+
+impl BitPacked for Nucleotide {
+    const WIDTH: usize = 2;
+
+    fn pack(self) -> u8 {
+        encode(self) as u8
+    }
+
+    fn unpack(bits: u8) -> Option<Self> {
+        Some(decode(bits.into()))
+    }
+}
+
+impl BitPacked for NucleotideAmbiguous {
+    const WIDTH: usize = 4;
+
+    fn pack(self) -> u8 {
+        self.bits()
+    }
+
+    fn unpack(bits: u8) -> Option<Self> {
+        NucleotideAmbiguous::ALL
+            .into_iter()
+            .find(|&n| n as u8 == bits)
+    }
+}
+
+/// Why packed bytes couldn't be unpacked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum UnpackError {
+    /// The number of bytes doesn't match the number of nucleotides.
+    Length(usize),
+    /// Some bits aren't a nucleotide.
+    Bits(u8),
+}
+
+pub(crate) fn pack<T: BitPacked>(dna: &[T]) -> Vec<u8> {
+    let per_byte = 8 / T::WIDTH;
+    dna.chunks(per_byte)
+        .map(|chunk| {
+            let byte = chunk
+                .iter()
+                .fold(0, |byte, &nuc| (byte << T::WIDTH) | nuc.pack());
+            byte << (T::WIDTH * (per_byte - chunk.len()))
+        })
+        .collect()
+}
+
+pub(crate) fn unpack<T: BitPacked>(len: usize, bytes: &[u8]) -> Result<Vec<T>, UnpackError> {
+    let per_byte = 8 / T::WIDTH;
+    if bytes.len() != len.div_ceil(per_byte) {
+        return Err(UnpackError::Length(bytes.len()));
+    }
+
+    let mask = (1 << T::WIDTH) - 1;
+    let mut dna = Vec::with_capacity(len);
+    for i in 0..len {
+        let shift = T::WIDTH * (per_byte - 1 - i % per_byte);
+        let bits = (bytes[i / per_byte] >> shift) & mask;
+        dna.push(T::unpack(bits).ok_or(UnpackError::Bits(bits))?);
+    }
+    Ok(dna)
+}
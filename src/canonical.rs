@@ -1,9 +1,13 @@
 // Copyright 2021-2024 SecureDNA Stiftung (SecureDNA Foundation) <licensing@securedna.org>
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
-use std::cmp::Ordering;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
 
-use crate::Nucleotide;
+use crate::expansions::Expansions;
+#[cfg(feature = "std")]
+use crate::DnaSequenceStrict;
+use crate::{Nucleotide, NucleotideAmbiguous};
 
 /// Permute bases (and maybe reverse sequence) to produce lexical-minimum substitution of DNA.
 ///
@@ -15,7 +19,7 @@ use crate::Nucleotide;
 /// Thus, two [`Nucleotide`] sequence have the same canonical form if-and-only-if one is
 /// isomorphic to the other (or its reverse). Canonicalization is idempotent.
 #[derive(Clone, Debug)]
-pub struct Canonical<I>(LexicalMin<ForwardCanonical<I>, ForwardCanonical<std::iter::Rev<I>>>);
+pub struct Canonical<I>(LexicalMin<ForwardCanonical<I>, ForwardCanonical<core::iter::Rev<I>>>);
 
 impl<I> Canonical<I>
 where
@@ -54,6 +58,113 @@ impl<I> ExactSizeIterator for Canonical<I> where
 {
 }
 
+/// A [`DnaSequenceStrict`] that is known to be in [`Canonical`] form.
+///
+/// Converting a sequence canonicalizes it once, so comparing and hashing are then as cheap as
+/// for the sequence itself. Two sequences convert to equal [`CanonicalDna`] if-and-only-if
+/// one is isomorphic to the other (or its reverse), which makes this a good key for
+/// deduplicating sequences up to isomorphism and reverse complement.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashSet;
+/// use quickdna::{canonical::CanonicalDna, DnaSequenceStrict};
+///
+/// let seqs: HashSet<CanonicalDna> = ["GATTACA", "TGTAATC", "CTAATGT", "GATTACC"]
+///     .into_iter()
+///     .map(|s| s.parse::<DnaSequenceStrict>().unwrap().into())
+///     .collect();
+/// assert_eq!(seqs.len(), 2);
+/// assert!(seqs.contains(&"ACATTAG".parse::<DnaSequenceStrict>().unwrap().into()));
+/// ```
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CanonicalDna(DnaSequenceStrict);
+
+#[cfg(feature = "std")]
+impl CanonicalDna {
+    /// The canonical sequence.
+    pub fn as_dna(&self) -> &DnaSequenceStrict {
+        &self.0
+    }
+
+    /// Consume this, returning the canonical sequence.
+    pub fn into_dna(self) -> DnaSequenceStrict {
+        self.0
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<DnaSequenceStrict> for CanonicalDna {
+    fn from(dna: DnaSequenceStrict) -> Self {
+        Self::from(&dna)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<&DnaSequenceStrict> for CanonicalDna {
+    fn from(dna: &DnaSequenceStrict) -> Self {
+        Self(dna.canonical())
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<CanonicalDna> for DnaSequenceStrict {
+    fn from(canonical: CanonicalDna) -> Self {
+        canonical.0
+    }
+}
+
+#[cfg(feature = "std")]
+impl AsRef<DnaSequenceStrict> for CanonicalDna {
+    fn as_ref(&self) -> &DnaSequenceStrict {
+        &self.0
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::fmt::Display for CanonicalDna {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Canonicalize ambiguous DNA against all of its unambiguous expansions.
+///
+/// Returns the lexical minimum of the [`Canonical`] sequences of every expansion of `dna`,
+/// so it agrees with [`Canonical`] on unambiguous input. Two ambiguous sequences share a
+/// result if-and-only-if the least-canonical of their expansions are isomorphic.
+///
+/// This visits every expansion, so it takes time exponential in the number of ambiguities;
+/// check [`DnaSequenceAmbiguous::expansion_count`](crate::DnaSequenceAmbiguous::expansion_count)
+/// first if the input is untrusted.
+///
+/// # Examples
+///
+/// ```
+/// use quickdna::{canonical::canonicalize_ambiguous, DnaSequenceAmbiguous, DnaSequenceStrict};
+///
+/// // Expands to CAT, CTT, CCT, CGT; CTT is isomorphic to ATT, which is canonically AAT.
+/// let dna: DnaSequenceAmbiguous = "CNT".parse().unwrap();
+/// let expected: DnaSequenceStrict = "AAT".parse().unwrap();
+/// assert_eq!(canonicalize_ambiguous(dna.as_ref()), expected.as_ref());
+/// ```
+pub fn canonicalize_ambiguous(dna: &[NucleotideAmbiguous]) -> Vec<Nucleotide> {
+    let mut expansions = Expansions::new(dna);
+    // There's always at least one expansion.
+    let first = expansions.next().unwrap();
+    let mut best: Vec<_> = Canonical::new(first.iter().copied()).collect();
+    for expansion in expansions {
+        let candidate = Canonical::new(expansion.iter().copied());
+        if candidate.clone().lt(best.iter().copied()) {
+            best.clear();
+            best.extend(candidate);
+        }
+    }
+    best
+}
+
 /// Permute bases to produce lexical-minimum substitution of DNA.
 ///
 /// This returns a sequence of [`Nucleotide`]s that is:
@@ -72,7 +183,7 @@ impl<I> ExactSizeIterator for Canonical<I> where
 pub struct ForwardCanonical<I> {
     inner: I,
     permutation: [Option<Nucleotide>; 4],
-    unmapped: std::slice::Iter<'static, Nucleotide>,
+    unmapped: core::slice::Iter<'static, Nucleotide>,
 }
 
 impl<I> ForwardCanonical<I>
@@ -99,12 +210,7 @@ where
     type Item = Nucleotide;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let idx = match self.inner.next()? {
-            Nucleotide::A => 0,
-            Nucleotide::T => 1,
-            Nucleotide::C => 2,
-            Nucleotide::G => 3,
-        };
+        let idx = nucleotide_index(self.inner.next()?);
         let nuc = *self.permutation[idx]
             .get_or_insert_with(|| self.unmapped.next().copied().unwrap_or(Nucleotide::A));
         Some(nuc)
@@ -117,6 +223,95 @@ where
 
 impl<I: ExactSizeIterator<Item = Nucleotide>> ExactSizeIterator for ForwardCanonical<I> {}
 
+/// A permutation of the four [`Nucleotide`]s.
+///
+/// Applying a [`Remap`] to a sequence produces an isomorphic sequence, and the 24
+/// [`PERMUTATIONS`] produce every sequence isomorphic to it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Remap([Nucleotide; 4]);
+
+impl Remap {
+    /// The permutation that maps each nucleotide to itself.
+    pub const IDENTITY: Self = Self(Nucleotide::ALL);
+
+    /// Create a permutation mapping the nucleotides of [`Nucleotide::ALL`] to `images`,
+    /// or `None` if `images` has duplicates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quickdna::{canonical::Remap, Nucleotide::*};
+    ///
+    /// // Swap A and T
+    /// let remap = Remap::new([T, A, C, G]).unwrap();
+    /// assert_eq!(remap.apply(A), T);
+    /// assert_eq!(remap.remap(&[A, T, G]), [T, A, G]);
+    ///
+    /// assert_eq!(Remap::new([A, A, C, G]), None);
+    /// ```
+    pub const fn new(images: [Nucleotide; 4]) -> Option<Self> {
+        let mut seen = 0u8;
+        let mut i = 0;
+        while i < 4 {
+            seen |= images[i] as u8;
+            i += 1;
+        }
+        if seen == 0b1111 {
+            Some(Self(images))
+        } else {
+            None
+        }
+    }
+
+    /// Apply this permutation to a single nucleotide.
+    #[inline]
+    pub fn apply(self, nucleotide: Nucleotide) -> Nucleotide {
+        self.0[nucleotide_index(nucleotide)]
+    }
+
+    /// Apply this permutation to every nucleotide of `dna`.
+    pub fn remap(self, dna: &[Nucleotide]) -> Vec<Nucleotide> {
+        dna.iter().map(|&nuc| self.apply(nuc)).collect()
+    }
+}
+
+/// All 24 permutations of the four [`Nucleotide`]s, starting with [`Remap::IDENTITY`].
+pub const PERMUTATIONS: [Remap; 24] = all_permutations();
+
+const fn all_permutations() -> [Remap; 24] {
+    let all = Nucleotide::ALL;
+    let mut permutations = [Remap::IDENTITY; 24];
+    let mut n = 0;
+    let mut a = 0;
+    while a < 4 {
+        let mut b = 0;
+        while b < 4 {
+            let mut c = 0;
+            while c < 4 {
+                if a != b && b != c && a != c {
+                    let d = 6 - a - b - c;
+                    permutations[n] = Remap([all[a], all[b], all[c], all[d]]);
+                    n += 1;
+                }
+                c += 1;
+            }
+            b += 1;
+        }
+        a += 1;
+    }
+    permutations
+}
+
+#[inline]
+fn nucleotide_index(nucleotide: Nucleotide) -> usize {
+    match nucleotide {
+        Nucleotide::A => 0,
+        Nucleotide::T => 1,
+        Nucleotide::C => 2,
+        Nucleotide::G => 3,
+    }
+}
+
 // Given two sequences, returns whichever one is lexically less than the other.
 // This is like an allocation-free equivalent of:
 //     Vec::from_iter(iter1).min(iter2.collect()).into_iter()
@@ -184,7 +379,7 @@ mod test {
 
     use quickcheck::quickcheck;
 
-    use crate::{BaseSequence, DnaSequenceStrict};
+    use crate::{BaseSequence, DnaSequenceAmbiguous, DnaSequenceStrict};
 
     fn fw_canon(src_dna: &str) -> String {
         let dna: DnaSequenceStrict = src_dna.parse().unwrap();
@@ -411,7 +606,86 @@ mod test {
         assert_eq!(canon("ATCGCCAT"), "ATCCGCAT");
     }
 
+    #[test]
+    fn ambiguous_canonicalization_of_unambiguous_dna() {
+        let dna: DnaSequenceAmbiguous = "TGCGAGTGTAGCGAGATGTAGCGTAGAGTCTGAGATGCAGTA"
+            .parse()
+            .unwrap();
+        let canonical = DnaSequenceStrict::new(canonicalize_ambiguous(dna.as_slice()));
+        assert_eq!(
+            canonical.to_string(),
+            "ATCAGCTACACTGTCACATCGCATCTACACGCATCTCACGCT"
+        );
+        assert!(canonicalize_ambiguous(&[]).is_empty());
+    }
+
+    #[test]
+    fn ambiguous_canonicalization_picks_least_expansion() {
+        let canon_amb = |src_dna: &str| {
+            let dna: DnaSequenceAmbiguous = src_dna.parse().unwrap();
+            DnaSequenceStrict::new(canonicalize_ambiguous(dna.as_slice())).to_string()
+        };
+        assert_eq!(canon_amb("N"), "A");
+        assert_eq!(canon_amb("NN"), "AA");
+        assert_eq!(canon_amb("CNT"), "AAT");
+        assert_eq!(canon_amb("CAY"), "ATA");
+        assert_eq!(canon_amb("ATCS"), "AATC");
+    }
+
+    #[test]
+    fn permutations_are_distinct() {
+        assert_eq!(PERMUTATIONS[0], Remap::IDENTITY);
+        for (i, p1) in PERMUTATIONS.iter().enumerate() {
+            assert!(Remap::new(p1.0).is_some());
+            for p2 in &PERMUTATIONS[i + 1..] {
+                assert_ne!(p1, p2);
+            }
+        }
+    }
+
+    #[test]
+    fn canonical_dna_is_already_canonical() {
+        let dna: DnaSequenceStrict = "TGCGAGTGTAGCGAGATGTAGCGTAGAGTCTGAGATGCAGTA"
+            .parse()
+            .unwrap();
+        let canonical = CanonicalDna::from(&dna);
+        assert_eq!(
+            canonical.to_string(),
+            "ATCAGCTACACTGTCACATCGCATCTACACGCATCTCACGCT"
+        );
+        assert_eq!(CanonicalDna::from(canonical.as_dna()), canonical);
+        assert_eq!(
+            CanonicalDna::from(DnaSequenceStrict::default()).into_dna(),
+            DnaSequenceStrict::default()
+        );
+    }
+
     quickcheck! {
+        fn canonical_dna_equal_for_reverse_complement_and_permutations(dna: DnaSequenceStrict) -> bool {
+            let canonical = CanonicalDna::from(&dna);
+            canonical == CanonicalDna::from(dna.reverse_complement())
+                && dna.all_permutations().all(|p| CanonicalDna::from(p) == canonical)
+        }
+
+        fn permutations_preserve_forward_canonical(dna: DnaSequenceStrict) -> bool {
+            let fw_canonical = dna.forward_canonical();
+            dna.all_permutations().all(|p| p.forward_canonical() == fw_canonical)
+        }
+
+        fn ambiguous_canonicalization_is_min_over_expansions(dna: DnaSequenceAmbiguous) -> quickcheck::TestResult {
+            if dna.expansion_count().is_none_or(|count| count > 256) {
+                return quickcheck::TestResult::discard();
+            }
+            let expected = dna
+                .expansions()
+                .map(|expansion| expansion.to_dna().canonical())
+                .min()
+                .unwrap();
+            quickcheck::TestResult::from_bool(
+                canonicalize_ambiguous(dna.as_slice()) == expected.as_slice()
+            )
+        }
+
         fn forward_canonicalization_is_idempotent(dna: DnaSequenceStrict) -> bool {
             let canonical = ForwardCanonical::new(dna.as_slice().iter().copied());
             let canonical2 = ForwardCanonical::new(canonical.clone());
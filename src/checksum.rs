@@ -0,0 +1,46 @@
+// Copyright 2021-2024 SecureDNA Stiftung (SecureDNA Foundation) <licensing@securedna.org>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Encodings for sequence checksums.
+//!
+//! These are only used to compute standard sequence identifiers (see
+//! [`DnaSequence::seguid`](crate::DnaSequence::seguid)), with the digests themselves coming
+//! from the `md-5` and `sha1` crates behind the `md5` and `sha1` features.
+
+/// Lowercase hexadecimal encoding of `bytes`.
+#[cfg(feature = "md5")]
+pub(crate) fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Standard base64 encoding of `bytes`, without `=` padding.
+#[cfg(feature = "sha1")]
+pub(crate) fn base64_unpadded(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &b)| n | (u32::from(b) << (16 - 8 * i)));
+        for i in 0..=chunk.len() {
+            out.push(ALPHABET[(n >> (18 - 6 * i)) as usize & 63] as char);
+        }
+    }
+    out
+}
+
+#[cfg(all(test, feature = "sha1"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64() {
+        assert_eq!(base64_unpadded(b""), "");
+        assert_eq!(base64_unpadded(b"f"), "Zg");
+        assert_eq!(base64_unpadded(b"fo"), "Zm8");
+        assert_eq!(base64_unpadded(b"foo"), "Zm9v");
+        assert_eq!(base64_unpadded(b"foobar"), "Zm9vYmFy");
+        assert_eq!(base64_unpadded(&[0xfb, 0xff]), "+/8");
+    }
+}
@@ -0,0 +1,206 @@
+// Copyright 2021-2024 SecureDNA Stiftung (SecureDNA Foundation) <licensing@securedna.org>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Codon usage tables, for choosing between synonymous codons.
+
+use std::collections::HashMap;
+
+use crate::{Codon, DnaSequenceStrict, Nucleotide, TranslationTable};
+
+/// Relative weights of codons, e.g. how often each codon is used by a target organism.
+///
+/// Codons without a weight have a weight of 0.
+///
+/// # Examples
+///
+/// ```
+/// use quickdna::{codon_usage::CodonUsageTable, DnaSequenceStrict};
+///
+/// let reference: DnaSequenceStrict = "CTGCTGTTA".parse().unwrap();
+/// let usage = CodonUsageTable::from_dna(&reference);
+/// assert_eq!(usage.weight("CTG".parse().unwrap()), 2.0 / 3.0);
+/// assert_eq!(usage.weight("CTC".parse().unwrap()), 0.0);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CodonUsageTable {
+    weights: HashMap<Codon, f64>,
+}
+
+impl CodonUsageTable {
+    /// An empty table, where every codon has a weight of 0.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A table weighting each codon by its frequency in the first reading frame of `dna`.
+    pub fn from_dna(dna: &DnaSequenceStrict) -> Self {
+        Self {
+            weights: dna.codon_frequencies(0),
+        }
+    }
+
+    /// The weight of `codon`.
+    pub fn weight(&self, codon: Codon) -> f64 {
+        self.weights.get(&codon).copied().unwrap_or(0.0)
+    }
+
+    /// Changes the weight of `codon`.
+    pub fn set_weight(&mut self, codon: Codon, weight: f64) {
+        self.weights.insert(codon, weight);
+    }
+
+    /// For each amino acid (indexed by ASCII code) coded for by `table`, its synonymous
+    /// codon with the highest weight.
+    ///
+    /// Ties are broken in favor of the first codon in the order of [`Nucleotide::ALL`].
+    pub(crate) fn best_codons(&self, table: TranslationTable) -> [Option<Codon>; 256] {
+        let translate = table.to_fn();
+        let mut best: [Option<Codon>; 256] = [None; 256];
+        for a in Nucleotide::ALL {
+            for b in Nucleotide::ALL {
+                for c in Nucleotide::ALL {
+                    let codon = Codon([a, b, c]);
+                    let slot = &mut best[translate(codon) as usize];
+                    match *slot {
+                        Some(prev) if self.weight(prev) >= self.weight(codon) => {}
+                        _ => *slot = Some(codon),
+                    }
+                }
+            }
+        }
+        best
+    }
+}
+
+/// The relative adaptedness of each codon: its weight in a [`CodonUsageTable`], divided by
+/// the highest weight among its synonymous codons. The preferred codon for each amino acid
+/// has a relative adaptedness of 1.
+///
+/// Codons whose synonyms all have a weight of 0 have a relative adaptedness of 0.
+///
+/// # Examples
+///
+/// ```
+/// use quickdna::{codon_usage::{CodonUsageTable, RelativeAdaptednessTable}, DnaSequenceStrict, TranslationTable};
+///
+/// let reference: DnaSequenceStrict = "CTGCTGTTA".parse().unwrap();
+/// let usage = CodonUsageTable::from_dna(&reference);
+/// let adaptedness = RelativeAdaptednessTable::new(&usage, TranslationTable::Ncbi1);
+/// assert_eq!(adaptedness.weight("CTG".parse().unwrap()), 1.0);
+/// assert_eq!(adaptedness.weight("TTA".parse().unwrap()), 0.5);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RelativeAdaptednessTable {
+    weights: HashMap<Codon, f64>,
+}
+
+impl RelativeAdaptednessTable {
+    /// Compute the relative adaptedness of every codon in `usage`, grouping synonymous
+    /// codons by what they translate to in `table`.
+    pub fn new(usage: &CodonUsageTable, table: TranslationTable) -> Self {
+        let translate = table.to_fn();
+        let mut max_weights = [0.0; 256];
+        for codon in all_codons() {
+            let max = &mut max_weights[translate(codon) as usize];
+            *max = usage.weight(codon).max(*max);
+        }
+        let weights = all_codons()
+            .map(|codon| {
+                let max = max_weights[translate(codon) as usize];
+                let weight = if max > 0.0 {
+                    usage.weight(codon) / max
+                } else {
+                    0.0
+                };
+                (codon, weight)
+            })
+            .collect();
+        Self { weights }
+    }
+
+    /// The relative adaptedness of `codon`, between 0 and 1.
+    pub fn weight(&self, codon: Codon) -> f64 {
+        self.weights.get(&codon).copied().unwrap_or(0.0)
+    }
+}
+
+/// For each amino acid (indexed by ASCII code), how many codons in `table` code for it.
+pub(crate) fn synonym_counts(table: TranslationTable) -> [u8; 256] {
+    let translate = table.to_fn();
+    let mut counts = [0; 256];
+    for codon in all_codons() {
+        counts[translate(codon) as usize] += 1;
+    }
+    counts
+}
+
+fn all_codons() -> impl Iterator<Item = Codon> {
+    Nucleotide::ALL.into_iter().flat_map(|a| {
+        Nucleotide::ALL
+            .into_iter()
+            .flat_map(move |b| Nucleotide::ALL.into_iter().map(move |c| Codon([a, b, c])))
+    })
+}
+
+impl From<HashMap<Codon, f64>> for CodonUsageTable {
+    fn from(weights: HashMap<Codon, f64>) -> Self {
+        Self { weights }
+    }
+}
+
+impl FromIterator<(Codon, f64)> for CodonUsageTable {
+    fn from_iter<I: IntoIterator<Item = (Codon, f64)>>(iter: I) -> Self {
+        Self {
+            weights: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn codon(codon: &str) -> Codon {
+        codon.parse().unwrap()
+    }
+
+    #[test]
+    fn best_codons_break_ties_in_order() {
+        let best = CodonUsageTable::new().best_codons(TranslationTable::Ncbi1);
+        // Leucine is TTA, TTG, CTT, CTC, CTA or CTG
+        assert_eq!(best[b'L' as usize], Some(codon("TTA")));
+        assert_eq!(best[b'M' as usize], Some(codon("ATG")));
+        assert_eq!(best[b'*' as usize], Some(codon("TAA")));
+        assert_eq!(best[b'X' as usize], None);
+
+        let usage: CodonUsageTable = [(codon("CTG"), 0.5), (codon("TTG"), 0.5)]
+            .into_iter()
+            .collect();
+        let best = usage.best_codons(TranslationTable::Ncbi1);
+        assert_eq!(best[b'L' as usize], Some(codon("TTG")));
+    }
+
+    #[test]
+    fn relative_adaptedness_is_per_amino_acid() {
+        let usage: CodonUsageTable = [
+            (codon("CTG"), 0.4),
+            (codon("TTG"), 0.1),
+            (codon("AAA"), 0.2),
+            (codon("AAG"), 0.3),
+        ]
+        .into_iter()
+        .collect();
+        let adaptedness = RelativeAdaptednessTable::new(&usage, TranslationTable::Ncbi1);
+        assert_eq!(adaptedness.weight(codon("CTG")), 1.0);
+        assert_eq!(adaptedness.weight(codon("TTG")), 0.25);
+        assert_eq!(adaptedness.weight(codon("CTC")), 0.0);
+        assert_eq!(adaptedness.weight(codon("AAG")), 1.0);
+        assert_eq!(adaptedness.weight(codon("GGG")), 0.0);
+
+        let counts = synonym_counts(TranslationTable::Ncbi1);
+        assert_eq!(counts[b'L' as usize], 6);
+        assert_eq!(counts[b'W' as usize], 1);
+        assert_eq!(counts[b'*' as usize], 3);
+        assert_eq!(counts.iter().map(|&c| c as usize).sum::<usize>(), 64);
+    }
+}
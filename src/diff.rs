@@ -0,0 +1,301 @@
+// Copyright 2021-2024 SecureDNA Stiftung (SecureDNA Foundation) <licensing@securedna.org>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Edit scripts between similar sequences.
+//!
+//! [`diff`] finds a shortest edit script with Myers' O(ND) algorithm, where N is the total
+//! length of both sequences and D is the number of nucleotides inserted or deleted, so it's
+//! fast for similar sequences. A deletion next to an insertion is reported as substitutions,
+//! which makes the script more informative than a Hamming distance when indels are present.
+
+/// One step of an edit script from a sequence to another. Positions are indices into the
+/// first sequence.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Edit<T> {
+    /// The `len` nucleotides starting at `pos` are the same in both sequences.
+    Match { pos: usize, len: usize },
+    /// The nucleotide at `pos` is `from` in the first sequence, and `to` in the second.
+    Substitution { pos: usize, from: T, to: T },
+    /// The second sequence has `dna` inserted before `pos`.
+    Insertion { pos: usize, dna: Vec<T> },
+    /// The `len` nucleotides starting at `pos` are missing from the second sequence.
+    Deletion { pos: usize, len: usize },
+}
+
+/// A single step through the edit graph.
+#[derive(Clone, Copy)]
+enum Step {
+    Equal,
+    Insert(usize),
+    Delete,
+}
+
+/// Returns an edit script that turns `a` into `b`, with as few inserted and deleted
+/// nucleotides as possible (counting each substitution as one of each).
+///
+/// Runs of matching nucleotides are merged into a single [`Edit::Match`], and so are runs of
+/// insertions and deletions. Where `n` nucleotides are deleted and `m` inserted at the same
+/// position, the first `min(n, m)` of them are reported as substitutions.
+///
+/// ```
+/// use quickdna::{diff::{diff, Edit}, Nucleotide::*};
+///
+/// assert_eq!(
+///     diff(&[G, A, T, T, A, C, A], &[G, C, T, T, A, A]),
+///     [
+///         Edit::Match { pos: 0, len: 1 },
+///         Edit::Substitution { pos: 1, from: A, to: C },
+///         Edit::Match { pos: 2, len: 3 },
+///         Edit::Deletion { pos: 5, len: 1 },
+///         Edit::Match { pos: 6, len: 1 },
+///     ]
+/// );
+/// ```
+pub fn diff<T: Copy + PartialEq>(a: &[T], b: &[T]) -> Vec<Edit<T>> {
+    let mut edits = vec![];
+    let mut pos = 0;
+    let (mut deleted, mut inserted) = (0, vec![]);
+    for step in shortest_path(a, b) {
+        match step {
+            Step::Equal => {
+                push_changes(&mut edits, a, pos, deleted, &inserted);
+                pos += deleted;
+                (deleted, inserted) = (0, vec![]);
+                push_match(&mut edits, pos, 1);
+                pos += 1;
+            }
+            Step::Insert(index) => inserted.push(b[index]),
+            Step::Delete => deleted += 1,
+        }
+    }
+    push_changes(&mut edits, a, pos, deleted, &inserted);
+    edits
+}
+
+/// Append a match of `len` nucleotides at `pos`, extending the last edit if it's a match.
+fn push_match<T>(edits: &mut Vec<Edit<T>>, pos: usize, len: usize) {
+    if let Some(Edit::Match { len: last_len, .. }) = edits.last_mut() {
+        *last_len += len;
+    } else {
+        edits.push(Edit::Match { pos, len });
+    }
+}
+
+/// Append the edits for deleting `deleted` nucleotides of `a` at `pos`, and inserting
+/// `inserted` in their place.
+fn push_changes<T: Copy + PartialEq>(
+    edits: &mut Vec<Edit<T>>,
+    a: &[T],
+    pos: usize,
+    deleted: usize,
+    inserted: &[T],
+) {
+    let substituted = deleted.min(inserted.len());
+    for i in 0..substituted {
+        let (from, to) = (a[pos + i], inserted[i]);
+        if from == to {
+            push_match(edits, pos + i, 1);
+        } else {
+            edits.push(Edit::Substitution {
+                pos: pos + i,
+                from,
+                to,
+            });
+        }
+    }
+    let pos = pos + substituted;
+    if deleted > substituted {
+        edits.push(Edit::Deletion {
+            pos,
+            len: deleted - substituted,
+        });
+    }
+    if inserted.len() > substituted {
+        edits.push(Edit::Insertion {
+            pos,
+            dna: inserted[substituted..].to_vec(),
+        });
+    }
+}
+
+/// Find a shortest path through the edit graph from `a` to `b`, in order, with Myers' greedy
+/// algorithm. This keeps the diagonals of every step's frontier that backtracking can reach,
+/// so it takes O(N + M + D²) memory as well as O((N + M)D) time.
+fn shortest_path<T: PartialEq>(a: &[T], b: &[T]) -> Vec<Step> {
+    let (n, m) = (a.len() as isize, b.len() as isize);
+    let offset = n + m + 1;
+    // `frontier[offset + k]` is the furthest x reached on diagonal k = x - y.
+    let mut frontier = vec![0isize; 2 * offset as usize + 1];
+    let mut trace = vec![];
+
+    'search: for d in 0..=n + m {
+        // Step d only reads diagonals -d - 1..=d + 1 of the previous frontier.
+        trace.push(frontier[(offset - d - 1) as usize..=(offset + d + 1) as usize].to_vec());
+        for k in (-d..=d).step_by(2) {
+            let i = (offset + k) as usize;
+            let mut x = if k == -d || (k != d && frontier[i - 1] < frontier[i + 1]) {
+                frontier[i + 1]
+            } else {
+                frontier[i - 1] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                (x, y) = (x + 1, y + 1);
+            }
+            frontier[i] = x;
+            if x >= n && y >= m {
+                break 'search;
+            }
+        }
+    }
+
+    let mut steps = vec![];
+    let (mut x, mut y) = (n, m);
+    for (d, frontier) in trace.iter().enumerate().rev() {
+        let (d, k) = (d as isize, x - y);
+        let at = |k: isize| frontier[(k + d + 1) as usize];
+        let prev_k = if k == -d || (k != d && at(k - 1) < at(k + 1)) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let prev_x = at(prev_k);
+        let prev_y = prev_x - prev_k;
+        while x > prev_x && y > prev_y {
+            steps.push(Step::Equal);
+            (x, y) = (x - 1, y - 1);
+        }
+        if d > 0 {
+            if x == prev_x {
+                steps.push(Step::Insert((y - 1) as usize));
+            } else {
+                steps.push(Step::Delete);
+            }
+        }
+        (x, y) = (prev_x, prev_y);
+    }
+    steps.reverse();
+    steps
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use quickcheck::quickcheck;
+
+    use crate::Nucleotide::{self, *};
+
+    /// Apply an edit script to `a`, checking that it's consistent with `a`.
+    fn apply<T: Copy + PartialEq + std::fmt::Debug>(a: &[T], edits: &[Edit<T>]) -> Vec<T> {
+        let mut b = vec![];
+        let mut pos = 0;
+        for edit in edits {
+            match edit {
+                Edit::Match { pos: p, len } => {
+                    assert_eq!(*p, pos);
+                    b.extend_from_slice(&a[pos..pos + len]);
+                    pos += len;
+                }
+                Edit::Substitution { pos: p, from, to } => {
+                    assert_eq!((*p, a[pos]), (pos, *from));
+                    assert_ne!(from, to);
+                    b.push(*to);
+                    pos += 1;
+                }
+                Edit::Insertion { pos: p, dna } => {
+                    assert_eq!(*p, pos);
+                    b.extend_from_slice(dna);
+                }
+                Edit::Deletion { pos: p, len } => {
+                    assert_eq!(*p, pos);
+                    pos += len;
+                }
+            }
+        }
+        assert_eq!(pos, a.len());
+        b
+    }
+
+    /// The number of nucleotides inserted or deleted by an edit script.
+    fn cost<T>(edits: &[Edit<T>]) -> usize {
+        edits
+            .iter()
+            .map(|edit| match edit {
+                Edit::Match { .. } => 0,
+                Edit::Substitution { .. } => 2,
+                Edit::Insertion { dna, .. } => dna.len(),
+                Edit::Deletion { len, .. } => *len,
+            })
+            .sum()
+    }
+
+    /// The fewest nucleotides inserted or deleted to turn `a` into `b`, by dynamic programming.
+    fn naive_cost<T: PartialEq>(a: &[T], b: &[T]) -> usize {
+        let mut lcs = vec![vec![0; b.len() + 1]; a.len() + 1];
+        for i in 0..a.len() {
+            for j in 0..b.len() {
+                lcs[i + 1][j + 1] = if a[i] == b[j] {
+                    lcs[i][j] + 1
+                } else {
+                    lcs[i][j + 1].max(lcs[i + 1][j])
+                };
+            }
+        }
+        a.len() + b.len() - 2 * lcs[a.len()][b.len()]
+    }
+
+    #[test]
+    fn diff_edge_cases() {
+        let empty: &[Nucleotide] = &[];
+        assert_eq!(diff(empty, empty), []);
+        assert_eq!(diff(&[A, C], &[A, C]), [Edit::Match { pos: 0, len: 2 }]);
+        assert_eq!(
+            diff(empty, &[A, C]),
+            [Edit::Insertion {
+                pos: 0,
+                dna: vec![A, C]
+            }]
+        );
+        assert_eq!(diff(&[A, C], empty), [Edit::Deletion { pos: 0, len: 2 }]);
+        assert_eq!(
+            diff(&[A, C, G], &[A, T, T, G]),
+            [
+                Edit::Match { pos: 0, len: 1 },
+                Edit::Substitution {
+                    pos: 1,
+                    from: C,
+                    to: T
+                },
+                Edit::Insertion {
+                    pos: 2,
+                    dna: vec![T]
+                },
+                Edit::Match { pos: 2, len: 1 },
+            ]
+        );
+    }
+
+    quickcheck! {
+        fn diff_is_shortest_edit_script(a: Vec<Nucleotide>, b: Vec<Nucleotide>) -> bool {
+            let edits = diff(&a, &b);
+            apply(&a, &edits) == b && cost(&edits) == naive_cost(&a, &b)
+        }
+
+        fn diff_of_similar_sequences(a: Vec<Nucleotide>, edits: Vec<(usize, Option<Nucleotide>)>) -> bool {
+            let mut b = a.clone();
+            for (pos, nuc) in edits {
+                let pos = pos % (b.len() + 1);
+                match nuc {
+                    Some(nuc) => b.insert(pos, nuc),
+                    None if pos < b.len() => {
+                        b.remove(pos);
+                    }
+                    None => {}
+                }
+            }
+            let edits = diff(&a, &b);
+            apply(&a, &edits) == b && cost(&edits) == naive_cost(&a, &b)
+        }
+    }
+}
@@ -1,37 +1,131 @@
 // Copyright 2021-2024 SecureDNA Stiftung (SecureDNA Foundation) <licensing@securedna.org>
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
-use std::array::TryFromSliceError;
+// Without `std`, these errors only implement `Debug`, since thiserror needs `std::error::Error`.
 
+use alloc::string::String;
+use core::array::TryFromSliceError;
+
+#[cfg(feature = "std")]
 use thiserror::Error;
 
-#[derive(Debug, Clone, Error)]
-#[error("on line {line_number}: {error}")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "std",
+    derive(Error),
+    error("on line {line_number}: {error}")
+)]
 pub struct Located<E> {
     pub line_number: usize,
 
-    #[source]
+    #[cfg_attr(feature = "std", source)]
+    pub error: E,
+}
+
+/// An error at a given byte offset of some input.
+///
+/// It displays as `at column {position + 1}: {error}`, so that within a line it reads like an
+/// editor's 1-based column, alongside the 1-based line number of a [`Located`] error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "std",
+    derive(Error),
+    error("at column {}: {error}", .position + 1)
+)]
+pub struct Positioned<E> {
+    /// The 0-based byte offset of the error, counting any whitespace that was skipped.
+    pub position: usize,
+
+    #[cfg_attr(feature = "std", source)]
     pub error: E,
 }
 
-#[derive(Debug, Clone, Error)]
+impl From<Positioned<TranslationError>> for TranslationError {
+    fn from(value: Positioned<TranslationError>) -> Self {
+        value.error
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Error))]
+#[non_exhaustive]
 pub enum TranslationError {
-    #[error("non-ascii byte: {:x?}", .0)]
+    #[cfg_attr(feature = "std", error("non-ascii byte: {:x?}", .0))]
     NonAsciiByte(u8),
-    #[error("non-ascii char: {:x?}", .0)]
+    #[cfg_attr(feature = "std", error("non-ascii char: {:x?}", .0))]
     NonAsciiChar(char),
-    #[error("bad nucleotide: {:?}", .0)]
+    #[cfg_attr(feature = "std", error("bad nucleotide: {:?}", .0))]
     BadNucleotide(char),
-    #[error("unexpected ambiguous nucleotide: {:?}", .0)]
+    #[cfg_attr(feature = "std", error("unexpected ambiguous nucleotide: {:?}", .0))]
     UnexpectedAmbiguousNucleotide(char),
-    #[error("not a ncbi translation table: {}", .0)]
+    #[cfg_attr(feature = "std", error("unexpected lowercase nucleotide: {:?}", .0))]
+    UnexpectedLowercase(char),
+    #[cfg_attr(feature = "std", error("ambiguous codon: {:?}", .0))]
+    AmbiguousCodon(String),
+    #[cfg_attr(feature = "std", error("bad amino acid: {:?}", .0))]
+    BadAminoAcid(char),
+    #[cfg_attr(feature = "std", error("not a ncbi translation table: {}", .0))]
     BadTranslationTable(u8),
+    #[cfg_attr(feature = "std", error("not a ncbi translation table: {:?}", .0))]
+    BadTranslationTableName(String),
+    #[cfg_attr(
+        feature = "std",
+        error("translation table {} is unavailable with the minimal-tables feature", .0)
+    )]
+    UnavailableTranslationTable(u8),
 }
 
-#[derive(Debug, Clone, Error)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "std", derive(Error))]
 pub enum CodonError {
-    #[error("{:?}", .0)]
-    BadTranslation(#[from] TranslationError),
-    #[error("{:?}", .0)]
-    BadSlice(#[from] TryFromSliceError),
+    #[cfg_attr(feature = "std", error("{:?}", .0))]
+    BadTranslation(#[cfg_attr(feature = "std", source)] TranslationError),
+    #[cfg_attr(feature = "std", error("{:?}", .0))]
+    BadSlice(#[cfg_attr(feature = "std", source)] TryFromSliceError),
+}
+
+impl From<TranslationError> for CodonError {
+    fn from(value: TranslationError) -> Self {
+        Self::BadTranslation(value)
+    }
+}
+
+impl From<TryFromSliceError> for CodonError {
+    fn from(value: TryFromSliceError) -> Self {
+        Self::BadSlice(value)
+    }
+}
+
+/// Returned when expanding ambiguous DNA would produce more expansions than allowed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "std",
+    derive(Error),
+    error(
+        "too many expansions: {} exceeds limit of {limit}",
+        .count.map_or_else(|| "more than u128::MAX".to_string(), |c| c.to_string())
+    )
+)]
+pub struct TooManyExpansions {
+    /// The number of expansions, or `None` if it overflowed a [`u128`].
+    pub count: Option<u128>,
+    /// The limit that was exceeded.
+    pub limit: usize,
+}
+
+/// Returned when sequences that should all have the same length don't.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "std",
+    derive(Error),
+    error(
+        "sequences {} and {} have different lengths ({} and {})",
+        .indices.0, .indices.1, .lengths.0, .lengths.1
+    )
+)]
+pub struct LengthMismatch {
+    /// The indices of a pair of sequences with different lengths.
+    pub indices: (usize, usize),
+    /// The lengths of those sequences.
+    pub lengths: (usize, usize),
 }
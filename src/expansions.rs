@@ -1,33 +1,47 @@
 // Copyright 2021-2024 SecureDNA Stiftung (SecureDNA Foundation) <licensing@securedna.org>
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
-use std::sync::Arc;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
 
 use smallvec::SmallVec;
 
-use crate::{
-    BaseSequence, DnaSequenceAmbiguous, DnaSequenceStrict, Nucleotide, NucleotideAmbiguous,
-    NucleotideLike,
-};
+#[cfg(feature = "std")]
+use crate::{BaseSequence, DnaSequenceStrict};
+use crate::{Nucleotide, NucleotideAmbiguous, NucleotideLike};
 
 /// Iterator of all unambiguous expansions of ambiguous DNA.
 ///
 /// Expansions are returned in lexicographic order based on the ordering of [`Nucleotide`]
-/// (not currently alphabetical).
+/// (not currently alphabetical). The iterator is double-ended, so the lexicographically
+/// last expansion can be produced cheaply with [`next_back`](DoubleEndedIterator::next_back).
+///
+/// [`ExactSizeIterator::len`] panics if the number of expansions doesn't fit in a [`usize`].
 #[derive(Clone)]
 pub struct Expansions {
     ambiguities: SmallVec<[Ambiguity; 8]>,
-    buf: Arc<[Nucleotide]>,
-    began: bool,
+    front: Arc<[Nucleotide]>,
+    back: Option<Arc<[Nucleotide]>>,
+    front_pending: bool,
+    back_pending: bool,
+    remaining: Option<u128>,
 }
 
 #[derive(Clone)]
 struct Ambiguity {
     index: usize,
-    digit: u8,
+    front_digit: u8,
+    back_digit: u8,
     nucleotide: NucleotideAmbiguous,
 }
 
+impl Ambiguity {
+    fn radix(&self) -> u8 {
+        self.nucleotide.bits().count_ones() as u8
+    }
+}
+
 /// Unambiguous DNA expansion produced by [`Expansions`] iterator.
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Expansion(Arc<[Nucleotide]>);
@@ -35,25 +49,32 @@ pub struct Expansion(Arc<[Nucleotide]>);
 impl Expansions {
     // Construct new [`Expansions`] iterator
     pub fn new(dna: &[NucleotideAmbiguous]) -> Self {
-        let ambiguities = dna
+        let ambiguities: SmallVec<[Ambiguity; 8]> = dna
             .iter()
             .enumerate()
             .filter(|(_, nuc)| nuc.bits().count_ones() > 1)
             .map(|(index, &nucleotide)| Ambiguity {
                 index,
-                digit: 0,
+                front_digit: 0,
+                back_digit: nucleotide.bits().count_ones() as u8 - 1,
                 nucleotide,
             })
             .collect();
-        let buf: SmallVec<[_; 64]> = dna
+        let remaining = ambiguities
+            .iter()
+            .try_fold(1u128, |count, amb| count.checked_mul(amb.radix().into()));
+        let front: SmallVec<[_; 64]> = dna
             .iter()
             .map(|nuc| *nuc.possibilities().first().unwrap())
             .collect();
-        let buf = Arc::from(buf.as_slice());
+        let front = Arc::from(front.as_slice());
         Expansions {
             ambiguities,
-            buf,
-            began: false,
+            front,
+            back: None,
+            front_pending: true,
+            back_pending: true,
+            remaining,
         }
     }
 
@@ -63,67 +84,111 @@ impl Expansions {
         this.next(); // `this` contains a single empty expansion; skip it
         this
     }
+
+    fn take_one(&mut self) -> bool {
+        match &mut self.remaining {
+            Some(0) => false,
+            Some(remaining) => {
+                *remaining -= 1;
+                true
+            }
+            // More than u128::MAX expansions; won't run out in practice.
+            None => true,
+        }
+    }
+}
+
+// Can't use Arc::make_mut because [T] is unsized
+fn make_mut(buf: &mut Arc<[Nucleotide]>) -> &mut [Nucleotide] {
+    if Arc::get_mut(buf).is_none() {
+        *buf = Arc::from(&**buf);
+    }
+    Arc::get_mut(buf).unwrap()
 }
 
 impl Iterator for Expansions {
     type Item = Expansion;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if !self.began {
-            self.began = true;
-            return Some(Expansion(self.buf.clone()));
+        if !self.take_one() {
+            return None;
         }
 
-        // Can't use Arc::make_mut because [T] is unsized
-        let buf = match Arc::get_mut(&mut self.buf) {
-            Some(buf) => buf,
-            None => {
-                self.buf = Arc::from(&*self.buf);
-                Arc::get_mut(&mut self.buf).unwrap()
-            }
-        };
-
-        for amb in self.ambiguities.iter_mut().rev() {
-            amb.digit = (amb.digit + 1) % (amb.nucleotide.bits().count_ones() as u8);
-            buf[amb.index] = amb.nucleotide.possibilities()[amb.digit as usize];
-            if amb.digit > 0 {
-                return Some(Expansion(self.buf.clone()));
+        if self.front_pending {
+            self.front_pending = false;
+        } else {
+            let buf = make_mut(&mut self.front);
+            for amb in self.ambiguities.iter_mut().rev() {
+                amb.front_digit = (amb.front_digit + 1) % amb.radix();
+                buf[amb.index] = amb.nucleotide.possibilities()[amb.front_digit as usize];
+                if amb.front_digit > 0 {
+                    break;
+                }
             }
         }
-        None
+        Some(Expansion(self.front.clone()))
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let size = (|| {
-            let mut size: usize = 0;
+        match self.remaining.and_then(|r| usize::try_from(r).ok()) {
+            Some(size) => (size, Some(size)),
+            None => (usize::MAX, None),
+        }
+    }
+}
+
+impl DoubleEndedIterator for Expansions {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if !self.take_one() {
+            return None;
+        }
+
+        let back = self.back.get_or_insert_with(|| {
+            let mut back: Arc<[Nucleotide]> = Arc::from(&*self.front);
+            let buf = Arc::get_mut(&mut back).unwrap();
             for amb in &self.ambiguities {
-                let num_digit_states = amb.nucleotide.bits().count_ones() as usize;
-                let remaining = num_digit_states - (amb.digit as usize) - 1;
-                size = size.checked_mul(num_digit_states)?.checked_add(remaining)?;
+                buf[amb.index] = amb.nucleotide.possibilities()[amb.back_digit as usize];
+            }
+            back
+        });
+
+        if self.back_pending {
+            self.back_pending = false;
+        } else {
+            let buf = make_mut(back);
+            for amb in self.ambiguities.iter_mut().rev() {
+                let radix = amb.radix();
+                amb.back_digit = (amb.back_digit + radix - 1) % radix;
+                buf[amb.index] = amb.nucleotide.possibilities()[amb.back_digit as usize];
+                if amb.back_digit < radix - 1 {
+                    break;
+                }
             }
-            size.checked_add(!self.began as usize)
-        })();
-        (size.unwrap_or(usize::MAX), size)
+        }
+        Some(Expansion(back.clone()))
     }
 }
 
+impl ExactSizeIterator for Expansions {}
+
 impl Default for Expansions {
     fn default() -> Self {
         Self::empty()
     }
 }
 
-impl std::fmt::Debug for Expansions {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        let dna = self.buf.iter().map(|&nuc| nuc.into()).collect();
-        let mut dna = DnaSequenceAmbiguous::new(dna);
+impl core::fmt::Debug for Expansions {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let mut dna: Vec<NucleotideAmbiguous> = self.front.iter().map(|&nuc| nuc.into()).collect();
         for amb in &self.ambiguities {
             dna[amb.index] = amb.nucleotide;
         }
-        f.debug_tuple("Expansions").field(&dna.to_string()).finish()
+        let dna: String = dna.into_iter().map(char::from).collect();
+        f.debug_tuple("Expansions").field(&dna).finish()
     }
 }
 
+#[cfg(feature = "std")]
 impl Expansion {
     /// Produces a [`DnaSequenceStrict`] from [`Expansion`].
     ///
@@ -133,6 +198,7 @@ impl Expansion {
     }
 }
 
+#[cfg(feature = "std")]
 impl From<DnaSequenceStrict> for Expansion {
     fn from(dna: DnaSequenceStrict) -> Self {
         dna.as_slice().into()
@@ -151,13 +217,14 @@ impl From<Arc<[Nucleotide]>> for Expansion {
     }
 }
 
+#[cfg(feature = "std")]
 impl From<Expansion> for DnaSequenceStrict {
     fn from(expansion: Expansion) -> Self {
         expansion.to_dna()
     }
 }
 
-impl std::ops::Deref for Expansion {
+impl core::ops::Deref for Expansion {
     type Target = [Nucleotide];
 
     fn deref(&self) -> &Self::Target {
@@ -171,23 +238,23 @@ impl AsRef<[Nucleotide]> for Expansion {
     }
 }
 
-impl std::cmp::PartialEq<&[Nucleotide]> for Expansion {
+impl core::cmp::PartialEq<&[Nucleotide]> for Expansion {
     fn eq(&self, other: &&[Nucleotide]) -> bool {
         self.as_ref() == *other
     }
 }
 
-impl std::cmp::PartialEq<DnaSequenceStrict> for Expansion {
+#[cfg(feature = "std")]
+impl core::cmp::PartialEq<DnaSequenceStrict> for Expansion {
     fn eq(&self, other: &DnaSequenceStrict) -> bool {
         self.as_ref() == other.as_slice()
     }
 }
 
-impl std::fmt::Debug for Expansion {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        f.debug_tuple("Expansion")
-            .field(&self.to_dna().to_string())
-            .finish()
+impl core::fmt::Debug for Expansion {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let dna: String = self.iter().map(|&nuc| char::from(nuc)).collect();
+        f.debug_tuple("Expansion").field(&dna).finish()
     }
 }
 
@@ -304,6 +371,41 @@ mod test {
         assert_eq!(expansions, expected);
     }
 
+    #[test]
+    fn reverse_expansions() {
+        for src_dna in ["", "ATCG", "W", "ATBCGYAC", "MRY", "HBN"] {
+            let forward: Vec<_> = amb_dna(src_dna).expansions().collect();
+            let mut backward: Vec<_> = amb_dna(src_dna).expansions().rev().collect();
+            backward.reverse();
+            assert_eq!(forward, backward, "Mismatch for {src_dna:?}");
+        }
+    }
+
+    #[test]
+    fn expansions_from_both_ends() {
+        let src_dna = amb_dna("ATBCGYAC"); // AT{TCG}CG{TC}AC
+        let mut expansions = src_dna.expansions();
+        assert_eq!(expansions.len(), 6);
+        assert_eq!(expansions.next_back().unwrap(), dna("ATGCGCAC"));
+        assert_eq!(expansions.next().unwrap(), dna("ATTCGTAC"));
+        assert_eq!(expansions.len(), 4);
+        assert_eq!(expansions.next_back().unwrap(), dna("ATGCGTAC"));
+        assert_eq!(expansions.next_back().unwrap(), dna("ATCCGCAC"));
+        assert_eq!(expansions.next().unwrap(), dna("ATTCGCAC"));
+        assert_eq!(expansions.len(), 1);
+        assert_eq!(expansions.next_back().unwrap(), dna("ATCCGTAC"));
+        assert_eq!(expansions.len(), 0);
+        assert!(expansions.next().is_none());
+        assert!(expansions.next_back().is_none());
+    }
+
+    #[test]
+    fn last_expansion_of_huge_sequence() {
+        let src_dna = amb_dna(&"N".repeat(100));
+        let last = src_dna.expansions().next_back().unwrap();
+        assert_eq!(last, dna(&"G".repeat(100)));
+    }
+
     #[test]
     fn empty_expansions() {
         let mut empty = Expansions::empty();
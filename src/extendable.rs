@@ -10,6 +10,16 @@ pub trait Extendable: Default {
 
     /// Extend this value by another value.
     fn extend(&mut self, other: Self);
+
+    /// Extend this value by the empty line that ends a record, under
+    /// [`FastaParseSettings::preserve_trailing_empty_line`](crate::FastaParseSettings::preserve_trailing_empty_line).
+    /// By default this does nothing, since sequences don't keep line breaks.
+    fn extend_empty_line(&mut self) {}
+
+    /// Drop any case information kept from parsing, under
+    /// [`CasePolicy::AllowLowercase`](crate::CasePolicy::AllowLowercase). By default this
+    /// does nothing; a [`MaskedDnaSequence`](crate::masked::MaskedDnaSequence) unmasks.
+    fn ignore_case(&mut self) {}
 }
 
 impl Extendable for String {
@@ -20,4 +30,8 @@ impl Extendable for String {
     fn extend(&mut self, other: Self) {
         self.push_str(&other)
     }
+
+    fn extend_empty_line(&mut self) {
+        self.push('\n')
+    }
 }
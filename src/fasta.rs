@@ -3,8 +3,12 @@
 
 //! This module is for reading and writing FASTA format files
 
+#[cfg(not(target_arch = "wasm32"))]
+use std::collections::HashSet;
 use std::fmt::Display;
-use std::io::{self, BufRead};
+use std::io::{self, BufRead, Write};
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
 use thiserror::Error;
@@ -12,15 +16,42 @@ use thiserror::Error;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-pub use crate::errors::Located;
-use crate::Extendable;
+use crate::errors::Located;
+use crate::{
+    BaseSequence, CasePolicy, DnaSequence, Extendable, NucleotideIter, NucleotideLike, Positioned,
+    ProteinSequence, Strand, TranslationError, TranslationTable,
+};
 
+/// A record of a FASTA file.
+///
+/// This struct is `#[non_exhaustive]`: build one with [`FastaRecord::new`] and its
+/// `with_*` setters.
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[non_exhaustive]
 pub struct FastaRecord<T> {
     /// The header of this record, without the leading character (usually '>')
     /// Depending on the content and parser settings, this header may be empty, and may contain newlines.
     pub header: String,
+    /// The character that introduced the header of this record, `>` or `;`.
+    /// This is always `>` unless [`FastaParseSettings::preserve_header_prefix`] is set.
+    /// See [`Self::header_lines`] for the prefix of each line of a concatenated header.
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default = "default_header_prefix",
+            skip_serializing_if = "is_default_header_prefix"
+        )
+    )]
+    pub header_prefix: char,
+    /// The prefixes of the second and later lines of the header, if they aren't all
+    /// [`Self::header_prefix`]. Empty otherwise, so that records compare equal however
+    /// their prefixes were stored.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    continuation_prefixes: Vec<char>,
     /// The contents of this record
     pub contents: T,
     /// The starting and ending line numbers of this record, start inclusive, end exclusive, 1-indexed.
@@ -28,6 +59,147 @@ pub struct FastaRecord<T> {
     pub line_range: (usize, usize),
 }
 
+impl<T> FastaRecord<T> {
+    /// Create a record to write out, with a `>` header prefix and a `line_range` of `(0, 0)`.
+    ///
+    /// ```
+    /// use quickdna::{DnaSequenceStrict, FastaRecord};
+    ///
+    /// let record = FastaRecord::new("seq1", "ACGT".parse::<DnaSequenceStrict>().unwrap());
+    /// assert!(record.validate().is_ok());
+    /// assert_eq!(record.to_string(), ">seq1\nACGT\n");
+    /// ```
+    pub fn new(header: impl Into<String>, contents: T) -> Self {
+        Self {
+            header: header.into(),
+            header_prefix: '>',
+            continuation_prefixes: Vec::new(),
+            contents,
+            line_range: (0, 0),
+        }
+    }
+
+    /// Changes the [`Self::header_prefix`] of every line of the header
+    ///
+    /// ```
+    /// use quickdna::FastaRecord;
+    ///
+    /// let record = FastaRecord::new("a\nb", "ACGT").with_header_prefix(';');
+    /// assert_eq!(record.to_string(), ";a\n;b\nACGT\n");
+    /// ```
+    pub fn with_header_prefix(mut self, header_prefix: char) -> Self {
+        self.header_prefix = header_prefix;
+        self.continuation_prefixes.clear();
+        self
+    }
+
+    /// Changes the [`Self::line_range`]
+    pub fn with_line_range(mut self, line_range: (usize, usize)) -> Self {
+        self.line_range = line_range;
+        self
+    }
+
+    /// Iterate over the lines of the header along with the prefix each was written with,
+    /// which may differ between lines of a concatenated header if
+    /// [`FastaParseSettings::preserve_header_prefix`] is set.
+    ///
+    /// ```
+    /// use quickdna::{FastaParser, FastaParseSettings};
+    ///
+    /// let fasta = ">a\n;b\nACGT\n";
+    /// let file = FastaParser::<String>::new(
+    ///     FastaParseSettings::new().preserve_header_prefix(true)
+    /// ).parse_str(fasta).unwrap();
+    /// let lines: Vec<_> = file.records[0].header_lines().collect();
+    /// assert_eq!(lines, [('>', "a"), (';', "b")]);
+    /// assert_eq!(file.to_string(), fasta);
+    /// ```
+    pub fn header_lines(&self) -> impl Iterator<Item = (char, &str)> + '_ {
+        let prefixes = std::iter::once(self.header_prefix).chain(
+            self.continuation_prefixes
+                .iter()
+                .copied()
+                .chain(std::iter::repeat(self.header_prefix)),
+        );
+        prefixes.zip(self.header.split('\n'))
+    }
+
+    /// The identifier of this record: its header up to the first whitespace.
+    ///
+    /// ```
+    /// use quickdna::{DnaSequenceStrict, FastaFile};
+    ///
+    /// let file: FastaFile<DnaSequenceStrict> = ">NC_045512.2 SARS-CoV-2\nACGT".parse().unwrap();
+    /// assert_eq!(file.records[0].id(), "NC_045512.2");
+    /// assert_eq!(file.records[0].description(), "SARS-CoV-2");
+    /// ```
+    pub fn id(&self) -> &str {
+        self.header_parts().0
+    }
+
+    /// The description of this record: its header after the [`Self::id`] and the whitespace
+    /// character that follows it. This is empty if the header has no whitespace.
+    pub fn description(&self) -> &str {
+        self.header_parts().1
+    }
+
+    fn header_parts(&self) -> (&str, &str) {
+        match self.header.find(|c: char| c.is_ascii_whitespace()) {
+            // ASCII whitespace is one byte, so this slice is panic-safe
+            Some(i) => (&self.header[..i], &self.header[i + 1..]),
+            None => (&self.header, ""),
+        }
+    }
+
+    /// Which read of a pair this record is, if its header follows one of the usual
+    /// paired-end conventions: an ID ending in `/1` or `/2`, or an Illumina-style
+    /// description starting with `1:` or `2:`.
+    ///
+    /// ```
+    /// use quickdna::{DnaSequenceStrict, FastaFile, Mate};
+    ///
+    /// let file: FastaFile<DnaSequenceStrict> =
+    ///     ">read7/1\nACGT\n>M00:1:FC:1:1:1:1 2:N:0:ATCACG\nACGT".parse().unwrap();
+    /// assert_eq!(file.records[0].mate(), Some(Mate::First));
+    /// assert_eq!(file.records[0].base_id(), "read7");
+    /// assert_eq!(file.records[1].mate(), Some(Mate::Second));
+    /// assert_eq!(file.records[1].base_id(), "M00:1:FC:1:1:1:1");
+    /// ```
+    pub fn mate(&self) -> Option<Mate> {
+        self.mate_parts().1
+    }
+
+    /// The [`Self::id`] of this record without any `/1` or `/2` mate suffix, so that both
+    /// reads of a pair share it.
+    pub fn base_id(&self) -> &str {
+        self.mate_parts().0
+    }
+
+    fn mate_parts(&self) -> (&str, Option<Mate>) {
+        let id = self.id();
+        if let Some(base) = id.strip_suffix("/1") {
+            (base, Some(Mate::First))
+        } else if let Some(base) = id.strip_suffix("/2") {
+            (base, Some(Mate::Second))
+        } else if self.description().starts_with("1:") {
+            (id, Some(Mate::First))
+        } else if self.description().starts_with("2:") {
+            (id, Some(Mate::Second))
+        } else {
+            (id, None)
+        }
+    }
+}
+
+/// One read of a paired-end pair, as returned by [`FastaRecord::mate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Mate {
+    /// The forward read, marked by `/1` or `1:`.
+    First,
+    /// The reverse read, marked by `/2` or `2:`.
+    Second,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct FastaFile<T> {
@@ -35,15 +207,223 @@ pub struct FastaFile<T> {
     pub records: Vec<FastaRecord<T>>,
 }
 
+#[cfg(feature = "serde")]
+fn default_header_prefix() -> char {
+    '>'
+}
+
+#[cfg(feature = "serde")]
+fn is_default_header_prefix(prefix: &char) -> bool {
+    *prefix == default_header_prefix()
+}
+
+impl<T: Display> FastaRecord<T> {
+    /// Check that writing this record out won't produce FASTA that parses differently.
+    ///
+    /// [`Display`] prefixes every line of the header, so any header text survives, but a
+    /// line of contents that starts with `>` or `;` would be parsed as a header. Use
+    /// [`FastaFile::validate`] to also check that records don't run into each other.
+    pub fn validate(&self) -> Result<(), FastaRecordError> {
+        if let Some(line) = self
+            .contents
+            .to_string()
+            .lines()
+            .position(|line| line.starts_with(['>', ';']))
+        {
+            return Err(FastaRecordError::ContentsLineStartsWithPrefix(line + 1));
+        }
+        Ok(())
+    }
+}
+
+impl<T: Display> FastaFile<T> {
+    /// Check that writing this file out won't produce FASTA that parses differently, returning
+    /// the index of the first bad record along with what's wrong with it.
+    ///
+    /// On top of [`FastaRecord::validate`], this checks that no record but the first has an
+    /// empty header: [`Display`] writes no header line for it, so its contents would be
+    /// parsed as part of the record before it.
+    ///
+    /// ```
+    /// use quickdna::{FastaFile, FastaRecord, FastaRecordError};
+    ///
+    /// let file = FastaFile {
+    ///     records: vec![FastaRecord::new("", "ACGT"), FastaRecord::new("b", "GG")],
+    /// };
+    /// assert!(file.validate().is_ok());
+    ///
+    /// let file = FastaFile {
+    ///     records: vec![FastaRecord::new("a", "ACGT"), FastaRecord::new("", "GG")],
+    /// };
+    /// assert_eq!(file.validate(), Err((1, FastaRecordError::EmptyHeader)));
+    /// ```
+    pub fn validate(&self) -> Result<(), (usize, FastaRecordError)> {
+        for (index, record) in self.records.iter().enumerate() {
+            if index > 0 && record.header.is_empty() {
+                return Err((index, FastaRecordError::EmptyHeader));
+            }
+            record.validate().map_err(|error| (index, error))?;
+        }
+        Ok(())
+    }
+}
+
 impl<T: Display> Display for FastaRecord<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if !self.header.is_empty() {
-            writeln!(f, ">{}", self.header.replace('\n', "\n>"))?;
+            for (prefix, line) in self.header_lines() {
+                writeln!(f, "{prefix}{line}")?;
+            }
         }
         writeln!(f, "{}", self.contents)
     }
 }
 
+/// What [`FastaFile::write_split`] does when two records are given the same path.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DuplicatePaths {
+    /// Fail with [`io::ErrorKind::AlreadyExists`] before writing anything.
+    #[default]
+    Error,
+    /// Append `_1`, `_2`, etc. to the file stem of each later duplicate.
+    Suffix,
+}
+
+// There is no filesystem to write to in the browser.
+#[cfg(not(target_arch = "wasm32"))]
+impl<T: Display> FastaFile<T> {
+    /// Write each record to its own file, at the path given by `name_fn`, returning the
+    /// paths written to in record order.
+    ///
+    /// Duplicate paths are handled according to `duplicates`. Existing files are overwritten.
+    ///
+    /// ```no_run
+    /// use std::path::PathBuf;
+    /// use quickdna::{DnaSequenceStrict, DuplicatePaths, FastaFile};
+    ///
+    /// let file: FastaFile<DnaSequenceStrict> = ">a\nACGT\n>b\nTT".parse().unwrap();
+    /// let paths = file
+    ///     .write_split(|record| PathBuf::from(format!("{}.fa", record.id())), DuplicatePaths::Error)
+    ///     .unwrap();
+    /// assert_eq!(paths, [PathBuf::from("a.fa"), PathBuf::from("b.fa")]);
+    /// ```
+    pub fn write_split<F>(&self, name_fn: F, duplicates: DuplicatePaths) -> io::Result<Vec<PathBuf>>
+    where
+        F: Fn(&FastaRecord<T>) -> PathBuf,
+    {
+        let mut seen = HashSet::new();
+        let mut paths = Vec::with_capacity(self.records.len());
+        for record in &self.records {
+            let mut path = name_fn(record);
+            if seen.contains(&path) {
+                match duplicates {
+                    DuplicatePaths::Error => {
+                        return Err(io::Error::new(
+                            io::ErrorKind::AlreadyExists,
+                            format!("duplicate path: {}", path.display()),
+                        ))
+                    }
+                    DuplicatePaths::Suffix => path = suffixed(&path, &seen),
+                }
+            }
+            seen.insert(path.clone());
+            paths.push(path);
+        }
+
+        for (record, path) in self.records.iter().zip(&paths) {
+            std::fs::write(path, record.to_string())?;
+        }
+        Ok(paths)
+    }
+}
+
+/// The first of `path` with `_1`, `_2`, etc. appended to its file stem that isn't in `seen`.
+#[cfg(not(target_arch = "wasm32"))]
+fn suffixed(path: &Path, seen: &HashSet<PathBuf>) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    (1..)
+        .map(|n| {
+            let mut name = format!("{stem}_{n}");
+            if let Some(extension) = path.extension() {
+                name.push('.');
+                name.push_str(&extension.to_string_lossy());
+            }
+            path.with_file_name(name)
+        })
+        .find(|path| !seen.contains(path))
+        .unwrap()
+}
+
+impl<T: NucleotideLike> FastaFile<DnaSequence<T>> {
+    /// Translate the first reading frame of each record with `table`, keeping its header
+    /// and line range.
+    ///
+    /// ```
+    /// use quickdna::{DnaSequenceStrict, FastaFile, TranslationTable};
+    ///
+    /// let file: FastaFile<DnaSequenceStrict> = ">a\nATGAAA\n>b\nTGA".parse().unwrap();
+    /// let proteins = file.translate_all(TranslationTable::Ncbi1);
+    /// assert_eq!(proteins.to_string(), ">a\nMK\n>b\n*\n");
+    /// ```
+    pub fn translate_all(&self, table: TranslationTable) -> FastaFile<ProteinSequence> {
+        let records = self
+            .records
+            .iter()
+            .map(|record| FastaRecord {
+                header: record.header.clone(),
+                header_prefix: record.header_prefix,
+                continuation_prefixes: record.continuation_prefixes.clone(),
+                contents: record.contents.translate(table),
+                line_range: record.line_range,
+            })
+            .collect();
+        FastaFile { records }
+    }
+
+    /// Translate every reading frame on both strands of each record with `table`, giving
+    /// up to 6 records per input record, in the order of
+    /// [`enumerate_all_reading_frames`](crate::NucleotideIter::enumerate_all_reading_frames).
+    ///
+    /// Each header is suffixed with the frame it was read from: ` frame=+1` to ` frame=+3`
+    /// for offsets 0 to 2 on the forward strand, and ` frame=-1` to ` frame=-3` for the
+    /// reverse complement. Line ranges are those of the input record.
+    ///
+    /// ```
+    /// use quickdna::{DnaSequenceStrict, FastaFile, TranslationTable};
+    ///
+    /// let file: FastaFile<DnaSequenceStrict> = ">a\nATGCA".parse().unwrap();
+    /// let proteins = file.translate_all_frames(TranslationTable::Ncbi1);
+    /// let headers: Vec<_> = proteins.records.iter().map(|r| r.header.as_str()).collect();
+    /// assert_eq!(headers[..2], ["a frame=+1", "a frame=+2"]);
+    /// assert_eq!(headers[5], "a frame=-3");
+    /// assert_eq!(proteins.records[5].contents.to_string(), "H");
+    /// ```
+    pub fn translate_all_frames(&self, table: TranslationTable) -> FastaFile<ProteinSequence> {
+        let mut records = vec![];
+        for record in &self.records {
+            let dna = record.contents.as_slice().iter().copied();
+            let proteins = record.contents.translate_all_frames(table);
+            for (((strand, offset), _), protein) in
+                dna.enumerate_all_reading_frames().into_iter().zip(proteins)
+            {
+                let sign = match strand {
+                    Strand::Forward => '+',
+                    Strand::ReverseComplement => '-',
+                };
+                records.push(FastaRecord {
+                    header: format!("{} frame={sign}{}", record.header, offset + 1),
+                    header_prefix: record.header_prefix,
+                    continuation_prefixes: record.continuation_prefixes.clone(),
+                    contents: protein,
+                    line_range: record.line_range,
+                });
+            }
+        }
+        FastaFile { records }
+    }
+}
+
 impl<T: Display> Display for FastaFile<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         for record in &self.records {
@@ -62,8 +442,30 @@ impl<T> IntoIterator for FastaFile<T> {
     }
 }
 
+impl<T: FastaContent> FromStr for FastaFile<T> {
+    type Err = Located<FastaParseError<T::Err>>;
+
+    /// Parse a FASTA file with the default [`FastaParser`].
+    ///
+    /// ```
+    /// use quickdna::{DnaSequenceStrict, FastaFile};
+    ///
+    /// let file: FastaFile<DnaSequenceStrict> = ">a\nACGT".parse().unwrap();
+    /// assert_eq!(file.records[0].header, "a");
+    /// assert_eq!(file.to_string(), ">a\nACGT\n");
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        FastaParser::default().parse_str(s)
+    }
+}
+
 /// Settings for a fasta parser.
+///
+/// With the `serde` feature, settings can be deserialized from config files; any missing
+/// keys take their default values.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct FastaParseSettings {
     /// If this flag is true, then successive headers in a FASTA file will be
     /// concatenated, instead of generating empty records. The default value is
@@ -78,16 +480,8 @@ pub struct FastaParseSettings {
     /// assert_eq!(
     ///     nonconcat.records,
     ///     vec![
-    ///         FastaRecord {
-    ///             header: "a".to_string(),
-    ///             contents: "".to_string(),
-    ///             line_range: (1, 2),
-    ///         },
-    ///         FastaRecord {
-    ///             header: "b".to_string(),
-    ///             contents: "...".to_string(),
-    ///             line_range: (2, 4),
-    ///         }
+    ///         FastaRecord::new("a", "".to_string()).with_line_range((1, 2)),
+    ///         FastaRecord::new("b", "...".to_string()).with_line_range((2, 4)),
     ///     ],
     /// );
     ///
@@ -97,11 +491,7 @@ pub struct FastaParseSettings {
     /// assert_eq!(
     ///     concat.records,
     ///     vec![
-    ///         FastaRecord {
-    ///             header: "a\nb".to_string(),
-    ///             contents: "...".to_string(),
-    ///             line_range: (1, 4),
-    ///         }
+    ///         FastaRecord::new("a\nb", "...".to_string()).with_line_range((1, 4)),
     ///     ],
     /// );
     /// ```
@@ -120,11 +510,7 @@ pub struct FastaParseSettings {
     /// assert_eq!(
     ///     comment.records,
     ///     vec![
-    ///         FastaRecord {
-    ///             header: "a".to_string(),
-    ///             contents: "sequence".to_string(),
-    ///             line_range: (2, 4),
-    ///         }
+    ///         FastaRecord::new("a", "sequence".to_string()).with_line_range((2, 4)),
     ///     ]
     /// );
     ///
@@ -134,30 +520,134 @@ pub struct FastaParseSettings {
     /// assert_eq!(
     ///     comment.records,
     ///     vec![
-    ///         FastaRecord {
-    ///             header: "".to_string(),
-    ///             contents: "comment".to_string(),
-    ///             line_range: (1, 2),
-    ///         },
-    ///         FastaRecord {
-    ///             header: "a".to_string(),
-    ///             contents: "sequence".to_string(),
-    ///             line_range: (2, 4),
-    ///         }
+    ///         FastaRecord::new("", "comment".to_string()).with_line_range((1, 2)),
+    ///         FastaRecord::new("a", "sequence".to_string()).with_line_range((2, 4)),
     ///     ]
     /// );
     /// ```
     allow_preceding_comment: bool,
+
+    /// If this flag is true, each record remembers whether its header started with `>` or
+    /// the semi-obsolete `;` in [`FastaRecord::header_prefix`], so that displaying the
+    /// file reproduces the original prefix. Otherwise, every header prefix is normalized to
+    /// `>`. The default value is `false`.
+    ///
+    /// Each line of a concatenated header keeps its own prefix: see
+    /// [`FastaRecord::header_lines`].
+    ///
+    /// ```rust
+    /// use quickdna::{FastaParser, FastaParseSettings};
+    ///
+    /// let fasta = ";a\nACGT\n";
+    /// let normalized = FastaParser::<String>::new(FastaParseSettings::new())
+    ///     .parse_str(fasta)
+    ///     .unwrap();
+    /// assert_eq!(normalized.to_string(), ">a\nACGT\n");
+    ///
+    /// let preserved = FastaParser::<String>::new(
+    ///     FastaParseSettings::new().preserve_header_prefix(true)
+    /// ).parse_str(fasta).unwrap();
+    /// assert_eq!(preserved.records[0].header_prefix, ';');
+    /// assert_eq!(preserved.to_string(), fasta);
+    /// ```
+    preserve_header_prefix: bool,
+
+    /// If this flag is true, lines starting with `;` after the first header are comments,
+    /// as in the original FASTA format, and are skipped instead of starting a new header.
+    /// A `;` line before any header still starts a header. The default value is `false`.
+    ///
+    /// ```rust
+    /// use quickdna::{FastaParser, FastaParseSettings};
+    ///
+    /// let fasta = ">a\n;a comment\nAC\n;another comment\nGT\n";
+    /// let headers = FastaParser::<String>::new(FastaParseSettings::new())
+    ///     .parse_str(fasta)
+    ///     .unwrap();
+    /// assert_eq!(headers.records.len(), 2);
+    ///
+    /// let comments = FastaParser::<String>::new(
+    ///     FastaParseSettings::new().semicolon_as_comment(true)
+    /// ).parse_str(fasta).unwrap();
+    /// assert_eq!(comments.records.len(), 1);
+    /// assert_eq!(comments.records[0].header, "a");
+    /// assert_eq!(comments.records[0].contents, "ACGT");
+    /// ```
+    semicolon_as_comment: bool,
+
+    /// If this flag is true, an empty line at the end of a record's contents, before the
+    /// next header or the end of the file, is kept with [`Extendable::extend_empty_line`]
+    /// instead of being skipped. Empty lines followed by more contents are skipped either
+    /// way. The default value is `false`.
+    ///
+    /// [`String`] contents keep the empty line as a trailing `\n`, so that displaying the
+    /// record reproduces it, while sequences, which have no line breaks, are unaffected. A
+    /// record whose only line of contents is empty still has empty contents, since that's
+    /// already how they're displayed.
+    ///
+    /// ```rust
+    /// use quickdna::{FastaParser, FastaParseSettings};
+    ///
+    /// let fasta = ">a\nAC\n\nGT\n\n>b\nTT\n";
+    /// let skipped = FastaParser::<String>::new(FastaParseSettings::new())
+    ///     .parse_str(fasta)
+    ///     .unwrap();
+    /// assert_eq!(skipped.records[0].contents, "ACGT");
+    ///
+    /// let kept = FastaParser::<String>::new(
+    ///     FastaParseSettings::new().preserve_trailing_empty_line(true)
+    /// ).parse_str(fasta).unwrap();
+    /// assert_eq!(kept.records[0].contents, "ACGT\n");
+    /// assert_eq!(kept.to_string(), ">a\nACGT\n\n>b\nTT\n");
+    /// ```
+    preserve_trailing_empty_line: bool,
+
+    /// How lowercase letters in a record's contents are handled. The default value is
+    /// [`CasePolicy::AllowLowercase`].
+    ///
+    /// Under [`CasePolicy::RejectLowercase`], a contents line with a lowercase letter is a
+    /// [`FastaParseError::Lowercase`] error, whatever the content type. Otherwise, lines are
+    /// passed to [`FastaContent::parse`] as written, and under [`CasePolicy::AllowLowercase`]
+    /// the parsed line is then passed to [`Extendable::ignore_case`]. So a
+    /// [`MaskedDnaSequence`](crate::masked::MaskedDnaSequence) is only soft-masked under
+    /// [`CasePolicy::PreserveCase`], while a [`DnaSequence`] is uppercased and a [`String`]
+    /// is kept as written under either.
+    ///
+    /// ```rust
+    /// use quickdna::{masked::MaskedDnaSequence, CasePolicy, FastaParser, FastaParseSettings, NucleotideAmbiguous};
+    ///
+    /// let fasta = ">a\nACGT\nacgtN\n";
+    /// let strict = FastaParser::<MaskedDnaSequence<NucleotideAmbiguous>>::new(
+    ///     FastaParseSettings::new().case_policy(CasePolicy::RejectLowercase)
+    /// );
+    /// let err = strict.parse_str(fasta).unwrap_err();
+    /// assert_eq!(err.line_number, 3);
+    ///
+    /// let masked = FastaParser::<MaskedDnaSequence<NucleotideAmbiguous>>::new(
+    ///     FastaParseSettings::new().case_policy(CasePolicy::PreserveCase)
+    /// ).parse_str(fasta).unwrap();
+    /// assert_eq!(masked.records[0].contents.soft_masked_string(), "ACGTacgtN");
+    ///
+    /// let unmasked = FastaParser::<MaskedDnaSequence<NucleotideAmbiguous>>::new(
+    ///     FastaParseSettings::new().case_policy(CasePolicy::AllowLowercase)
+    /// ).parse_str(fasta).unwrap();
+    /// assert_eq!(unmasked.records[0].contents.soft_masked_string(), "ACGTACGTN");
+    /// ```
+    case_policy: CasePolicy,
 }
 
 // "Builder-lite" pattern: https://matklad.github.io/2022/05/29/builder-lite.html
 impl FastaParseSettings {
-    /// Initializes settings to their defaults: concatenate headers, and
-    /// disallow a preceding comment.
+    /// Initializes settings to their defaults: concatenate headers, disallow a
+    /// preceding comment, normalize header prefixes, treat `;` lines as headers, skip
+    /// trailing empty content lines, and allow lowercase contents.
     pub fn new() -> Self {
         Self {
             concatenate_headers: true,
             allow_preceding_comment: false,
+            preserve_header_prefix: false,
+            semicolon_as_comment: false,
+            preserve_trailing_empty_line: false,
+            case_policy: CasePolicy::AllowLowercase,
         }
     }
 
@@ -172,6 +662,30 @@ impl FastaParseSettings {
         self.allow_preceding_comment = allow_preceding_comment;
         self
     }
+
+    /// Changes the setting for [`Self::preserve_header_prefix`]
+    pub fn preserve_header_prefix(mut self, preserve_header_prefix: bool) -> Self {
+        self.preserve_header_prefix = preserve_header_prefix;
+        self
+    }
+
+    /// Changes the setting for [`Self::semicolon_as_comment`]
+    pub fn semicolon_as_comment(mut self, semicolon_as_comment: bool) -> Self {
+        self.semicolon_as_comment = semicolon_as_comment;
+        self
+    }
+
+    /// Changes the setting for [`Self::preserve_trailing_empty_line`]
+    pub fn preserve_trailing_empty_line(mut self, preserve_trailing_empty_line: bool) -> Self {
+        self.preserve_trailing_empty_line = preserve_trailing_empty_line;
+        self
+    }
+
+    /// Changes the setting for [`Self::case_policy`]
+    pub fn case_policy(mut self, case_policy: CasePolicy) -> Self {
+        self.case_policy = case_policy;
+        self
+    }
 }
 
 impl Default for FastaParseSettings {
@@ -185,6 +699,52 @@ pub trait FastaContent: Extendable {
     fn parse(line_number: usize, line: &str) -> Result<Self, Located<FastaParseError<Self::Err>>>
     where
         Self: Sized;
+
+    /// Called once on the contents of each record when it's complete, after all its lines
+    /// have been parsed and concatenated, to validate the record as a whole. An error here
+    /// is reported on the first line of the record. By default, this does nothing.
+    ///
+    /// ```
+    /// use quickdna::{Extendable, FastaContent, FastaParseError, FastaParser, Located};
+    ///
+    /// /// Contents that must be exactly four characters long.
+    /// #[derive(Debug, Default)]
+    /// struct Four(String);
+    ///
+    /// impl Extendable for Four {
+    ///     fn is_blank(&self) -> bool {
+    ///         self.0.trim().is_empty()
+    ///     }
+    ///
+    ///     fn extend(&mut self, other: Self) {
+    ///         self.0.push_str(&other.0)
+    ///     }
+    /// }
+    ///
+    /// impl FastaContent for Four {
+    ///     type Err = usize;
+    ///
+    ///     fn parse(_: usize, line: &str) -> Result<Self, Located<FastaParseError<usize>>> {
+    ///         Ok(Four(line.to_string()))
+    ///     }
+    ///
+    ///     fn finalize(&mut self) -> Result<(), usize> {
+    ///         match self.0.len() {
+    ///             4 => Ok(()),
+    ///             len => Err(len),
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let parser = FastaParser::<Four>::default();
+    /// assert!(parser.parse_str(">a\nAC\nGT\n").is_ok());
+    /// let err = parser.parse_str(">a\nAC\nGT\n>b\nACG\n").unwrap_err();
+    /// assert_eq!(err.line_number, 4);
+    /// assert!(matches!(err.error, FastaParseError::ParseError(3)));
+    /// ```
+    fn finalize(&mut self) -> Result<(), Self::Err> {
+        Ok(())
+    }
 }
 
 impl<T: FromStr + Extendable> FastaContent for T {
@@ -211,12 +771,19 @@ enum ParserState<T: FastaContent> {
     },
     InHeader {
         start_line_number: usize,
+        header_prefix: char,
+        continuation_prefixes: Vec<char>,
         header: String,
     },
     InRecord {
         start_line_number: usize,
+        header_prefix: char,
+        continuation_prefixes: Vec<char>,
         header: String,
         contents: T,
+        /// whether the last line was empty and should be kept, if this is the end of the
+        /// record (only if settings.preserve_trailing_empty_line is true)
+        trailing_empty_line: bool,
     },
 }
 
@@ -234,18 +801,31 @@ impl<T: FastaContent> ParserState<T> {
         line: &str,
         line_number: usize,
     ) -> ParseLineResult<T> {
-        let new_header = try_parse_header(line);
+        let in_record = !matches!(self, ParserState::StartOfFile { .. });
+        if settings.semicolon_as_comment && in_record && line.starts_with(';') {
+            return Ok((self, None));
+        }
+        let new_header = try_parse_header(line).map(|(prefix, header)| {
+            let prefix = if settings.preserve_header_prefix {
+                prefix
+            } else {
+                '>'
+            };
+            (prefix, header)
+        });
         let (new_state, record) = match (self, new_header) {
             // start of file, and we have a header line => start a new record,
             // maybe emiting the preceding content as a headerless record depending
             // on parse settings
-            (ParserState::StartOfFile { contents }, Some(new_header)) => {
+            (ParserState::StartOfFile { contents }, Some((new_prefix, new_header))) => {
                 // don't emit if the settings don't want it, or if the record would just be whitespace
                 let record = if settings.allow_preceding_comment || contents.is_blank() {
                     None
                 } else {
                     Some(FastaRecord {
                         header: "".to_string(),
+                        header_prefix: '>',
+                        continuation_prefixes: Vec::new(),
                         contents,
                         line_range: (1, line_number),
                     })
@@ -254,6 +834,8 @@ impl<T: FastaContent> ParserState<T> {
                 (
                     Self::InHeader {
                         start_line_number: line_number,
+                        header_prefix: new_prefix,
+                        continuation_prefixes: Vec::new(),
                         header: new_header.to_string(),
                     },
                     record,
@@ -264,7 +846,7 @@ impl<T: FastaContent> ParserState<T> {
             (ParserState::StartOfFile { mut contents }, None) => {
                 if !settings.allow_preceding_comment {
                     // only bother to keep contents updated if we need to emit it
-                    contents.extend(T::parse(line_number, line)?);
+                    contents.extend(parse_contents(settings, line_number, line)?);
                 }
                 (Self::StartOfFile { contents }, None)
             }
@@ -274,16 +856,26 @@ impl<T: FastaContent> ParserState<T> {
             (
                 ParserState::InHeader {
                     start_line_number,
+                    header_prefix,
+                    mut continuation_prefixes,
                     mut header,
                 },
-                Some(new_header),
+                Some((new_prefix, new_header)),
             ) => {
                 if settings.concatenate_headers {
+                    // only keep track of continuation prefixes once one differs
+                    if new_prefix != header_prefix || !continuation_prefixes.is_empty() {
+                        let lines_so_far = header.matches('\n').count();
+                        continuation_prefixes.resize(lines_so_far, header_prefix);
+                        continuation_prefixes.push(new_prefix);
+                    }
                     header.push('\n');
                     header.push_str(new_header);
                     (
                         Self::InHeader {
                             start_line_number,
+                            header_prefix,
+                            continuation_prefixes,
                             header,
                         },
                         None,
@@ -292,10 +884,14 @@ impl<T: FastaContent> ParserState<T> {
                     (
                         Self::InHeader {
                             start_line_number: line_number,
+                            header_prefix: new_prefix,
+                            continuation_prefixes: Vec::new(),
                             header: new_header.to_string(),
                         },
                         Some(FastaRecord {
                             header,
+                            header_prefix,
+                            continuation_prefixes,
                             contents: T::default(),
                             line_range: (start_line_number, line_number),
                         }),
@@ -306,14 +902,20 @@ impl<T: FastaContent> ParserState<T> {
             (
                 ParserState::InHeader {
                     start_line_number,
+                    header_prefix,
+                    continuation_prefixes,
                     header,
                 },
                 None,
             ) => (
                 Self::InRecord {
                     start_line_number,
+                    header_prefix,
+                    continuation_prefixes,
                     header,
-                    contents: T::parse(line_number, line)?,
+                    contents: parse_contents(settings, line_number, line)?,
+                    // an empty first line is already how empty contents are displayed
+                    trailing_empty_line: false,
                 },
                 None,
             ),
@@ -322,27 +924,42 @@ impl<T: FastaContent> ParserState<T> {
             (
                 ParserState::InRecord {
                     start_line_number,
+                    header_prefix,
+                    continuation_prefixes,
                     header,
-                    contents,
-                },
-                Some(new_header),
-            ) => (
-                Self::InHeader {
-                    start_line_number: line_number,
-                    header: new_header.to_string(),
+                    mut contents,
+                    trailing_empty_line,
                 },
-                Some(FastaRecord {
-                    header,
-                    contents,
-                    line_range: (start_line_number, line_number),
-                }),
-            ),
+                Some((new_prefix, new_header)),
+            ) => {
+                if trailing_empty_line {
+                    contents.extend_empty_line();
+                }
+                (
+                    Self::InHeader {
+                        start_line_number: line_number,
+                        header_prefix: new_prefix,
+                        continuation_prefixes: Vec::new(),
+                        header: new_header.to_string(),
+                    },
+                    Some(FastaRecord {
+                        header,
+                        header_prefix,
+                        continuation_prefixes,
+                        contents,
+                        line_range: (start_line_number, line_number),
+                    }),
+                )
+            }
             // in record and we don't have a new header => continue record
             (
                 ParserState::InRecord {
                     start_line_number,
+                    header_prefix,
+                    continuation_prefixes,
                     header,
                     mut contents,
+                    ..
                 },
                 None,
             ) => {
@@ -350,13 +967,17 @@ impl<T: FastaContent> ParserState<T> {
                     // don't push an empty line to a record at the end of a file with a trailing newline
                     // (if this isn't the EOF, the line numbers will continue and pushing an empty line would
                     // have been a no-op anyways)
-                    contents.extend(T::parse(line_number, line)?);
+                    contents.extend(parse_contents(settings, line_number, line)?);
                 }
                 (
                     Self::InRecord {
                         start_line_number,
+                        header_prefix,
+                        continuation_prefixes,
                         header,
                         contents,
+                        trailing_empty_line: settings.preserve_trailing_empty_line
+                            && line.is_empty(),
                     },
                     None,
                 )
@@ -384,6 +1005,8 @@ impl<T: FastaContent> ParserState<T> {
                 } else {
                     Some(FastaRecord {
                         header: "".to_string(),
+                        header_prefix: '>',
+                        continuation_prefixes: Vec::new(),
                         contents,
                         line_range: (1, eof_line_number),
                     })
@@ -393,9 +1016,13 @@ impl<T: FastaContent> ParserState<T> {
             // in header => emit the header as empty record
             ParserState::InHeader {
                 start_line_number,
+                header_prefix,
+                continuation_prefixes,
                 header,
             } => Some(FastaRecord {
                 header,
+                header_prefix,
+                continuation_prefixes,
                 contents: T::default(),
                 line_range: (start_line_number, eof_line_number),
             }),
@@ -403,88 +1030,661 @@ impl<T: FastaContent> ParserState<T> {
             // in record => emit as final record
             ParserState::InRecord {
                 start_line_number,
+                header_prefix,
+                continuation_prefixes,
                 header,
-                contents,
-            } => Some(FastaRecord {
-                header,
-                contents,
-                line_range: (start_line_number, eof_line_number),
-            }),
+                mut contents,
+                trailing_empty_line,
+            } => {
+                if trailing_empty_line {
+                    contents.extend_empty_line();
+                }
+                Some(FastaRecord {
+                    header,
+                    header_prefix,
+                    continuation_prefixes,
+                    contents,
+                    line_range: (start_line_number, eof_line_number),
+                })
+            }
         }
     }
 }
 
+/// Why a [`FastaRecord`] can't be written out faithfully, as returned by
+/// [`FastaRecord::validate`] and [`FastaFile::validate`]. Line numbers are 1-indexed within
+/// the contents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum FastaRecordError {
+    #[error("contents line {0} starts with '>' or ';', so it would be parsed as a header")]
+    ContentsLineStartsWithPrefix(usize),
+    #[error("header is empty, so the record would be parsed as part of the one before it")]
+    EmptyHeader,
+}
+
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum FastaParseError<ParseError> {
     #[error("error reading from reader: {0}")]
     IOError(#[from] io::Error),
     #[error("error parsing record: {0}")]
     ParseError(#[source] ParseError), // can't use #[from] due to generic impl clash
+    #[error("error checking case: {0}")]
+    Lowercase(#[source] Positioned<TranslationError>),
+}
+
+impl<E> FastaParseError<E> {
+    /// Convert this error into an [`OwnedFastaParseError`], which can be cloned and compared.
+    pub fn into_owned(self) -> OwnedFastaParseError<E> {
+        match self {
+            Self::IOError(e) => OwnedFastaParseError::IOError {
+                kind: e.kind(),
+                message: e.to_string(),
+            },
+            Self::ParseError(e) => OwnedFastaParseError::ParseError(e),
+            Self::Lowercase(e) => OwnedFastaParseError::Lowercase(e),
+        }
+    }
+}
+
+impl<E> Located<FastaParseError<E>> {
+    /// Convert this error into one with an [`OwnedFastaParseError`], which can be cloned and
+    /// compared.
+    ///
+    /// ```
+    /// use quickdna::{DnaSequenceStrict, FastaParser, Located, OwnedFastaParseError, Positioned, TranslationError};
+    ///
+    /// let err = FastaParser::<DnaSequenceStrict>::default()
+    ///     .parse_str(">a\nACGU")
+    ///     .unwrap_err();
+    /// assert_eq!(
+    ///     err.into_owned(),
+    ///     Located {
+    ///         line_number: 2,
+    ///         error: OwnedFastaParseError::ParseError(Positioned {
+    ///             position: 3,
+    ///             error: TranslationError::BadNucleotide('U'),
+    ///         }),
+    ///     }
+    /// );
+    /// ```
+    pub fn into_owned(self) -> Located<OwnedFastaParseError<E>> {
+        Located {
+            line_number: self.line_number,
+            error: self.error.into_owned(),
+        }
+    }
+}
+
+/// A [`FastaParseError`] that can be cloned, compared and sent between threads, with any I/O
+/// error reduced to its kind and message. See [`FastaParseError::into_owned`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum OwnedFastaParseError<ParseError> {
+    #[error("error reading from reader: {message}")]
+    IOError {
+        kind: io::ErrorKind,
+        message: String,
+    },
+    #[error("error parsing record: {0}")]
+    ParseError(#[source] ParseError),
+    #[error("error checking case: {0}")]
+    Lowercase(#[source] Positioned<TranslationError>),
+}
+
+impl<E> From<FastaParseError<E>> for OwnedFastaParseError<E> {
+    fn from(value: FastaParseError<E>) -> Self {
+        value.into_owned()
+    }
+}
+
+pub struct FastaParser<T: FastaContent> {
+    settings: FastaParseSettings,
+    _marker: std::marker::PhantomData<T>,
+}
+
+impl<T: FastaContent> FastaParser<T> {
+    /// Construct a new FastaParser with the given [`FastaParseSettings`]
+    pub fn new(settings: FastaParseSettings) -> Self {
+        Self {
+            settings,
+            _marker: Default::default(),
+        }
+    }
+
+    pub fn parse<R: BufRead>(
+        &self,
+        handle: R,
+    ) -> Result<FastaFile<T>, Located<FastaParseError<T::Err>>> {
+        let records = Self::parse_records(&self.settings, handle, 1)?;
+        Ok(FastaFile { records })
+    }
+
+    /// Parse records from `handle` one at a time, as they're read, so that a large file can
+    /// be processed without holding all of it in memory.
+    ///
+    /// This yields the same records as [`Self::parse`]. After yielding an error, the
+    /// iterator ends.
+    ///
+    /// ```
+    /// use quickdna::{DnaSequenceStrict, FastaParser};
+    ///
+    /// let parser = FastaParser::<DnaSequenceStrict>::default();
+    /// let mut records = parser.records(">a\nACGT\n>b\nTTGU\n>c\nA".as_bytes());
+    /// assert_eq!(records.next().unwrap().unwrap().contents.to_string(), "ACGT");
+    /// assert_eq!(records.next().unwrap().unwrap_err().line_number, 4);
+    /// assert!(records.next().is_none());
+    /// ```
+    pub fn records<R: BufRead>(&self, handle: R) -> FastaRecords<R, T> {
+        FastaRecords::new(self.settings, handle, 1)
+    }
+
+    /// Parse a FASTA file using multiple threads, with the same result as [`Self::parse`].
+    ///
+    /// This first reads through `handle` to find where records start, then seeks back and
+    /// parses batches of whole records in parallel. Batches are only split before the first
+    /// line of a header, so each batch parses as it would within the whole file, and line
+    /// numbers (including those of errors) still count from the start of `handle`.
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use quickdna::{DnaSequenceStrict, FastaParser};
+    ///
+    /// let fasta = ">a\nACGT\n>b\nTTGA\n";
+    /// let parser = FastaParser::<DnaSequenceStrict>::default();
+    /// let parsed = parser.parse_parallel(Cursor::new(fasta)).unwrap();
+    /// assert_eq!(parsed, parser.parse_str(fasta).unwrap());
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn parse_parallel<R: io::Read + io::Seek>(
+        &self,
+        handle: R,
+    ) -> Result<FastaFile<T>, Located<FastaParseError<T::Err>>>
+    where
+        T: Send,
+        T::Err: Send,
+    {
+        /// Minimum number of bytes parsed by each task.
+        const CHUNK_BYTES: usize = 1 << 20;
+
+        self.parse_chunked(handle, CHUNK_BYTES)
+    }
+
+    /// Like [`Self::parse_parallel`], but with chunks of at least `chunk_bytes` bytes.
+    #[cfg(feature = "rayon")]
+    fn parse_chunked<R: io::Read + io::Seek>(
+        &self,
+        handle: R,
+        chunk_bytes: usize,
+    ) -> Result<FastaFile<T>, Located<FastaParseError<T::Err>>>
+    where
+        T: Send,
+        T::Err: Send,
+    {
+        use rayon::prelude::*;
+        use std::io::{Read, Seek, SeekFrom};
+
+        let mut reader = io::BufReader::new(handle);
+        let seek_error = |e: io::Error| Located {
+            line_number: 1,
+            error: e.into(),
+        };
+        let start = reader.stream_position().map_err(seek_error)?;
+        let chunks = self.find_chunks(&mut reader, chunk_bytes)?;
+        reader.seek(SeekFrom::Start(start)).map_err(seek_error)?;
+
+        // `Self` isn't `Sync` unless `T` is, so don't share it between threads.
+        let settings = &self.settings;
+        let mut records = vec![];
+        for batch in chunks.chunks(rayon::current_num_threads()) {
+            let mut buffers = Vec::with_capacity(batch.len());
+            for chunk in batch {
+                let mut buffer = vec![0; chunk.bytes];
+                reader.read_exact(&mut buffer).map_err(|e| Located {
+                    line_number: chunk.first_line_number,
+                    error: e.into(),
+                })?;
+                buffers.push(buffer);
+            }
+            let parsed: Vec<_> = buffers
+                .par_iter()
+                .zip(batch)
+                .map(|(buffer, chunk)| {
+                    Self::parse_records(settings, &buffer[..], chunk.first_line_number)
+                })
+                .collect();
+            // Return the first error in the file, as the serial parser would.
+            for chunk_records in parsed {
+                records.extend(chunk_records?);
+            }
+        }
+        Ok(FastaFile { records })
+    }
+
+    /// Read through `reader`, splitting it into chunks of whole lines that can be parsed
+    /// independently: each chunk after the first starts with a header line that doesn't
+    /// continue a header, and has at least `chunk_bytes` bytes (except the last).
+    #[cfg(feature = "rayon")]
+    fn find_chunks<R: BufRead>(
+        &self,
+        reader: &mut R,
+        chunk_bytes: usize,
+    ) -> Result<Vec<Chunk>, Located<FastaParseError<T::Err>>> {
+        let mut chunks = vec![Chunk {
+            first_line_number: 1,
+            bytes: 0,
+        }];
+        let mut line = vec![];
+        // Whether we've left `ParserState::StartOfFile`, and whether we're `InHeader`.
+        let (mut seen_header, mut in_header) = (false, false);
+        for line_number in 1.. {
+            line.clear();
+            let bytes = reader.read_until(b'\n', &mut line).map_err(|e| Located {
+                line_number,
+                error: e.into(),
+            })?;
+            if bytes == 0 {
+                break;
+            }
+
+            let prefix = line.first().copied();
+            let is_comment =
+                self.settings.semicolon_as_comment && seen_header && prefix == Some(b';');
+            let is_header = !is_comment && matches!(prefix, Some(b'>' | b';'));
+            let chunk = chunks.last_mut().unwrap();
+            if is_header && !in_header && chunk.bytes >= chunk_bytes.max(1) {
+                chunks.push(Chunk {
+                    first_line_number: line_number,
+                    bytes: 0,
+                });
+            }
+            chunks.last_mut().unwrap().bytes += bytes;
+            if !is_comment {
+                in_header = is_header;
+            }
+            seen_header |= is_header;
+        }
+        Ok(chunks)
+    }
+
+    /// Parse records from `handle`, whose first line is line `first_line_number` of the file.
+    fn parse_records<R: BufRead>(
+        settings: &FastaParseSettings,
+        handle: R,
+        first_line_number: usize,
+    ) -> Result<Vec<FastaRecord<T>>, Located<FastaParseError<T::Err>>> {
+        FastaRecords::new(*settings, handle, first_line_number).collect()
+    }
+
+    /// Run [`FastaContent::finalize`] on a completed record.
+    fn finalize(
+        mut record: FastaRecord<T>,
+    ) -> Result<FastaRecord<T>, Located<FastaParseError<T::Err>>> {
+        record.contents.finalize().map_err(|e| Located {
+            line_number: record.line_range.0,
+            error: FastaParseError::ParseError(e),
+        })?;
+        Ok(record)
+    }
+
+    pub fn parse_str(&self, s: &str) -> Result<FastaFile<T>, Located<FastaParseError<T::Err>>> {
+        self.parse(s.as_bytes())
+    }
+}
+
+/// Iterator over the records of a FASTA file, parsed as they're read.
+///
+/// This `struct` is created by [`FastaParser::records`]. See its documentation for more.
+pub struct FastaRecords<R, T: FastaContent> {
+    settings: FastaParseSettings,
+    lines: io::Lines<R>,
+    /// The number of the last line read.
+    line_number: usize,
+    /// `None` once the file is finished, or after an error.
+    state: Option<ParserState<T>>,
+}
+
+impl<R: BufRead, T: FastaContent> FastaRecords<R, T> {
+    /// Parse records from `handle`, whose first line is line `first_line_number` of the file.
+    fn new(settings: FastaParseSettings, handle: R, first_line_number: usize) -> Self {
+        Self {
+            settings,
+            lines: handle.lines(),
+            line_number: first_line_number - 1,
+            state: Some(ParserState::StartOfFile {
+                contents: T::default(),
+            }),
+        }
+    }
+}
+
+impl<R: BufRead, T: FastaContent> Iterator for FastaRecords<R, T> {
+    type Item = Result<FastaRecord<T>, Located<FastaParseError<T::Err>>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut state = self.state.take()?;
+        for line in self.lines.by_ref() {
+            self.line_number += 1;
+            let line_number = self.line_number;
+            let line = match line {
+                Ok(line) => line,
+                Err(e) => {
+                    return Some(Err(Located {
+                        line_number,
+                        error: e.into(),
+                    }))
+                }
+            };
+
+            let (new_state, record) = match state.advance_line(&self.settings, &line, line_number) {
+                Ok(advanced) => advanced,
+                Err(e) => return Some(Err(e)),
+            };
+            state = new_state;
+            if let Some(record) = record {
+                self.state = Some(state);
+                return Some(FastaParser::finalize(record));
+            }
+        }
+
+        state
+            .advance_eof(&self.settings, self.line_number + 1)
+            .map(FastaParser::finalize)
+    }
+}
+
+impl<R: BufRead, T: FastaContent> std::iter::FusedIterator for FastaRecords<R, T> {}
+
+/// A range of whole lines of a FASTA file, for [`FastaParser::parse_parallel`].
+#[cfg(feature = "rayon")]
+struct Chunk {
+    first_line_number: usize,
+    bytes: usize,
+}
+
+impl<T: FastaContent> Default for FastaParser<T> {
+    /// Construct a new FastaParser with default settings (see [`FastaParseSettings::new()`])
+    fn default() -> Self {
+        Self::new(FastaParseSettings::default())
+    }
 }
 
-pub struct FastaParser<T: FastaContent> {
-    settings: FastaParseSettings,
-    _marker: std::marker::PhantomData<T>,
+/// Wrapper that serializes a [`FastaFile`] as a list of self-describing records, rather than
+/// in the compact form of its own serde impls.
+///
+/// Each record becomes an object with its position in the file as `index`, its
+/// [`id`](FastaRecord::id) and [`description`](FastaRecord::description) instead of its
+/// header, its contents as `sequence`, and its [`line_range`](FastaRecord::line_range) as
+/// `line_start` and `line_end`. Deserializing joins a non-empty description back onto the id
+/// with a space, and requires the records to be in index order.
+///
+/// So that this round-trips, serializing fails on a record whose header wouldn't be rebuilt
+/// exactly: one whose id is followed by whitespace other than a space (such as a tab, or the
+/// line break of a concatenated header) or by nothing, or whose header prefix isn't `>`.
+///
+/// ```
+/// use quickdna::{DnaSequenceStrict, FastaFile, NumberedFasta};
+///
+/// let file: FastaFile<DnaSequenceStrict> = ">a first\nACGT\n>b\nTT".parse().unwrap();
+/// let json = serde_json::to_value(NumberedFasta(file.clone())).unwrap();
+/// assert_eq!(
+///     json,
+///     serde_json::json!([
+///         {"index": 0, "id": "a", "description": "first", "sequence": "ACGT", "line_start": 1, "line_end": 3},
+///         {"index": 1, "id": "b", "description": "", "sequence": "TT", "line_start": 3, "line_end": 5},
+///     ])
+/// );
+///
+/// let round_trip: NumberedFasta<DnaSequenceStrict> = serde_json::from_value(json).unwrap();
+/// assert_eq!(round_trip.0, file);
+/// ```
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NumberedFasta<T>(pub FastaFile<T>);
+
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct NumberedRecord<S, T> {
+    index: usize,
+    id: S,
+    description: S,
+    sequence: T,
+    line_start: usize,
+    line_end: usize,
 }
 
-impl<T: FastaContent> FastaParser<T> {
-    /// Construct a new FastaParser with the given [`FastaParseSettings`]
-    pub fn new(settings: FastaParseSettings) -> Self {
-        Self {
-            settings,
-            _marker: Default::default(),
+#[cfg(feature = "serde")]
+impl<T: Serialize> Serialize for NumberedFasta<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::{Error, SerializeSeq};
+
+        let mut seq = serializer.serialize_seq(Some(self.0.records.len()))?;
+        for (index, record) in self.0.records.iter().enumerate() {
+            if record.header_lines().any(|(prefix, _)| prefix != '>') {
+                return Err(S::Error::custom(format!(
+                    "record {index} has a header prefix other than '>'"
+                )));
+            }
+            if !splits_losslessly(&record.header) {
+                return Err(S::Error::custom(format!(
+                    "header {:?} of record {index} can't be rebuilt from its id and description",
+                    record.header
+                )));
+            }
+            seq.serialize_element(&NumberedRecord {
+                index,
+                id: record.id(),
+                description: record.description(),
+                sequence: &record.contents,
+                line_start: record.line_range.0,
+                line_end: record.line_range.1,
+            })?;
         }
+        seq.end()
     }
+}
 
-    pub fn parse<R: BufRead>(
-        &self,
-        handle: R,
-    ) -> Result<FastaFile<T>, Located<FastaParseError<T::Err>>> {
-        let mut records: Vec<FastaRecord<T>> = vec![];
-        let mut state = ParserState::StartOfFile {
-            contents: T::default(),
-        };
-
-        let mut line_number = 0;
-        for (idx, line) in handle.lines().enumerate() {
-            line_number = idx + 1;
-            let line = line.map_err(|e| Located {
-                line_number,
-                error: e.into(),
-            })?;
+/// Whether joining the id and description of `header` with a space, as [`NumberedFasta`]
+/// deserializes them, gives back `header`.
+#[cfg(feature = "serde")]
+fn splits_losslessly(header: &str) -> bool {
+    match header.find(|c: char| c.is_ascii_whitespace()) {
+        Some(i) => header.as_bytes()[i] == b' ' && i + 1 < header.len(),
+        None => true,
+    }
+}
 
-            let (new_state, record) = state.advance_line(&self.settings, &line, line_number)?;
-            state = new_state;
-            if let Some(record) = record {
-                records.push(record);
+#[cfg(feature = "serde")]
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for NumberedFasta<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let numbered = Vec::<NumberedRecord<String, T>>::deserialize(deserializer)?;
+        let mut records = Vec::with_capacity(numbered.len());
+        for (i, record) in numbered.into_iter().enumerate() {
+            if record.index != i {
+                return Err(serde::de::Error::custom(format!(
+                    "expected record index {i}, got {}",
+                    record.index
+                )));
+            }
+            let mut header = record.id;
+            if !record.description.is_empty() {
+                header.push(' ');
+                header.push_str(&record.description);
             }
+            records.push(
+                FastaRecord::new(header, record.sequence)
+                    .with_line_range((record.line_start, record.line_end)),
+            );
         }
+        Ok(Self(FastaFile { records }))
+    }
+}
 
-        if let Some(record) = state.advance_eof(&self.settings, line_number + 1) {
-            records.push(record);
-        }
+/// Why [`reverse_complement_fasta`] failed.
+#[derive(Debug, Error)]
+pub enum ReverseComplementFastaError<ParseError> {
+    #[error(transparent)]
+    Parse(#[from] Located<FastaParseError<ParseError>>),
+    #[error("error writing to writer: {0}")]
+    Write(#[source] io::Error),
+}
 
-        Ok(FastaFile { records })
+/// Reverse complement every record of the FASTA file read from `reader`, writing the records
+/// to `writer` with their headers unchanged, one at a time so that memory use is bounded by
+/// the largest record.
+///
+/// Records are parsed as [`DnaSequence<T>`] according to `settings`, and each sequence is
+/// written on a single line. A record with no contents gets an empty line, as in its
+/// [`Display`], unless it's the last one, so that it isn't merged into the next record when
+/// the output is parsed again. Records before a parse error have already been written when
+/// it's returned.
+///
+/// Output is buffered internally, so `writer` needn't be.
+///
+/// ```
+/// use quickdna::{reverse_complement_fasta, FastaParseSettings, NucleotideAmbiguous};
+///
+/// let fasta = ">a first\nGATT\nACA\n>b\nRYN\n";
+/// let mut out = vec![];
+/// reverse_complement_fasta::<NucleotideAmbiguous, _, _>(fasta.as_bytes(), &mut out, FastaParseSettings::new())
+///     .unwrap();
+/// assert_eq!(String::from_utf8(out).unwrap(), ">a first\nTGTAATC\n>b\nNRY\n");
+/// ```
+pub fn reverse_complement_fasta<T, R, W>(
+    reader: R,
+    writer: W,
+    settings: FastaParseSettings,
+) -> Result<(), ReverseComplementFastaError<Positioned<TranslationError>>>
+where
+    T: NucleotideLike,
+    R: BufRead,
+    W: io::Write,
+{
+    let mut writer = io::BufWriter::new(writer);
+    let mut line = vec![];
+    let mut empty_line_pending = false;
+    for record in FastaParser::<DnaSequence<T>>::new(settings).records(reader) {
+        let record = record?;
+        if empty_line_pending {
+            writer
+                .write_all(b"\n")
+                .map_err(ReverseComplementFastaError::Write)?;
+        }
+        line.clear();
+        line.extend(
+            record
+                .contents
+                .reverse_complement_iter()
+                .map(NucleotideLike::to_ascii),
+        );
+        write_reverse_complement(&mut writer, &record, &line)
+            .map_err(ReverseComplementFastaError::Write)?;
+        empty_line_pending = line.is_empty();
     }
+    writer.flush().map_err(ReverseComplementFastaError::Write)
+}
 
-    pub fn parse_str(&self, s: &str) -> Result<FastaFile<T>, Located<FastaParseError<T::Err>>> {
-        self.parse(s.as_bytes())
+/// Write `record` like its [`Display`] does, but with `reverse_complement` as its contents,
+/// written in one go, and without the empty line for empty contents.
+fn write_reverse_complement<T, W: io::Write>(
+    writer: &mut W,
+    record: &FastaRecord<T>,
+    reverse_complement: &[u8],
+) -> io::Result<()> {
+    if !record.header.is_empty() {
+        for (prefix, line) in record.header_lines() {
+            writeln!(writer, "{prefix}{line}")?;
+        }
     }
+    if !reverse_complement.is_empty() {
+        writer.write_all(reverse_complement)?;
+        writer.write_all(b"\n")?;
+    }
+    Ok(())
 }
 
-impl<T: FastaContent> Default for FastaParser<T> {
-    /// Construct a new FastaParser with default settings (see [`FastaParseSettings::new()`])
-    fn default() -> Self {
-        Self::new(FastaParseSettings::default())
+/// Translate DNA read from numbered lines of text, such as the content lines of a FASTA
+/// record, one codon at a time. Codons may span lines, and any trailing partial codon is
+/// dropped.
+///
+/// Each item is the amino acid for a codon, or the error for the first of its bytes that
+/// isn't a valid `T`, along with where that byte is: its line number, and its byte offset
+/// within the line. Iteration continues with the next codon after an error, so every bad
+/// codon can be reported.
+///
+/// ```
+/// use quickdna::{translate_lines, Nucleotide, NucleotideAmbiguous, TranslationTable};
+///
+/// let fasta = ">seq\nATGRN\nNTAA\n";
+/// let lines = || fasta.lines().enumerate().skip(1).map(|(i, line)| (i + 1, line));
+///
+/// let lenient: Vec<_> = translate_lines::<NucleotideAmbiguous, _>(lines(), TranslationTable::Ncbi1)
+///     .collect::<Result<_, _>>()
+///     .unwrap();
+/// assert_eq!(lenient, b"MX*");
+///
+/// let strict: Vec<_> = translate_lines::<Nucleotide, _>(lines(), TranslationTable::Ncbi1).collect();
+/// let err = strict[1].as_ref().unwrap_err();
+/// assert_eq!((err.line_number, err.error.position), (2, 3));
+/// assert_eq!(strict[2].as_ref().unwrap(), &b'*');
+/// ```
+pub fn translate_lines<'a, T, I>(
+    lines: I,
+    table: TranslationTable,
+) -> impl Iterator<Item = Result<u8, Located<Positioned<TranslationError>>>> + 'a
+where
+    T: NucleotideLike + 'a,
+    I: IntoIterator<Item = (usize, &'a str)>,
+    I::IntoIter: 'a,
+{
+    let mut bytes = lines.into_iter().flat_map(|(line_number, line)| {
+        line.bytes()
+            .enumerate()
+            .map(move |(position, byte)| (line_number, position, byte))
+    });
+    let translate = table.to_fn::<T, [T; 3]>();
+    let parse = |(line_number, position, byte): (usize, usize, u8)| {
+        T::try_from(byte).map_err(|error| Located {
+            line_number,
+            error: Positioned { position, error },
+        })
+    };
+    std::iter::from_fn(move || {
+        let codon = [bytes.next()?, bytes.next()?, bytes.next()?];
+        Some(match codon.map(parse) {
+            [Ok(a), Ok(b), Ok(c)] => Ok(translate([a, b, c])),
+            [Err(e), _, _] | [_, Err(e), _] | [_, _, Err(e)] => Err(e),
+        })
+    })
+}
+
+/// Parse a contents line, after checking it against [`FastaParseSettings::case_policy`].
+fn parse_contents<T: FastaContent>(
+    settings: &FastaParseSettings,
+    line_number: usize,
+    line: &str,
+) -> Result<T, Located<FastaParseError<T::Err>>> {
+    line.bytes()
+        .enumerate()
+        .try_for_each(|(position, b)| settings.case_policy.check(position, b))
+        .map_err(|error| Located {
+            line_number,
+            error: FastaParseError::Lowercase(error),
+        })?;
+    let mut contents = T::parse(line_number, line)?;
+    if settings.case_policy == CasePolicy::AllowLowercase {
+        contents.ignore_case();
     }
+    Ok(contents)
 }
 
-/// Try to parse a FASTA header (prefixed with > or ;), returning the line without the prefix char.
-fn try_parse_header(line: &str) -> Option<&str> {
-    let head = line.chars().next();
+/// Try to parse a FASTA header (prefixed with > or ;), returning the prefix char and the line
+/// without it.
+fn try_parse_header(line: &str) -> Option<(char, &str)> {
+    let head = line.chars().next()?;
     // ; is semi-obsolete alternative header char
-    if head == Some('>') || head == Some(';') {
-        Some(&line[1..]) // we know it's an ASCII char so this slice is panic-safe
+    if head == '>' || head == ';' {
+        Some((head, &line[1..])) // we know it's an ASCII char so this slice is panic-safe
     } else {
         None
     }
@@ -494,7 +1694,10 @@ fn try_parse_header(line: &str) -> Option<&str> {
 mod tests {
     use super::*;
 
-    use crate::{DnaSequence, Nucleotide, NucleotideAmbiguous, ProteinSequence, TranslationError};
+    use crate::{
+        masked::MaskedDnaSequence, DnaSequence, Nucleotide, NucleotideAmbiguous, Positioned,
+        ProteinSequence, ProteinValidation, TranslationError,
+    };
     use std::time::Duration;
 
     macro_rules! assert_parse {
@@ -533,6 +1736,7 @@ mod tests {
                     FastaParser::<String>::new(FastaParseSettings {
                         concatenate_headers,
                         allow_preceding_comment,
+                        ..FastaParseSettings::new()
                     }),
                     expected
                 );
@@ -553,6 +1757,7 @@ mod tests {
                 FastaParser::<String>::new(FastaParseSettings {
                     concatenate_headers,
                     allow_preceding_comment,
+                    ..FastaParseSettings::new()
                 }),
                 expected
             );
@@ -572,12 +1777,45 @@ mod tests {
                 FastaParser::<String>::new(FastaParseSettings {
                     concatenate_headers,
                     allow_preceding_comment,
+                    ..FastaParseSettings::new()
                 }),
                 expected
             );
         }
     }
 
+    /// Assert that parsing `s` in parallel, split into chunks that are as small as possible,
+    /// gives the same result as parsing it serially, with any combination of parser settings
+    #[cfg(feature = "rayon")]
+    fn assert_parallel_parse_matches<T>(s: &str)
+    where
+        T: FastaContent + std::fmt::Debug + Send,
+        T::Err: std::fmt::Debug + Send,
+    {
+        for flags in 0..1 << 5 {
+            for case_policy in [CasePolicy::AllowLowercase, CasePolicy::RejectLowercase] {
+                let parser = FastaParser::<T>::new(FastaParseSettings {
+                    concatenate_headers: flags & 1 != 0,
+                    allow_preceding_comment: flags & 2 != 0,
+                    preserve_header_prefix: flags & 4 != 0,
+                    semicolon_as_comment: flags & 8 != 0,
+                    preserve_trailing_empty_line: flags & 16 != 0,
+                    case_policy,
+                });
+                let serial = format!("{:?}", parser.parse_str(s));
+                for chunk_bytes in [0, 8, usize::MAX] {
+                    let parallel = parser.parse_chunked(std::io::Cursor::new(s), chunk_bytes);
+                    assert_eq!(
+                        format!("{parallel:?}"),
+                        serial,
+                        "{s:?} with settings = {:?}, chunk_bytes = {chunk_bytes}",
+                        parser.settings
+                    );
+                }
+            }
+        }
+    }
+
     /// Helper to panic if a closure doesn't complete within a specified Duration.
     /// Author: @shepmaster, https://github.com/rust-lang/rfcs/issues/2798#issuecomment-552949300
     fn panic_after<T: Send + 'static, F: FnOnce() -> T + Send + 'static>(d: Duration, f: F) -> T {
@@ -615,6 +1853,8 @@ mod tests {
             ">Virus\n",
             vec![FastaRecord {
                 header: "Virus".to_string(),
+                header_prefix: '>',
+                continuation_prefixes: Vec::new(),
                 contents: "".to_string(),
                 line_range: (1, 2),
             }],
@@ -624,6 +1864,8 @@ mod tests {
             ";Virus\n",
             vec![FastaRecord {
                 header: "Virus".to_string(),
+                header_prefix: '>',
+                continuation_prefixes: Vec::new(),
                 contents: "".to_string(),
                 line_range: (1, 2),
             }],
@@ -636,6 +1878,8 @@ mod tests {
             ">Virus\n\n",
             vec![FastaRecord {
                 header: "Virus".to_string(),
+                header_prefix: '>',
+                continuation_prefixes: Vec::new(),
                 contents: "".to_string(),
                 line_range: (1, 3),
             }],
@@ -644,6 +1888,8 @@ mod tests {
             ";Virus\n\n",
             vec![FastaRecord {
                 header: "Virus".to_string(),
+                header_prefix: '>',
+                continuation_prefixes: Vec::new(),
                 contents: "".to_string(),
                 line_range: (1, 3),
             }],
@@ -666,6 +1912,8 @@ mod tests {
             false,
             vec![FastaRecord {
                 header: "".to_string(),
+                header_prefix: '>',
+                continuation_prefixes: Vec::new(),
                 contents: "this is a file comment@author is foo".to_string(),
                 line_range: (1, 3),
             }],
@@ -679,6 +1927,8 @@ mod tests {
             true,
             vec![FastaRecord {
                 header: "Virus".to_string(),
+                header_prefix: '>',
+                continuation_prefixes: Vec::new(),
                 contents: "".to_string(),
                 line_range: (4, 6),
             }],
@@ -693,11 +1943,15 @@ mod tests {
             vec![
                 FastaRecord {
                     header: "".to_string(),
+                    header_prefix: '>',
+                    continuation_prefixes: Vec::new(),
                     contents: "this is a file comment@author is foo".to_string(),
                     line_range: (1, 4),
                 },
                 FastaRecord {
                     header: "Virus".to_string(),
+                    header_prefix: '>',
+                    continuation_prefixes: Vec::new(),
                     contents: "".to_string(),
                     line_range: (4, 6),
                 },
@@ -711,6 +1965,8 @@ mod tests {
             "   \t\n\r\t   \n\n>Virus\n\n",
             vec![FastaRecord {
                 header: "Virus".to_string(),
+                header_prefix: '>',
+                continuation_prefixes: Vec::new(),
                 contents: "".to_string(),
                 line_range: (4, 6),
             }],
@@ -723,6 +1979,8 @@ mod tests {
             ">Virus\nCAAAGT\n",
             vec![FastaRecord {
                 header: "Virus".to_string(),
+                header_prefix: '>',
+                continuation_prefixes: Vec::new(),
                 contents: "CAAAGT".to_string(),
                 line_range: (1, 3),
             }],
@@ -731,6 +1989,8 @@ mod tests {
             ";Virus\nCAAAGT\n",
             vec![FastaRecord {
                 header: "Virus".to_string(),
+                header_prefix: '>',
+                continuation_prefixes: Vec::new(),
                 contents: "CAAAGT".to_string(),
                 line_range: (1, 3),
             }],
@@ -743,6 +2003,8 @@ mod tests {
             ">Virus\nCAAAGT",
             vec![FastaRecord {
                 header: "Virus".to_string(),
+                header_prefix: '>',
+                continuation_prefixes: Vec::new(),
                 contents: "CAAAGT".to_string(),
                 line_range: (1, 3),
             }],
@@ -751,6 +2013,8 @@ mod tests {
             ";Virus\nCAAAGT",
             vec![FastaRecord {
                 header: "Virus".to_string(),
+                header_prefix: '>',
+                continuation_prefixes: Vec::new(),
                 contents: "CAAAGT".to_string(),
                 line_range: (1, 3),
             }],
@@ -763,6 +2027,8 @@ mod tests {
             ">Virus\nAAAA\nCCCC\nGGGG\n",
             vec![FastaRecord {
                 header: "Virus".to_string(),
+                header_prefix: '>',
+                continuation_prefixes: Vec::new(),
                 contents: "AAAACCCCGGGG".to_string(),
                 line_range: (1, 5),
             }],
@@ -771,6 +2037,8 @@ mod tests {
             ";Virus\nAAAA\nCCCC\nGGGG\n",
             vec![FastaRecord {
                 header: "Virus".to_string(),
+                header_prefix: '>',
+                continuation_prefixes: Vec::new(),
                 contents: "AAAACCCCGGGG".to_string(),
                 line_range: (1, 5),
             }],
@@ -784,11 +2052,15 @@ mod tests {
             vec![
                 FastaRecord {
                     header: "Virus1".to_string(),
+                    header_prefix: '>',
+                    continuation_prefixes: Vec::new(),
                     contents: "AAAA".to_string(),
                     line_range: (1, 3),
                 },
                 FastaRecord {
                     header: "Virus2".to_string(),
+                    header_prefix: '>',
+                    continuation_prefixes: Vec::new(),
                     contents: "CCCC".to_string(),
                     line_range: (3, 5),
                 },
@@ -799,11 +2071,15 @@ mod tests {
             vec![
                 FastaRecord {
                     header: "Virus1".to_string(),
+                    header_prefix: '>',
+                    continuation_prefixes: Vec::new(),
                     contents: "AAAA".to_string(),
                     line_range: (1, 3),
                 },
                 FastaRecord {
                     header: "Virus2".to_string(),
+                    header_prefix: '>',
+                    continuation_prefixes: Vec::new(),
                     contents: "CCCC".to_string(),
                     line_range: (3, 5),
                 },
@@ -814,11 +2090,15 @@ mod tests {
             vec![
                 FastaRecord {
                     header: "Virus1".to_string(),
+                    header_prefix: '>',
+                    continuation_prefixes: Vec::new(),
                     contents: "AAAA".to_string(),
                     line_range: (1, 3),
                 },
                 FastaRecord {
                     header: "Virus2".to_string(),
+                    header_prefix: '>',
+                    continuation_prefixes: Vec::new(),
                     contents: "CCCC".to_string(),
                     line_range: (3, 5),
                 },
@@ -829,11 +2109,15 @@ mod tests {
             vec![
                 FastaRecord {
                     header: "Virus1".to_string(),
+                    header_prefix: '>',
+                    continuation_prefixes: Vec::new(),
                     contents: "AAAA".to_string(),
                     line_range: (1, 3),
                 },
                 FastaRecord {
                     header: "Virus2".to_string(),
+                    header_prefix: '>',
+                    continuation_prefixes: Vec::new(),
                     contents: "CCCC".to_string(),
                     line_range: (3, 5),
                 },
@@ -848,11 +2132,15 @@ mod tests {
             vec![
                 FastaRecord {
                     header: "Virus1".to_string(),
+                    header_prefix: '>',
+                    continuation_prefixes: Vec::new(),
                     contents: "AAAAAAAA".to_string(),
                     line_range: (1, 4),
                 },
                 FastaRecord {
                     header: "Virus2".to_string(),
+                    header_prefix: '>',
+                    continuation_prefixes: Vec::new(),
                     contents: "CCCCCCCC".to_string(),
                     line_range: (4, 7),
                 },
@@ -863,11 +2151,15 @@ mod tests {
             vec![
                 FastaRecord {
                     header: "Virus1".to_string(),
+                    header_prefix: '>',
+                    continuation_prefixes: Vec::new(),
                     contents: "AAAAAAAA".to_string(),
                     line_range: (1, 4),
                 },
                 FastaRecord {
                     header: "Virus2".to_string(),
+                    header_prefix: '>',
+                    continuation_prefixes: Vec::new(),
                     contents: "CCCCCCCC".to_string(),
                     line_range: (4, 7),
                 },
@@ -878,11 +2170,15 @@ mod tests {
             vec![
                 FastaRecord {
                     header: "Virus1".to_string(),
+                    header_prefix: '>',
+                    continuation_prefixes: Vec::new(),
                     contents: "AAAAAAAA".to_string(),
                     line_range: (1, 4),
                 },
                 FastaRecord {
                     header: "Virus2".to_string(),
+                    header_prefix: '>',
+                    continuation_prefixes: Vec::new(),
                     contents: "CCCCCCCC".to_string(),
                     line_range: (4, 7),
                 },
@@ -893,11 +2189,15 @@ mod tests {
             vec![
                 FastaRecord {
                     header: "Virus1".to_string(),
+                    header_prefix: '>',
+                    continuation_prefixes: Vec::new(),
                     contents: "AAAAAAAA".to_string(),
                     line_range: (1, 4),
                 },
                 FastaRecord {
                     header: "Virus2".to_string(),
+                    header_prefix: '>',
+                    continuation_prefixes: Vec::new(),
                     contents: "CCCCCCCC".to_string(),
                     line_range: (4, 7),
                 },
@@ -912,16 +2212,22 @@ mod tests {
             vec![
                 FastaRecord {
                     header: "Virus1".to_string(),
+                    header_prefix: '>',
+                    continuation_prefixes: Vec::new(),
                     contents: "AAAAAAAA".to_string(),
                     line_range: (1, 4),
                 },
                 FastaRecord {
                     header: "Virus2".to_string(),
+                    header_prefix: '>',
+                    continuation_prefixes: Vec::new(),
                     contents: "CCCCCCCC".to_string(),
                     line_range: (4, 7),
                 },
                 FastaRecord {
                     header: "Virus3".to_string(),
+                    header_prefix: '>',
+                    continuation_prefixes: Vec::new(),
                     contents: "CCCCCCCC".to_string(),
                     line_range: (7, 10),
                 },
@@ -936,16 +2242,22 @@ mod tests {
             vec![
                 FastaRecord {
                     header: "Virus1".to_string(),
+                    header_prefix: '>',
+                    continuation_prefixes: Vec::new(),
                     contents: "AAAAAAAA".to_string(),
                     line_range: (1, 4),
                 },
                 FastaRecord {
                     header: "Virus2".to_string(),
+                    header_prefix: '>',
+                    continuation_prefixes: Vec::new(),
                     contents: "CCCCCCCC".to_string(),
                     line_range: (4, 7),
                 },
                 FastaRecord {
                     header: "Virus3".to_string(),
+                    header_prefix: '>',
+                    continuation_prefixes: Vec::new(),
                     contents: "".to_string(),
                     line_range: (7, 8),
                 },
@@ -960,6 +2272,8 @@ mod tests {
             true,
             vec![FastaRecord {
                 header: "a\nb".to_string(),
+                header_prefix: '>',
+                continuation_prefixes: Vec::new(),
                 contents: "test".to_string(),
                 line_range: (1, 4),
             }],
@@ -974,11 +2288,15 @@ mod tests {
             vec![
                 FastaRecord {
                     header: "a".to_string(),
+                    header_prefix: '>',
+                    continuation_prefixes: Vec::new(),
                     contents: "".to_string(),
                     line_range: (1, 2),
                 },
                 FastaRecord {
                     header: "b".to_string(),
+                    header_prefix: '>',
+                    continuation_prefixes: Vec::new(),
                     contents: "test".to_string(),
                     line_range: (2, 4),
                 },
@@ -993,6 +2311,8 @@ mod tests {
             true,
             vec![FastaRecord {
                 header: "a\nb".to_string(),
+                header_prefix: '>',
+                continuation_prefixes: Vec::new(),
                 contents: "".to_string(),
                 line_range: (1, 3),
             }],
@@ -1005,6 +2325,8 @@ mod tests {
         // Note that some "spacey" unicode characters, like U+200B "ZERO WIDTH SPACE", are White_Space=no
         assert_parse_with_all_settings("\u{2009}\r\n>ὦ Ᾰ̓θηνᾶ, Heizölrückstoßabdämpfungを持つ!\nPchnąć w tę łódź jeża lub ośm skrzyń fig", vec![FastaRecord {
             header: "ὦ Ᾰ̓θηνᾶ, Heizölrückstoßabdämpfungを持つ!".to_string(),
+            header_prefix: '>',
+            continuation_prefixes: Vec::new(),
             contents: "Pchnąć w tę łódź jeża lub ośm skrzyń fig".to_string(),
             line_range: (2, 4),
         }])
@@ -1037,6 +2359,8 @@ mod tests {
             false,
             vec![FastaRecord {
                 header: "".to_string(),
+                header_prefix: '>',
+                continuation_prefixes: Vec::new(),
                 contents: test_case,
                 line_range: (1, 2),
             }],
@@ -1052,6 +2376,8 @@ mod tests {
             "\r\n>i love compatability\r\nwindows is awesome\r\n",
             vec![FastaRecord {
                 header: "i love compatability".to_string(),
+                header_prefix: '>',
+                continuation_prefixes: Vec::new(),
                 contents: "windows is awesome".to_string(),
                 line_range: (2, 4),
             }],
@@ -1074,6 +2400,8 @@ mod tests {
                 true,
                 vec![FastaRecord {
                     header,
+                    header_prefix: '>',
+                    continuation_prefixes: Vec::new(),
                     contents: "".to_string(),
                     line_range: (1, 10_001),
                 }],
@@ -1097,6 +2425,8 @@ mod tests {
                 false,
                 vec![FastaRecord {
                     header: "".to_string(),
+                    header_prefix: '>',
+                    continuation_prefixes: Vec::new(),
                     contents,
                     line_range: (1, 10_001),
                 }],
@@ -1120,6 +2450,8 @@ mod tests {
                 &test_case,
                 vec![FastaRecord {
                     header: "header".to_string(),
+                    header_prefix: '>',
+                    continuation_prefixes: Vec::new(),
                     contents,
                     line_range: (1, 10_002),
                 }],
@@ -1134,6 +2466,8 @@ mod tests {
             FastaParser::<DnaSequence<Nucleotide>>::default(),
             vec![FastaRecord {
                 header: "Virus1".to_string(),
+                header_prefix: '>',
+                continuation_prefixes: Vec::new(),
                 contents: "AAAA".parse().unwrap(),
                 line_range: (1, 3),
             }]
@@ -1147,6 +2481,8 @@ mod tests {
             FastaParser::<DnaSequence<NucleotideAmbiguous>>::default(),
             vec![FastaRecord {
                 header: "Virus1".to_string(),
+                header_prefix: '>',
+                continuation_prefixes: Vec::new(),
                 contents: "ABCD".parse().unwrap(),
                 line_range: (1, 3),
             }]
@@ -1161,9 +2497,10 @@ mod tests {
             FastaParser::<DnaSequence<Nucleotide>>::default(),
             Located {
                 line_number: 2,
-                error: FastaParseError::ParseError(
-                    TranslationError::UnexpectedAmbiguousNucleotide('B')
-                )
+                error: FastaParseError::ParseError(Positioned {
+                    position: 1,
+                    error: TranslationError::UnexpectedAmbiguousNucleotide('B')
+                })
             }
         );
     }
@@ -1176,11 +2513,15 @@ mod tests {
             vec![
                 FastaRecord {
                     header: "Virus1".to_string(),
+                    header_prefix: '>',
+                    continuation_prefixes: Vec::new(),
                     contents: "AAAAAAAA".parse().unwrap(),
                     line_range: (1, 4),
                 },
                 FastaRecord {
                     header: "Virus2".to_string(),
+                    header_prefix: '>',
+                    continuation_prefixes: Vec::new(),
                     contents: "CCCCCCCC".parse().unwrap(),
                     line_range: (4, 7),
                 },
@@ -1192,11 +2533,15 @@ mod tests {
             vec![
                 FastaRecord {
                     header: "Virus1".to_string(),
+                    header_prefix: '>',
+                    continuation_prefixes: Vec::new(),
                     contents: "AAAAAAAA".parse().unwrap(),
                     line_range: (1, 4),
                 },
                 FastaRecord {
                     header: "Virus2".to_string(),
+                    header_prefix: '>',
+                    continuation_prefixes: Vec::new(),
                     contents: "CCCCRRRR".parse().unwrap(),
                     line_range: (4, 7),
                 },
@@ -1211,6 +2556,8 @@ mod tests {
             FastaParser::<DnaSequence<Nucleotide>>::default(),
             vec![FastaRecord {
                 header: "Virus1".to_string(),
+                header_prefix: '>',
+                continuation_prefixes: Vec::new(),
                 contents: "AAAA".parse().unwrap(),
                 line_range: (1, 3),
             }]
@@ -1221,6 +2568,8 @@ mod tests {
             FastaParser::<DnaSequence<NucleotideAmbiguous>>::default(),
             vec![FastaRecord {
                 header: "Virus1".to_string(),
+                header_prefix: '>',
+                continuation_prefixes: Vec::new(),
                 contents: "AAAABCD".parse().unwrap(),
                 line_range: (1, 3),
             }]
@@ -1234,7 +2583,10 @@ mod tests {
             FastaParser::<DnaSequence<Nucleotide>>::default(),
             Located {
                 line_number: 2,
-                error: FastaParseError::ParseError(TranslationError::BadNucleotide('e'))
+                error: FastaParseError::ParseError(Positioned {
+                    position: 3,
+                    error: TranslationError::BadNucleotide('e')
+                })
             }
         );
         assert_parse_err!(
@@ -1242,7 +2594,10 @@ mod tests {
             FastaParser::<DnaSequence<NucleotideAmbiguous>>::default(),
             Located {
                 line_number: 2,
-                error: FastaParseError::ParseError(TranslationError::BadNucleotide('e'))
+                error: FastaParseError::ParseError(Positioned {
+                    position: 3,
+                    error: TranslationError::BadNucleotide('e')
+                })
             }
         );
     }
@@ -1254,7 +2609,10 @@ mod tests {
             FastaParser::<DnaSequence<Nucleotide>>::default(),
             Located {
                 line_number: 4,
-                error: FastaParseError::ParseError(TranslationError::BadNucleotide('e'))
+                error: FastaParseError::ParseError(Positioned {
+                    position: 4,
+                    error: TranslationError::BadNucleotide('e')
+                })
             }
         );
         assert_parse_err!(
@@ -1262,7 +2620,10 @@ mod tests {
             FastaParser::<DnaSequence<NucleotideAmbiguous>>::default(),
             Located {
                 line_number: 4,
-                error: FastaParseError::ParseError(TranslationError::BadNucleotide('e'))
+                error: FastaParseError::ParseError(Positioned {
+                    position: 4,
+                    error: TranslationError::BadNucleotide('e')
+                })
             }
         );
     }
@@ -1274,7 +2635,10 @@ mod tests {
             FastaParser::<DnaSequence<Nucleotide>>::default(),
             Located {
                 line_number: 2,
-                error: FastaParseError::ParseError(TranslationError::NonAsciiByte(196))
+                error: FastaParseError::ParseError(Positioned {
+                    position: 2,
+                    error: TranslationError::NonAsciiByte(196)
+                })
             }
         );
         assert_parse_err!(
@@ -1282,7 +2646,10 @@ mod tests {
             FastaParser::<DnaSequence<NucleotideAmbiguous>>::default(),
             Located {
                 line_number: 2,
-                error: FastaParseError::ParseError(TranslationError::NonAsciiByte(196))
+                error: FastaParseError::ParseError(Positioned {
+                    position: 2,
+                    error: TranslationError::NonAsciiByte(196)
+                })
             }
         );
     }
@@ -1294,6 +2661,8 @@ mod tests {
             FastaParser::<ProteinSequence>::default(),
             vec![FastaRecord {
                 header: "Virus1".to_string(),
+                header_prefix: '>',
+                continuation_prefixes: Vec::new(),
                 contents: "AAAA".parse().unwrap(),
                 line_range: (1, 3),
             }]
@@ -1308,11 +2677,15 @@ mod tests {
             vec![
                 FastaRecord {
                     header: "Virus1".to_string(),
+                    header_prefix: '>',
+                    continuation_prefixes: Vec::new(),
                     contents: "AAAAAAAA".parse().unwrap(),
                     line_range: (1, 4),
                 },
                 FastaRecord {
                     header: "Virus2".to_string(),
+                    header_prefix: '>',
+                    continuation_prefixes: Vec::new(),
                     contents: "CCCCCCCC".parse().unwrap(),
                     line_range: (4, 7),
                 },
@@ -1327,11 +2700,15 @@ mod tests {
             vec![
                 FastaRecord {
                     header: "Virus1".to_string(),
+                    header_prefix: '>',
+                    continuation_prefixes: Vec::new(),
                     contents: "AAAAAAAA".parse().unwrap(),
                     line_range: (1, 4),
                 },
                 FastaRecord {
                     header: "Virus1".to_string(),
+                    header_prefix: '>',
+                    continuation_prefixes: Vec::new(),
                     contents: "CCCCCCCC".parse().unwrap(),
                     line_range: (4, 7),
                 },
@@ -1341,13 +2718,12 @@ mod tests {
 
     #[test]
     fn test_to_string() {
-        let parser = FastaParser::<DnaSequence<Nucleotide>>::default();
         let string = ">Virus1\nAC\nT\n>Empty\n\n>Virus2\n>with many\n>comment lines\nC  AT";
-        let parsed = parser.parse_str(string).unwrap();
+        let parsed: FastaFile<DnaSequence<Nucleotide>> = string.parse().unwrap();
 
         // Test: if we to_string the parsed file and parse it again, we should
         // get the same records again, ignoring line_range.
-        let reparsed = parser.parse_str(&parsed.to_string()).unwrap();
+        let reparsed: FastaFile<DnaSequence<Nucleotide>> = parsed.to_string().parse().unwrap();
 
         assert_eq!(parsed.records.len(), 3);
         assert_eq!(reparsed.records.len(), 3);
@@ -1357,6 +2733,482 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_preserve_header_prefix() {
+        let string = ";Virus1\nACT\n>Virus2\n;with comment\nCAT\n;Empty\n";
+        let parser = FastaParser::<DnaSequence<Nucleotide>>::new(
+            FastaParseSettings::new().preserve_header_prefix(true),
+        );
+        let parsed = parser.parse_str(string).unwrap();
+        let prefixes: Vec<char> = parsed.records.iter().map(|r| r.header_prefix).collect();
+        assert_eq!(prefixes, [';', '>', ';']);
+        assert_eq!(parsed.records[1].header, "Virus2\nwith comment");
+        assert_eq!(
+            parsed.records[1].header_lines().collect::<Vec<_>>(),
+            [('>', "Virus2"), (';', "with comment")]
+        );
+        assert_eq!(
+            parsed.to_string(),
+            ";Virus1\nACT\n>Virus2\n;with comment\nCAT\n;Empty\n\n"
+        );
+
+        let normalized: FastaFile<DnaSequence<Nucleotide>> = string.parse().unwrap();
+        assert!(normalized
+            .records
+            .iter()
+            .all(|r| r.header_lines().all(|(prefix, _)| prefix == '>')));
+        let reparsed = parser.parse_str(&parsed.to_string()).unwrap();
+        assert_eq!(parsed.to_string(), reparsed.to_string());
+
+        let record = parsed.records[1].clone().with_header_prefix(';');
+        assert_eq!(record.to_string(), ";Virus2\n;with comment\nCAT\n");
+    }
+
+    #[test]
+    fn test_preserve_mixed_header_prefixes() {
+        let string = ">a\n>b\n;c\n>d\nACGT\n;e\n;f\nGG\n";
+        let parser = FastaParser::<DnaSequence<Nucleotide>>::new(
+            FastaParseSettings::new().preserve_header_prefix(true),
+        );
+        let parsed = parser.parse_str(string).unwrap();
+        assert_eq!(parsed.to_string(), string);
+        assert_eq!(
+            parsed.records[0].header_lines().collect::<Vec<_>>(),
+            [('>', "a"), ('>', "b"), (';', "c"), ('>', "d")]
+        );
+        // a header with a single prefix compares equal to one built from scratch
+        assert_eq!(
+            parsed.records[1],
+            FastaRecord::new("e\nf", "GG".parse().unwrap())
+                .with_header_prefix(';')
+                .with_line_range((6, 9))
+        );
+    }
+
+    #[test]
+    fn test_semicolon_as_comment() {
+        let string = ";Virus1\nACT\n>Virus2\n;with comment\nCAT\n;comment\nGAT\n;trailing";
+        let parser = FastaParser::<DnaSequence<Nucleotide>>::new(
+            FastaParseSettings::new().semicolon_as_comment(true),
+        );
+        let parsed = parser.parse_str(string).unwrap();
+        let headers: Vec<&str> = parsed.records.iter().map(|r| r.header.as_str()).collect();
+        assert_eq!(headers, ["Virus1", "Virus2"]);
+        assert_eq!(parsed.records[1].contents.to_string(), "CATGAT");
+        assert_eq!(parsed.records[1].line_range, (3, 9));
+
+        let default: FastaFile<DnaSequence<Nucleotide>> = string.parse().unwrap();
+        assert_eq!(default.records.len(), 4);
+    }
+
+    /// Contents that remember each line they were parsed from.
+    #[derive(Debug, Default, PartialEq)]
+    struct Lines(Vec<String>);
+
+    impl FromStr for Lines {
+        type Err = std::convert::Infallible;
+
+        fn from_str(line: &str) -> Result<Self, Self::Err> {
+            Ok(Self(vec![line.to_string()]))
+        }
+    }
+
+    impl Extendable for Lines {
+        fn is_blank(&self) -> bool {
+            self.0.iter().all(|line| line.trim().is_empty())
+        }
+
+        fn extend(&mut self, other: Self) {
+            self.0.extend(other.0)
+        }
+
+        fn extend_empty_line(&mut self) {
+            self.0.push(String::new())
+        }
+    }
+
+    /// Contents that must have an even number of lines.
+    #[derive(Debug, Default, PartialEq)]
+    struct EvenLines(Lines);
+
+    impl Extendable for EvenLines {
+        fn is_blank(&self) -> bool {
+            self.0.is_blank()
+        }
+
+        fn extend(&mut self, other: Self) {
+            self.0.extend(other.0)
+        }
+    }
+
+    impl FastaContent for EvenLines {
+        type Err = usize;
+
+        fn parse(_: usize, line: &str) -> Result<Self, Located<FastaParseError<usize>>> {
+            Ok(Self(Lines(vec![line.to_string()])))
+        }
+
+        fn finalize(&mut self) -> Result<(), usize> {
+            let Self(Lines(lines)) = self;
+            match lines.len() % 2 {
+                0 => Ok(()),
+                _ => Err(lines.len()),
+            }
+        }
+    }
+
+    #[test]
+    fn test_finalize() {
+        let parser = FastaParser::<EvenLines>::default();
+        let parsed = parser.parse_str(">a\nAC\nGT\n>b\n>c\nA\nC").unwrap();
+        assert_eq!(parsed.records.len(), 2);
+
+        // Every completed record is finalized, including the last and empty ones.
+        let parser =
+            FastaParser::<EvenLines>::new(FastaParseSettings::new().concatenate_headers(false));
+        assert!(parser.parse_str(">a\n>b\nAC\nGT").is_ok());
+        for (fasta, line_number, len) in [
+            (">a\nAC\n>b\nGT\nAC\n", 1, 1),
+            (">a\nAC\nGT\n>b\nGT\n", 4, 1),
+            ("AC\n>a\nAC\nGT", 1, 1),
+        ] {
+            let err = parser.parse_str(fasta).unwrap_err();
+            assert_eq!(err.line_number, line_number);
+            assert!(matches!(err.error, FastaParseError::ParseError(l) if l == len));
+        }
+    }
+
+    #[test]
+    fn test_preserve_trailing_empty_line() {
+        let string = ">a\nACGT\n\nAC\n\n";
+        let parser = FastaParser::<Lines>::default();
+        let parsed = parser.parse_str(string).unwrap();
+        assert_eq!(parsed.records[0].contents.0, ["ACGT", "AC"]);
+
+        let settings = FastaParseSettings::new().preserve_trailing_empty_line(true);
+        let parsed = FastaParser::<Lines>::new(settings)
+            .parse_str(string)
+            .unwrap();
+        assert_eq!(parsed.records[0].contents.0, ["ACGT", "AC", ""]);
+        assert_eq!(parsed.records[0].line_range, (1, 6));
+
+        let string = ">a\nACGT\n\n>b\n\n>c\nAC\n\n";
+        let parsed = FastaParser::<String>::new(settings)
+            .parse_str(string)
+            .unwrap();
+        let contents: Vec<_> = parsed.records.iter().map(|r| r.contents.as_str()).collect();
+        assert_eq!(contents, ["ACGT\n", "", "AC\n"]);
+        assert_eq!(parsed.to_string(), string);
+
+        // sequences have no line breaks to keep
+        let parsed = FastaParser::<DnaSequence<Nucleotide>>::new(settings)
+            .parse_str(string)
+            .unwrap();
+        assert_eq!(parsed.records[0].contents.to_string(), "ACGT");
+    }
+
+    #[test]
+    fn test_case_policy() {
+        let string = ">lowercase header\nACGT\nACgT\n";
+        let parsed: FastaFile<String> = string.parse().unwrap();
+        assert_eq!(parsed.records[0].contents, "ACGTACgT");
+        let unmasked: FastaFile<MaskedDnaSequence<Nucleotide>> = string.parse().unwrap();
+        assert_eq!(
+            unmasked.records[0].contents.soft_masked_string(),
+            "ACGTACGT"
+        );
+        let masked = FastaParser::<MaskedDnaSequence<Nucleotide>>::new(
+            FastaParseSettings::new().case_policy(CasePolicy::PreserveCase),
+        )
+        .parse_str(string)
+        .unwrap();
+        assert_eq!(masked.records[0].contents.soft_masked_string(), "ACGTACgT");
+
+        let settings = FastaParseSettings::new().case_policy(CasePolicy::RejectLowercase);
+        let err = FastaParser::<String>::new(settings)
+            .parse_str(string)
+            .unwrap_err();
+        assert_eq!(err.line_number, 3);
+        assert!(matches!(
+            err.error,
+            FastaParseError::Lowercase(Positioned {
+                position: 2,
+                error: TranslationError::UnexpectedLowercase('g')
+            })
+        ));
+
+        // Only lines that are parsed as contents are checked.
+        let parser = FastaParser::<String>::new(settings.allow_preceding_comment(true));
+        assert!(parser.parse_str("a comment\n>a\nACGT").is_ok());
+        let parser = FastaParser::<String>::new(settings);
+        assert_eq!(
+            parser
+                .parse_str("not a comment\n>a\nACGT")
+                .unwrap_err()
+                .line_number,
+            1
+        );
+    }
+
+    #[test]
+    fn test_owned_errors() {
+        let err = FastaParser::<String>::default()
+            .parse(&b">a\nAC\n\xff"[..])
+            .unwrap_err()
+            .into_owned();
+        assert_eq!(err.line_number, 3);
+        assert!(matches!(
+            &err.error,
+            OwnedFastaParseError::IOError { kind: io::ErrorKind::InvalidData, message }
+                if !message.is_empty()
+        ));
+        assert_eq!(err.clone(), err);
+        assert!(err
+            .to_string()
+            .starts_with("on line 3: error reading from reader: "));
+
+        let settings = FastaParseSettings::new().case_policy(CasePolicy::RejectLowercase);
+        let errors: Vec<_> = [">a\nAc", ">a\nAC\n>b\nAc"]
+            .into_iter()
+            .map(|s| {
+                FastaParser::<DnaSequence<Nucleotide>>::new(settings)
+                    .parse_str(s)
+                    .unwrap_err()
+                    .into_owned()
+            })
+            .collect();
+        let lowercase = OwnedFastaParseError::Lowercase(Positioned {
+            position: 1,
+            error: TranslationError::UnexpectedLowercase('c'),
+        });
+        assert_eq!(errors[0].error, lowercase);
+        assert_eq!(errors[1].error, lowercase);
+        assert_ne!(errors[0], errors[1]);
+    }
+
+    #[test]
+    fn test_records() {
+        let fasta = "ACGT\n>a\n>b\nAC\nGT\n\n>c\nAXGT\n>d\nA";
+        for settings in [
+            FastaParseSettings::new(),
+            FastaParseSettings::new()
+                .concatenate_headers(false)
+                .allow_preceding_comment(true),
+        ] {
+            let parser = FastaParser::<DnaSequence<Nucleotide>>::new(settings);
+            let mut records: Vec<_> = parser.records(fasta.as_bytes()).collect();
+            let last = records.pop().unwrap();
+            assert_eq!(last.unwrap_err().line_number, 8);
+            let records: Vec<_> = records.into_iter().map(Result::unwrap).collect();
+            let parsed = parser
+                .parse_str(&fasta[..fasta.find(">c").unwrap()])
+                .unwrap();
+            assert_eq!(records, parsed.records);
+        }
+
+        let parser = FastaParser::<DnaSequence<Nucleotide>>::default();
+        let mut records = parser.records(&b">a\nAC\n\xff\n>b\nGT"[..]);
+        assert!(records.next().unwrap().is_err());
+        assert!(records.next().is_none());
+        assert!(parser.records(&b""[..]).next().is_none());
+    }
+
+    /// A writer that fails once more than `.0` bytes have been written to it.
+    struct FailingWriter(usize);
+
+    impl io::Write for FailingWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if self.0 < buf.len() {
+                return Err(io::Error::other("disk full"));
+            }
+            self.0 -= buf.len();
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_reverse_complement_fasta() {
+        let rc = |fasta: &str, settings| {
+            let mut out = vec![];
+            reverse_complement_fasta::<NucleotideAmbiguous, _, _>(
+                fasta.as_bytes(),
+                &mut out,
+                settings,
+            )
+            .map(|()| String::from_utf8(out).unwrap())
+        };
+        let settings = FastaParseSettings::new();
+        assert_eq!(rc("", settings).unwrap(), "");
+        assert_eq!(
+            rc(
+                "GAT\n;a\nGG\nAC\n>c\n",
+                settings.preserve_header_prefix(true)
+            )
+            .unwrap(),
+            "ATC\n;a\nGTCC\n>c\n"
+        );
+        assert_eq!(
+            rc(">a\n>b\nRY\n>c\n", settings.concatenate_headers(false)).unwrap(),
+            ">a\n\n>b\nRY\n>c\n"
+        );
+
+        let fasta = ">a one\nGATTACA\nNNRY\n>b two\nacgtn\n";
+        let once = rc(fasta, settings).unwrap();
+        assert_eq!(once, ">a one\nRYNNTGTAATC\n>b two\nNACGT\n");
+        assert_eq!(
+            rc(&once, settings).unwrap(),
+            ">a one\nGATTACANNRY\n>b two\nACGTN\n"
+        );
+
+        let err = rc(">a\nACGT\n>b\nAC-T\n", settings).unwrap_err();
+        let ReverseComplementFastaError::Parse(err) = err else {
+            panic!("expected a parse error, got {err:?}");
+        };
+        assert_eq!(err.line_number, 4);
+
+        let err = reverse_complement_fasta::<NucleotideAmbiguous, _, _>(
+            fasta.as_bytes(),
+            FailingWriter(10),
+            settings,
+        )
+        .unwrap_err();
+        assert!(matches!(err, ReverseComplementFastaError::Write(_)));
+        assert!(err.to_string().ends_with("disk full"));
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_parse_parallel() {
+        let corpus = [
+            "",
+            "\n",
+            ">Virus\n\n",
+            "this is a file comment\n@author is foo\n\n>Virus\n\n",
+            "   \t\n\r\t   \n\n>Virus\n\n",
+            ";Virus1\nAAAA\nAAAA\n>Virus2\nCCCC\nCCCC\n",
+            ">Virus1\nAAAA\nAAAA\n>Virus2\nCCCC\nCCCC\n>Virus3",
+            ">a\n>b\ntest\n>c\n\n>d\n;e\n>f\nAC\n;g\n>h\nGT",
+            "\r\n>i love compatability\r\nwindows is awesome\r\n>a\r\nacgt\r\n",
+            ">a\nACGT\n>b\nACxT\n>c\nACyT\n",
+        ];
+        for s in corpus {
+            assert_parallel_parse_matches::<String>(s);
+            assert_parallel_parse_matches::<DnaSequence<Nucleotide>>(s);
+        }
+
+        let parser = FastaParser::<String>::default();
+        let mut handle = std::io::Cursor::new(b"skipped\n>a\nACGT\n>b\nTT");
+        handle.set_position(8);
+        let parsed = parser.parse_chunked(handle, 0).unwrap();
+        assert_eq!(parsed, parser.parse_str(">a\nACGT\n>b\nTT").unwrap());
+
+        let err = parser
+            .parse_chunked(std::io::Cursor::new(b">a\nAC\n>b\n\xff\n"), 0)
+            .unwrap_err();
+        assert_eq!(err.line_number, 4);
+        assert!(matches!(err.error, FastaParseError::IOError(_)));
+    }
+
+    #[cfg(feature = "rayon")]
+    quickcheck::quickcheck! {
+        fn parse_parallel_matches_serial(lines: Vec<u8>, trailing_newline: bool) -> bool {
+            const LINES: [&str; 8] = [">a", ";b", "ACGT", "", "acgt", "x", "  ", "TT\r"];
+            let mut s = lines
+                .iter()
+                .map(|&i| LINES[i as usize % LINES.len()])
+                .collect::<Vec<_>>()
+                .join("\n");
+            if trailing_newline {
+                s.push('\n');
+            }
+            assert_parallel_parse_matches::<String>(&s);
+            assert_parallel_parse_matches::<DnaSequence<Nucleotide>>(&s);
+            true
+        }
+    }
+
+    #[test]
+    fn test_translate_lines() {
+        let text = ">a\nATGC\nXGTAGATN\nTG";
+        let file: FastaFile<String> = text.parse().unwrap();
+        let start = file.records[0].line_range.0 + 1;
+        let lines = text.lines().skip(1).zip(start..).map(|(line, i)| (i, line));
+        let located: Vec<_> = translate_lines::<Nucleotide, _>(lines, TranslationTable::Ncbi1)
+            .map(|r| r.map_err(|e| (e.line_number, e.error.position)))
+            .collect();
+        assert_eq!(located, [Ok(b'M'), Err((3, 0)), Ok(b'*'), Err((3, 7))]);
+    }
+
+    #[test]
+    fn test_translate_all() {
+        let string = ">a\nATGAAAT\nGC\n>b\nTG\n>c\nATGA";
+        let file: FastaFile<DnaSequence<NucleotideAmbiguous>> = string.parse().unwrap();
+        let table = TranslationTable::Ncbi1;
+
+        let proteins = file.translate_all(table);
+        assert_eq!(proteins.records.len(), 3);
+        for (record, protein) in file.records.iter().zip(&proteins.records) {
+            assert_eq!(protein.header, record.header);
+            assert_eq!(protein.line_range, record.line_range);
+            assert_eq!(protein.contents, record.contents.translate(table));
+        }
+
+        let frames = file.translate_all_frames(table);
+        let headers: Vec<_> = frames.records.iter().map(|r| r.header.as_str()).collect();
+        assert_eq!(
+            headers,
+            [
+                "a frame=+1",
+                "a frame=+2",
+                "a frame=+3",
+                "a frame=-1",
+                "a frame=-2",
+                "a frame=-3",
+                "c frame=+1",
+                "c frame=+2",
+                "c frame=-1",
+                "c frame=-2",
+            ]
+        );
+        assert_eq!(frames.records[6].line_range, file.records[2].line_range);
+        let proteins: Vec<_> = frames.records[..6].iter().map(|r| &r.contents).collect();
+        assert!(proteins
+            .into_iter()
+            .eq(&file.records[0].contents.translate_all_frames(table)));
+    }
+
+    #[test]
+    fn test_write_split() {
+        let dir = std::env::temp_dir().join(format!("quickdna-split-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file: FastaFile<DnaSequence<Nucleotide>> =
+            ">a one\nACGT\n>b\nTT\n>a two\nGG\n>a three\nCC"
+                .parse()
+                .unwrap();
+        let name_fn = |record: &FastaRecord<_>| dir.join(format!("{}.fa", record.id()));
+
+        let err = file
+            .write_split(name_fn, DuplicatePaths::Error)
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+        assert!(!dir.join("a.fa").exists());
+
+        let paths = file.write_split(name_fn, DuplicatePaths::Suffix).unwrap();
+        let names: Vec<_> = paths.iter().map(|p| p.file_name().unwrap()).collect();
+        assert_eq!(names, ["a.fa", "b.fa", "a_1.fa", "a_2.fa"]);
+        for (record, path) in file.records.iter().zip(&paths) {
+            let written: FastaFile<DnaSequence<Nucleotide>> =
+                std::fs::read_to_string(path).unwrap().parse().unwrap();
+            assert_eq!(written.records[0].header, record.header);
+            assert_eq!(written.records[0].contents, record.contents);
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
     #[test]
     fn test_fasta_file_into_iter() {
         let parser = FastaParser::<DnaSequence<Nucleotide>>::default();
@@ -1373,11 +3225,15 @@ mod tests {
             [
                 FastaRecord {
                     header: "Virus1".to_owned(),
+                    header_prefix: '>',
+                    continuation_prefixes: Vec::new(),
                     contents: "CAT".parse().unwrap(),
                     line_range: (1, 3)
                 },
                 FastaRecord {
                     header: "Virus2".to_owned(),
+                    header_prefix: '>',
+                    continuation_prefixes: Vec::new(),
                     contents: "TAG".parse().unwrap(),
                     line_range: (3, 5)
                 }
@@ -1391,7 +3247,7 @@ mod tests {
         let string = ">Virus1\nAAA\nCCCxGGG";
         assert_eq!(
             parser.parse_str(string).unwrap_err().to_string(),
-            "on line 3: error parsing record: bad nucleotide: 'x'"
+            "on line 3: error parsing record: at column 4: bad nucleotide: 'x'"
         )
     }
 
@@ -1414,7 +3270,173 @@ mod tests {
         let round_trip: FastaFile<DnaSequence<NucleotideAmbiguous>> =
             serde_json::from_value(json).unwrap();
         assert_eq!(file, round_trip);
+
+        // Records serialized before `header_prefix` existed default to '>'
+        let legacy: FastaRecord<DnaSequence<NucleotideAmbiguous>> = serde_json::from_value(
+            serde_json::json!({"header": "Virus1", "contents": "CAR", "line_range": [1, 3]}),
+        )
+        .unwrap();
+        assert_eq!(legacy, file.records[0]);
+
+        // Only a non-default prefix is serialized
+        let record = file.records[0].clone().with_header_prefix(';');
+        let json = serde_json::to_value(&record).unwrap();
+        assert_eq!(json["header_prefix"], ";");
+        assert_eq!(
+            serde_json::from_value::<FastaRecord<DnaSequence<NucleotideAmbiguous>>>(json).unwrap(),
+            record
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_numbered_fasta_serde_json() {
+        let string = ">Virus1 a virus\ncar \n>Virus2\nBAG\n> leading space";
+        let file: FastaFile<DnaSequence<NucleotideAmbiguous>> = string.parse().unwrap();
+        assert_eq!(file.records[2].id(), "");
+        assert_eq!(file.records[2].description(), "leading space");
+
+        let json = serde_json::to_value(NumberedFasta(file.clone())).unwrap();
+        assert_eq!(json[0]["description"], "a virus");
+        assert_eq!(json[1]["line_start"], 3);
+        assert_eq!(json[2]["index"], 2);
+
+        let round_trip: NumberedFasta<DnaSequence<NucleotideAmbiguous>> =
+            serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(round_trip.0, file);
+
+        // headers that wouldn't come back the same are rejected rather than mangled
+        for string in [
+            ">a\tb\nAC",
+            ">\tleading tab\nAC",
+            ">a\n>b\nAC",
+            ">trailing \nAC",
+        ] {
+            let file: FastaFile<DnaSequence<NucleotideAmbiguous>> = string.parse().unwrap();
+            assert!(
+                serde_json::to_value(NumberedFasta(file)).is_err(),
+                "{string:?}"
+            );
+        }
+        let file = FastaParser::<DnaSequence<NucleotideAmbiguous>>::new(
+            FastaParseSettings::new().preserve_header_prefix(true),
+        )
+        .parse_str(";a\nAC")
+        .unwrap();
+        assert!(serde_json::to_value(NumberedFasta(file)).is_err());
+
+        let mut swapped = json;
+        swapped.as_array_mut().unwrap().swap(0, 1);
+        assert!(
+            serde_json::from_value::<NumberedFasta<DnaSequence<NucleotideAmbiguous>>>(swapped)
+                .is_err()
+        );
     }
 
-    // TODO: when we add validation for ProteinSequence, add tests for that here
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_settings_serde_json() {
+        let settings = FastaParseSettings::new()
+            .concatenate_headers(false)
+            .allow_preceding_comment(true);
+        let json = serde_json::to_value(settings).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "concatenate_headers": false,
+                "allow_preceding_comment": true,
+                "preserve_header_prefix": false,
+                "semicolon_as_comment": false,
+                "preserve_trailing_empty_line": false,
+                "case_policy": "AllowLowercase"
+            })
+        );
+        let round_trip: FastaParseSettings = serde_json::from_value(json).unwrap();
+        assert_eq!(round_trip, settings);
+
+        let partial: FastaParseSettings =
+            serde_json::from_value(serde_json::json!({"allow_preceding_comment": true})).unwrap();
+        assert_eq!(
+            partial,
+            FastaParseSettings::new().allow_preceding_comment(true)
+        );
+        let empty: FastaParseSettings = serde_json::from_value(serde_json::json!({})).unwrap();
+        assert_eq!(empty, FastaParseSettings::default());
+    }
+
+    #[test]
+    fn test_mates() {
+        let string =
+            ">r1/1\nA\n>r1/2 desc\nA\n>r2 1:N:0:GATT\nA\n>r2 2:Y:0:GATT\nA\n>r3/3 1\nA\n>/1\nA";
+        let file: FastaFile<DnaSequence<Nucleotide>> = string.parse().unwrap();
+        let mates: Vec<_> = file.records.iter().map(|r| r.mate()).collect();
+        assert_eq!(
+            mates,
+            [
+                Some(Mate::First),
+                Some(Mate::Second),
+                Some(Mate::First),
+                Some(Mate::Second),
+                None,
+                Some(Mate::First),
+            ]
+        );
+        let base_ids: Vec<_> = file.records.iter().map(|r| r.base_id()).collect();
+        assert_eq!(base_ids, ["r1", "r1", "r2", "r2", "r3/3", ""]);
+    }
+
+    #[test]
+    fn test_record_validation() {
+        let contents: DnaSequence<Nucleotide> = "ACGT".parse().unwrap();
+        let record = FastaRecord::new("seq1\nmore header", contents.clone());
+        assert_eq!(record.line_range, (0, 0));
+        assert!(record.validate().is_ok());
+        let file: FastaFile<DnaSequence<Nucleotide>> = record.to_string().parse().unwrap();
+        assert_eq!(file.records[0], record.clone().with_line_range((1, 4)));
+
+        // header lines are all prefixed when written, so '>' in a header survives
+        let record = FastaRecord::new("seq1\n>seq2", contents.clone());
+        assert!(record.validate().is_ok());
+        let file: FastaFile<DnaSequence<Nucleotide>> = record.to_string().parse().unwrap();
+        assert_eq!(file.records[0], record.clone().with_line_range((1, 4)));
+
+        let record = FastaRecord::new("seq1", "ACGT\n;comment");
+        assert_eq!(
+            record.validate(),
+            Err(FastaRecordError::ContentsLineStartsWithPrefix(2))
+        );
+
+        let file = FastaFile {
+            records: vec![
+                FastaRecord::new("", contents.clone()),
+                FastaRecord::new("seq1", contents.clone()),
+                FastaRecord::new("", contents),
+            ],
+        };
+        assert_eq!(file.validate(), Err((2, FastaRecordError::EmptyHeader)));
+        let reparsed: FastaFile<DnaSequence<Nucleotide>> = file.to_string().parse().unwrap();
+        assert_eq!(reparsed.records.len(), 2);
+        assert!(FastaFile {
+            records: file.records[..2].to_vec()
+        }
+        .validate()
+        .is_ok());
+    }
+
+    #[test]
+    fn test_protein_validation() {
+        let parser = FastaParser::<ProteinSequence>::default();
+        let file = parser
+            .parse_str(">Protein1\nmkv\n>Protein2\nMKV*\n")
+            .unwrap();
+        assert!(file.records[0].contents.validate_standard().is_ok());
+        assert!(matches!(
+            file.records[1].contents.validate_standard(),
+            Err(TranslationError::BadAminoAcid('*'))
+        ));
+        assert!(file.records[1]
+            .contents
+            .validate(ProteinValidation::new().allow_stop(true))
+            .is_ok());
+    }
 }
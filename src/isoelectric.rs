@@ -0,0 +1,116 @@
+// Copyright 2021-2024 SecureDNA Stiftung (SecureDNA Foundation) <licensing@securedna.org>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Net charge and isoelectric point estimation for proteins.
+//!
+//! Charges are computed with the Henderson-Hasselbalch equation from the EMBOSS `iep`
+//! pKa values, counting each ionizable side chain plus the N- and C-termini.
+
+/// pKa of the free amino group at the N-terminus.
+pub const PKA_N_TERMINUS: f64 = 8.6;
+
+/// pKa of the free carboxyl group at the C-terminus.
+pub const PKA_C_TERMINUS: f64 = 3.6;
+
+/// pKa of the side chains that are positively charged below it, by one-letter code.
+pub const PKA_POSITIVE: [(u8, f64); 3] = [(b'H', 6.5), (b'K', 10.8), (b'R', 12.5)];
+
+/// pKa of the side chains that are negatively charged above it, by one-letter code.
+pub const PKA_NEGATIVE: [(u8, f64); 4] = [(b'C', 8.5), (b'D', 3.9), (b'E', 4.1), (b'Y', 10.1)];
+
+/// Net charge of a protein at the given pH, given its (uppercase) one-letter codes.
+///
+/// Residues without an ionizable side chain, including non-standard bytes like `X` and `*`,
+/// contribute nothing beyond the termini.
+pub fn net_charge(protein: &[u8], ph: f64) -> f64 {
+    ChargedCounts::new(protein).net_charge(ph)
+}
+
+/// Estimate the pH at which a protein, given its (uppercase) one-letter codes, has no net
+/// charge, by bisection between pH 0 and 14.
+///
+/// Like [`net_charge`], this skips residues without an ionizable side chain.
+pub fn isoelectric_point(protein: &[u8]) -> f64 {
+    let counts = ChargedCounts::new(protein);
+    let (mut low, mut high) = (0.0, 14.0);
+    // Net charge decreases with pH, so keep the root between `low` and `high`.
+    while high - low > 1e-4 {
+        let mid = (low + high) / 2.0;
+        if counts.net_charge(mid) > 0.0 {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    (low + high) / 2.0
+}
+
+/// How many of each ionizable side chain a protein has, in the order of [`PKA_POSITIVE`]
+/// and [`PKA_NEGATIVE`].
+struct ChargedCounts {
+    positive: [usize; PKA_POSITIVE.len()],
+    negative: [usize; PKA_NEGATIVE.len()],
+}
+
+impl ChargedCounts {
+    fn new(protein: &[u8]) -> Self {
+        let mut counts = Self {
+            positive: [0; PKA_POSITIVE.len()],
+            negative: [0; PKA_NEGATIVE.len()],
+        };
+        for &aa in protein {
+            if let Some(i) = PKA_POSITIVE.iter().position(|&(code, _)| code == aa) {
+                counts.positive[i] += 1;
+            } else if let Some(i) = PKA_NEGATIVE.iter().position(|&(code, _)| code == aa) {
+                counts.negative[i] += 1;
+            }
+        }
+        counts
+    }
+
+    fn net_charge(&self, ph: f64) -> f64 {
+        let positive = |count: usize, pka: f64| count as f64 / (1.0 + 10f64.powf(ph - pka));
+        let negative = |count: usize, pka: f64| count as f64 / (1.0 + 10f64.powf(pka - ph));
+        let side_chains: f64 = self
+            .positive
+            .iter()
+            .zip(PKA_POSITIVE)
+            .map(|(&count, (_, pka))| positive(count, pka))
+            .chain(
+                self.negative
+                    .iter()
+                    .zip(PKA_NEGATIVE)
+                    .map(|(&count, (_, pka))| -negative(count, pka)),
+            )
+            .sum();
+        side_chains + positive(1, PKA_N_TERMINUS) - negative(1, PKA_C_TERMINUS)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn charge_decreases_with_ph() {
+        let protein = b"MKHRDECYGG";
+        let charges: Vec<f64> = (0..=14).map(|ph| net_charge(protein, ph as f64)).collect();
+        assert!(charges.windows(2).all(|w| w[0] > w[1]));
+        assert!(charges[0] > 0.0 && charges[14] < 0.0);
+    }
+
+    #[test]
+    fn isoelectric_points() {
+        // With only the termini, the pI is halfway between their pKas.
+        let termini = (PKA_N_TERMINUS + PKA_C_TERMINUS) / 2.0;
+        assert!((isoelectric_point(b"GG") - termini).abs() < 1e-3);
+        assert!((isoelectric_point(b"") - termini).abs() < 1e-3);
+
+        assert!(isoelectric_point(b"KKKKRK") > 10.0);
+        assert!(isoelectric_point(b"DDEDDE") < 4.0);
+        let protein = b"MKHRDECYGG";
+        assert!(net_charge(protein, isoelectric_point(protein)).abs() < 1e-3);
+
+        assert_eq!(isoelectric_point(b"GKX*"), isoelectric_point(b"GK"));
+    }
+}
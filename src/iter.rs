@@ -3,7 +3,10 @@
 
 use smallvec::SmallVec;
 
-use crate::{Nucleotide, NucleotideAmbiguous, NucleotideLike};
+use crate::{
+    Codon, CodonAmbiguous, Nucleotide, NucleotideAmbiguous, NucleotideGapped, NucleotideLike,
+    TranslationTable,
+};
 
 /// Helper trait to support iters regardless of whether their items are by-ref or by-value
 pub trait ToNucleotideLike
@@ -44,6 +47,14 @@ impl ToNucleotideLike for &NucleotideAmbiguous {
     }
 }
 
+impl ToNucleotideLike for &NucleotideGapped {
+    type NucleotideType = NucleotideGapped;
+
+    fn to_nucleotide_like(self) -> NucleotideGapped {
+        *self
+    }
+}
+
 /// Extension trait for nucleotide iterators
 pub trait NucleotideIter: Iterator + Sized {
     /// Returns up to 6 non-empty codon iterators for forward and reverse complement reading frames.
@@ -113,6 +124,72 @@ pub trait NucleotideIter: Iterator + Sized {
     where
         Self: Clone + DoubleEndedIterator + ExactSizeIterator;
 
+    /// Returns the number of ambiguous nucleotides, as determined by
+    /// [`NucleotideLike::is_ambiguous`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quickdna::{NucleotideAmbiguous, NucleotideIter};
+    ///
+    /// use NucleotideAmbiguous::*;
+    /// let dna = [C, N, A, R, T];
+    ///
+    /// assert_eq!(dna.iter().ambiguity_count(), 2);
+    /// ```
+    fn ambiguity_count(self) -> usize;
+
+    /// Returns iterator of the possible unambiguous codons of each codon in the first reading
+    /// frame of this nucleotide sequence, in the order of [`CodonAmbiguous::possibilities`].
+    /// As with [`codons`](Self::codons), excess nucleotides at the end are silently discarded.
+    ///
+    /// Unlike [`Expansions`](crate::expansions::Expansions), this expands each codon on its
+    /// own, rather than the whole sequence at once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quickdna::{Codon, NucleotideAmbiguous, NucleotideIter};
+    ///
+    /// use NucleotideAmbiguous::*;
+    /// let dna = [A, T, G, T, A, R, C];
+    ///
+    /// let expansions: Vec<Vec<String>> = dna
+    ///     .iter()
+    ///     .codon_expansions()
+    ///     .map(|codons| codons.iter().map(Codon::to_string).collect())
+    ///     .collect();
+    /// assert_eq!(expansions, [vec!["ATG"], vec!["TAA", "TAG"]]);
+    /// ```
+    fn codon_expansions(self) -> CodonExpansions<Self>;
+
+    /// Returns iterator of overlapping codons starting at every position of this nucleotide
+    /// sequence, rather than just at multiples of 3.
+    ///
+    /// A sequence of length `n` has `n - 2` codon windows (or none, if `n < 3`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quickdna::{Nucleotide, NucleotideIter};
+    ///
+    /// use Nucleotide::*;
+    /// let dna = [C, G, A, T, C];
+    ///
+    /// let windows = dna.iter().codon_windows();
+    /// assert_eq!(windows.len(), 3);
+    /// assert!(windows.eq([
+    ///     [C, G, A].into(),
+    ///     [G, A, T].into(),
+    ///     [A, T, C].into(),
+    /// ]));
+    ///
+    /// assert_eq!(dna[..2].iter().codon_windows().len(), 0);
+    /// ```
+    fn codon_windows(self) -> CodonWindows<Self>
+    where
+        Self: Clone;
+
     /// Returns iterator of codons for the first reading frame of this nucleotide sequence.
     /// If the number of nucleotides isn't divisible by 3, excess nucleotides are silently
     /// discarded. Note that if the returned iterator is non-empty, it is the same as the
@@ -150,6 +227,99 @@ pub trait NucleotideIter: Iterator + Sized {
     /// ```
     fn complement(self) -> Complement<Self>;
 
+    /// Returns up to 6 non-empty codon iterators for reading frames on both strands, like
+    /// [`all_reading_frames`](Self::all_reading_frames), each paired with its strand and its
+    /// offset on that strand. Offsets on the reverse complement strand are counted from the
+    /// end of the original sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quickdna::{Nucleotide, NucleotideIter, Strand};
+    ///
+    /// use Nucleotide::*;
+    /// let dna = [C, G, A, T];
+    ///
+    /// let frames = dna.iter().enumerate_all_reading_frames();
+    /// let positions: Vec<_> = frames.iter().map(|(position, _)| *position).collect();
+    /// assert_eq!(
+    ///     positions,
+    ///     [
+    ///         (Strand::Forward, 0),
+    ///         (Strand::Forward, 1),
+    ///         (Strand::ReverseComplement, 0),
+    ///         (Strand::ReverseComplement, 1),
+    ///     ]
+    /// );
+    /// assert!(frames[3].1.clone().eq([[T, C, G].into()]));
+    /// ```
+    fn enumerate_all_reading_frames(self) -> EnumeratedReadingFrames<Self>
+    where
+        Self: Clone + DoubleEndedIterator + ExactSizeIterator;
+
+    /// Returns up to 3 non-empty codon iterators for reading frames, like
+    /// [`self_reading_frames`](Self::self_reading_frames), each paired with its offset from
+    /// the beginning of the nucleotide sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quickdna::{Nucleotide, NucleotideIter};
+    ///
+    /// use Nucleotide::*;
+    /// let dna = [C, G, A, T];
+    ///
+    /// let frames = dna.iter().enumerate_reading_frames();
+    /// assert_eq!(frames.len(), 2);
+    /// assert_eq!(frames[1].0, 1);
+    /// assert!(frames[1].1.clone().eq([[G, A, T].into()]));
+    /// ```
+    fn enumerate_reading_frames(self) -> SmallVec<[(usize, Codons<Self>); 3]>
+    where
+        Self: Clone + ExactSizeIterator;
+
+    /// Returns iterator of the GC fraction of each overlapping window of the given size.
+    ///
+    /// The count of G/C nucleotides is updated incrementally as the window slides, so this
+    /// takes *O*(*N*) time regardless of window size. Ambiguous nucleotides count fractionally
+    /// by the share of their possibilities that are G or C; for example, `S` counts as 1,
+    /// `N` as 0.5, and `B` as 2/3. Gaps count as 0, since windows are over alignment columns.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `window` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quickdna::{Nucleotide, NucleotideIter};
+    ///
+    /// use Nucleotide::*;
+    /// let dna = [G, C, A, T, G];
+    ///
+    /// let gc = dna.iter().gc_windows(2);
+    /// assert_eq!(gc.len(), 4);
+    /// assert!(gc.eq([1.0, 0.5, 0.0, 0.5]));
+    /// ```
+    fn gc_windows(self, window: usize) -> GcWindows<Self>
+    where
+        Self: Clone;
+
+    /// Returns the length of the longest run of consecutive ambiguous nucleotides, as
+    /// determined by [`NucleotideLike::is_ambiguous`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quickdna::{NucleotideAmbiguous, NucleotideIter};
+    ///
+    /// use NucleotideAmbiguous::*;
+    /// let dna = [N, A, R, Y, N, T, N];
+    ///
+    /// assert_eq!(dna.iter().max_run_of_ambiguities(), 3);
+    /// ```
+    fn max_run_of_ambiguities(self) -> usize;
+
     /// Returns iterator of reverse complement of contained nucleotides.
     ///
     /// # Examples
@@ -212,6 +382,27 @@ pub trait NucleotideIter: Iterator + Sized {
     where
         Self: Clone + ExactSizeIterator;
 
+    /// Returns iterator of amino acids translated lazily from the codons of this nucleotide
+    /// sequence, using the given translation table. As with [`codons`](Self::codons), excess
+    /// nucleotides at the end that don't form a whole codon are silently discarded.
+    ///
+    /// This doesn't buffer the sequence, so it can translate sequences too large to hold
+    /// in memory at once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quickdna::{Nucleotide, NucleotideIter, TranslationTable};
+    ///
+    /// use Nucleotide::*;
+    /// let dna = [A, T, C, G, A, T, C, G];
+    ///
+    /// let aas = dna.iter().translate(TranslationTable::Ncbi1);
+    /// assert_eq!(aas.len(), 2);
+    /// assert!(aas.eq([b'I', b'D']));
+    /// ```
+    fn translate(self, table: TranslationTable) -> Translate<Self>;
+
     /// Trims excess nucleotides off iterator end so it aligns with a codon boundary.
     ///
     /// This makes the iterator's length a multiple of 3 by removing up to 2 elements from its end.
@@ -289,6 +480,31 @@ where
         frames
     }
 
+    fn ambiguity_count(self) -> usize {
+        self.map(|n| n.to_nucleotide_like().is_ambiguous() as usize)
+            .sum()
+    }
+
+    fn codon_expansions(self) -> CodonExpansions<Self> {
+        CodonExpansions(self.codons())
+    }
+
+    fn codon_windows(self) -> CodonWindows<Self>
+    where
+        Self: Clone,
+    {
+        let first = self;
+        let mut second = first.clone();
+        second.next();
+        let mut third = second.clone();
+        third.next();
+        CodonWindows {
+            first,
+            second,
+            third,
+        }
+    }
+
     fn codons(self) -> Codons<Self> {
         Codons(self)
     }
@@ -297,6 +513,58 @@ where
         Complement(self)
     }
 
+    fn enumerate_all_reading_frames(self) -> EnumeratedReadingFrames<Self>
+    where
+        Self: Clone + DoubleEndedIterator + ExactSizeIterator,
+    {
+        // Each strand's frames are in order of offset, with only trailing frames omitted.
+        let mut offsets = [0, 0];
+        self.all_reading_frames()
+            .into_iter()
+            .map(|frame| {
+                let strand = frame.strand();
+                let offset = &mut offsets[strand as usize];
+                *offset += 1;
+                ((strand, *offset - 1), frame)
+            })
+            .collect()
+    }
+
+    fn enumerate_reading_frames(self) -> SmallVec<[(usize, Codons<Self>); 3]>
+    where
+        Self: Clone + ExactSizeIterator,
+    {
+        self.self_reading_frames().into_iter().enumerate().collect()
+    }
+
+    fn gc_windows(self, window: usize) -> GcWindows<Self>
+    where
+        Self: Clone,
+    {
+        assert!(window > 0, "window size must be non-zero");
+        GcWindows {
+            trailing: self.clone(),
+            leading: self,
+            window,
+            gc: 0,
+            primed: false,
+        }
+    }
+
+    fn max_run_of_ambiguities(self) -> usize {
+        let mut run = 0;
+        let mut max_run = 0;
+        for n in self {
+            if n.to_nucleotide_like().is_ambiguous() {
+                run += 1;
+                max_run = max_run.max(run);
+            } else {
+                run = 0;
+            }
+        }
+        max_run
+    }
+
     fn reverse_complement(self) -> Complement<std::iter::Rev<Self>>
     where
         Self: DoubleEndedIterator,
@@ -318,6 +586,13 @@ where
         frames
     }
 
+    fn translate(self, table: TranslationTable) -> Translate<Self> {
+        Translate {
+            codons: self.codons(),
+            table,
+        }
+    }
+
     fn trim_to_codon(&mut self)
     where
         Self: DoubleEndedIterator + ExactSizeIterator,
@@ -328,6 +603,68 @@ where
     }
 }
 
+/// Returns the number of non-empty reading frames on one strand of a sequence with `len`
+/// nucleotides, as yielded by [`self_reading_frames`](NucleotideIter::self_reading_frames).
+///
+/// A frame needs a whole codon, so this is 3 for `len >= 5`, 2 for `len == 4`,
+/// 1 for `len == 3`, and 0 otherwise.
+///
+/// ```
+/// use quickdna::reading_frame_count;
+///
+/// assert_eq!(reading_frame_count(100), 3);
+/// assert_eq!(reading_frame_count(4), 2);
+/// assert_eq!(reading_frame_count(2), 0);
+/// ```
+pub const fn reading_frame_count(len: usize) -> usize {
+    match len {
+        0..=2 => 0,
+        3 => 1,
+        4 => 2,
+        _ => 3,
+    }
+}
+
+/// Returns the number of non-empty reading frames on both strands of a sequence with `len`
+/// nucleotides, as yielded by [`all_reading_frames`](NucleotideIter::all_reading_frames).
+pub const fn all_reading_frame_count(len: usize) -> usize {
+    2 * reading_frame_count(len)
+}
+
+/// Returns the start, on the forward strand, of a window of `window_len` nucleotides found at
+/// `rc_index` on the reverse complement of a sequence with `forward_len` nucleotides.
+///
+/// Both windows cover the same base pairs, so the forward window is
+/// `rc_position(..)..rc_position(..) + window_len`. This also maps a protein window back to
+/// the DNA it was translated from: a window of `n` amino acids starting at codon `i` of the
+/// reverse complement frame with offset `offset` covers `3 * n` nucleotides starting at
+/// `3 * i + offset` on the reverse complement.
+///
+/// # Panics
+///
+/// Panics if the window doesn't fit in the sequence, i.e. if `rc_index + window_len` is
+/// greater than `forward_len`.
+///
+/// ```
+/// use quickdna::{rc_position, BaseSequence, DnaSequenceStrict};
+///
+/// let dna: DnaSequenceStrict = "GATTACA".parse().unwrap();
+/// let rc: DnaSequenceStrict = "TGTAATC".parse().unwrap();
+/// assert_eq!(dna.reverse_complement(), rc);
+///
+/// let (rc_index, window) = rc.indexed_windows(3).nth(1).unwrap(); // GTA
+/// let start = rc_position(dna.len(), rc_index, 3);
+/// assert_eq!(start, 3);
+/// assert_eq!(dna.windows(3).nth(start).unwrap().reverse_complement(), window); // TAC
+/// ```
+pub const fn rc_position(forward_len: usize, rc_index: usize, window_len: usize) -> usize {
+    assert!(
+        rc_index <= forward_len && window_len <= forward_len - rc_index,
+        "window extends past the end of the sequence"
+    );
+    forward_len - rc_index - window_len
+}
+
 /// Adapter yielding codons of the contained iterator.
 ///
 /// This `struct` is created by the [`codons`](NucleotideIter::codons)
@@ -343,12 +680,10 @@ where
     type Item = <N::NucleotideType as NucleotideLike>::Codon;
 
     fn next(&mut self) -> Option<Self::Item> {
-        match (self.0.next(), self.0.next(), self.0.next()) {
-            (Some(n1), Some(n2), Some(n3)) => {
-                Some([n1, n2, n3].map(|n| n.to_nucleotide_like()).into())
-            }
-            _ => None,
-        }
+        let n1 = self.0.next()?;
+        let n2 = self.0.next()?;
+        let n3 = self.0.next()?;
+        Some([n1, n2, n3].map(|n| n.to_nucleotide_like()).into())
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
@@ -364,12 +699,10 @@ where
 {
     fn next_back(&mut self) -> Option<Self::Item> {
         self.0.trim_to_codon();
-        match (self.0.next_back(), self.0.next_back(), self.0.next_back()) {
-            (Some(n3), Some(n2), Some(n1)) => {
-                Some([n1, n2, n3].map(|n| n.to_nucleotide_like()).into())
-            }
-            _ => None,
-        }
+        let n3 = self.0.next_back()?;
+        let n2 = self.0.next_back()?;
+        let n1 = self.0.next_back()?;
+        Some([n1, n2, n3].map(|n| n.to_nucleotide_like()).into())
     }
 }
 
@@ -383,6 +716,47 @@ where
     }
 }
 
+/// Adapter yielding overlapping codons of the contained iterator.
+///
+/// This `struct` is created by the [`codon_windows`](NucleotideIter::codon_windows)
+/// method on [`NucleotideIter`]. See its documentation for more.
+#[derive(Clone, Debug)]
+pub struct CodonWindows<I> {
+    first: I,
+    second: I,
+    third: I,
+}
+
+impl<N, I> Iterator for CodonWindows<I>
+where
+    N: ToNucleotideLike,
+    I: Iterator<Item = N>,
+{
+    type Item = <N::NucleotideType as NucleotideLike>::Codon;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let n3 = self.third.next()?;
+        match (self.first.next(), self.second.next()) {
+            (Some(n1), Some(n2)) => Some([n1, n2, n3].map(|n| n.to_nucleotide_like()).into()),
+            _ => None,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.third.size_hint()
+    }
+}
+
+impl<I> ExactSizeIterator for CodonWindows<I>
+where
+    Self: Iterator,
+    I: ExactSizeIterator,
+{
+    fn len(&self) -> usize {
+        self.third.len()
+    }
+}
+
 /// Adapter yielding complementary nucleotide of the contained iterator.
 ///
 /// This `struct` is created by the [`complement`](NucleotideIter::complement)
@@ -428,6 +802,184 @@ where
     }
 }
 
+/// Adapter yielding the GC fraction of each window of the contained iterator.
+///
+/// This `struct` is created by the [`gc_windows`](NucleotideIter::gc_windows)
+/// method on [`NucleotideIter`]. See its documentation for more.
+#[derive(Clone, Debug)]
+pub struct GcWindows<I> {
+    leading: I,
+    trailing: I,
+    window: usize,
+    /// G/C count of the current window, in twelfths (see [`gc_twelfths`])
+    gc: usize,
+    primed: bool,
+}
+
+/// G/C content of a nucleotide in twelfths, so that ambiguity codes (which cover 1 to 4
+/// nucleotides) can be summed exactly as integers. Gaps count as 0.
+pub(crate) fn gc_twelfths(n: impl NucleotideLike) -> usize {
+    const GC_BITS: u8 = Nucleotide::G as u8 | Nucleotide::C as u8;
+    if n.is_gap() {
+        return 0;
+    }
+    let bits = n.bits();
+    12 * (bits & GC_BITS).count_ones() as usize / bits.count_ones() as usize
+}
+
+impl<N, I> Iterator for GcWindows<I>
+where
+    N: ToNucleotideLike,
+    I: Iterator<Item = N>,
+{
+    type Item = f64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.primed {
+            let added = self.leading.next()?.to_nucleotide_like();
+            let removed = self.trailing.next()?.to_nucleotide_like();
+            self.gc = self.gc + gc_twelfths(added) - gc_twelfths(removed);
+        } else {
+            for _ in 0..self.window {
+                self.gc += gc_twelfths(self.leading.next()?.to_nucleotide_like());
+            }
+            self.primed = true;
+        }
+        Some(self.gc as f64 / (12 * self.window) as f64)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (min, max) = self.leading.size_hint();
+        if self.primed {
+            (min, max)
+        } else {
+            let unprimed = |n: usize| (n + 1).saturating_sub(self.window);
+            (unprimed(min), max.map(unprimed))
+        }
+    }
+}
+
+impl<I> ExactSizeIterator for GcWindows<I>
+where
+    Self: Iterator,
+    I: ExactSizeIterator,
+{
+}
+
+/// Adapter yielding amino acids translated from codons of the contained iterator.
+///
+/// This `struct` is created by the [`translate`](NucleotideIter::translate)
+/// method on [`NucleotideIter`]. See its documentation for more.
+#[derive(Clone, Debug)]
+pub struct Translate<I> {
+    codons: Codons<I>,
+    table: TranslationTable,
+}
+
+impl<N, I> Iterator for Translate<I>
+where
+    N: ToNucleotideLike,
+    I: Iterator<Item = N>,
+{
+    type Item = u8;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let codon = self.codons.next()?;
+        Some(self.table.to_fn::<N::NucleotideType, _>()(codon))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.codons.size_hint()
+    }
+}
+
+impl<N, I> DoubleEndedIterator for Translate<I>
+where
+    N: ToNucleotideLike,
+    I: DoubleEndedIterator<Item = N> + ExactSizeIterator,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let codon = self.codons.next_back()?;
+        Some(self.table.to_fn::<N::NucleotideType, _>()(codon))
+    }
+}
+
+impl<I> ExactSizeIterator for Translate<I>
+where
+    Self: Iterator,
+    Codons<I>: ExactSizeIterator,
+{
+    fn len(&self) -> usize {
+        self.codons.len()
+    }
+}
+
+/// Adapter yielding the possible unambiguous codons of each codon of the contained iterator.
+///
+/// This `struct` is created by the [`codon_expansions`](NucleotideIter::codon_expansions)
+/// method on [`NucleotideIter`]. See its documentation for more.
+#[derive(Clone, Debug)]
+pub struct CodonExpansions<I>(Codons<I>);
+
+fn expand_codon<N: NucleotideLike + Into<NucleotideAmbiguous>>(
+    codon: impl Into<[N; 3]>,
+) -> SmallVec<[Codon; 4]> {
+    CodonAmbiguous(codon.into().map(Into::into))
+        .possibilities()
+        .collect()
+}
+
+impl<N, I> Iterator for CodonExpansions<I>
+where
+    N: ToNucleotideLike,
+    N::NucleotideType: Into<NucleotideAmbiguous>,
+    I: Iterator<Item = N>,
+{
+    type Item = SmallVec<[Codon; 4]>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(expand_codon::<N::NucleotideType>)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<N, I> DoubleEndedIterator for CodonExpansions<I>
+where
+    N: ToNucleotideLike,
+    N::NucleotideType: Into<NucleotideAmbiguous>,
+    I: DoubleEndedIterator<Item = N> + ExactSizeIterator,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(expand_codon::<N::NucleotideType>)
+    }
+}
+
+impl<I> ExactSizeIterator for CodonExpansions<I>
+where
+    Self: Iterator,
+    Codons<I>: ExactSizeIterator,
+{
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+/// Reading frames paired with their strand and offset, as returned by
+/// [`enumerate_all_reading_frames`](NucleotideIter::enumerate_all_reading_frames).
+pub type EnumeratedReadingFrames<I> = SmallVec<[((Strand, usize), ForwardOrRcCodons<I>); 6]>;
+
+/// One of the two strands of double-stranded DNA.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Strand {
+    /// The strand of the original sequence.
+    Forward,
+    /// The reverse complement of the original sequence.
+    ReverseComplement,
+}
+
 /// Adapter capable of holding either forward codon iterators or reverse complement codon iterators.
 ///
 /// This `struct` is created by the [`all_reading_frames`](NucleotideIter::all_reading_frames)
@@ -438,6 +990,16 @@ pub enum ForwardOrRcCodons<I> {
     Rc(Codons<Complement<std::iter::Rev<I>>>),
 }
 
+impl<I> ForwardOrRcCodons<I> {
+    /// The strand these codons are read from.
+    pub fn strand(&self) -> Strand {
+        match self {
+            Self::Forward(_) => Strand::Forward,
+            Self::Rc(_) => Strand::ReverseComplement,
+        }
+    }
+}
+
 impl<N, I> Iterator for ForwardOrRcCodons<I>
 where
     N: ToNucleotideLike,
@@ -491,6 +1053,104 @@ where
 mod test {
     use super::*;
 
+    #[test]
+    fn test_ambiguities() {
+        use NucleotideAmbiguous::*;
+        let dna = [N, N, A, R, Y, S, T, N];
+        assert_eq!(dna.iter().ambiguity_count(), 6);
+        assert_eq!(dna.iter().max_run_of_ambiguities(), 3);
+        assert_eq!(dna[2..3].iter().max_run_of_ambiguities(), 0);
+        assert_eq!(dna[..0].iter().max_run_of_ambiguities(), 0);
+
+        let strict = [Nucleotide::A, Nucleotide::C];
+        assert_eq!(strict.iter().ambiguity_count(), 0);
+        assert_eq!(strict.iter().max_run_of_ambiguities(), 0);
+    }
+
+    #[test]
+    fn test_reading_frame_count() {
+        let dna = [Nucleotide::A; 10];
+        for len in 0..dna.len() {
+            let dna = &dna[..len];
+            assert_eq!(
+                dna.iter().self_reading_frames().len(),
+                reading_frame_count(len)
+            );
+            assert_eq!(
+                dna.iter().all_reading_frames().len(),
+                all_reading_frame_count(len)
+            );
+        }
+    }
+
+    #[test]
+    fn test_rc_position() {
+        use Nucleotide::*;
+        let dna = [A, T, G, T, G, G, T, A, A, C];
+        let rc: Vec<_> = dna.iter().reverse_complement().collect();
+        for window_len in 1..=dna.len() {
+            for (rc_index, window) in rc.windows(window_len).enumerate() {
+                let start = rc_position(dna.len(), rc_index, window_len);
+                let forward = dna[start..start + window_len].iter().reverse_complement();
+                assert!(forward.eq(window.iter().copied()));
+            }
+        }
+        assert_eq!(rc_position(10, 0, 10), 0);
+        assert_eq!(rc_position(10, 9, 1), 0);
+        assert_eq!(rc_position(10, 0, 1), 9);
+
+        // The last protein window of the first reverse complement frame of 20 nucleotides:
+        // 2 amino acids from codon 4, or nucleotides 12..18 of the reverse complement.
+        assert_eq!(rc_position(20, 3 * 4, 3 * 2), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "window extends past the end of the sequence")]
+    fn test_rc_position_out_of_bounds() {
+        rc_position(10, 8, 3);
+    }
+
+    #[test]
+    fn test_enumerate_reading_frames() {
+        use Nucleotide::*;
+        let dna = [A, T, G, T, G, G, T, A, A, C];
+        for len in 0..dna.len() {
+            let dna = &dna[..len];
+            let frames = dna.iter().enumerate_reading_frames();
+            assert_eq!(frames.len(), len.saturating_sub(2).min(3));
+            for (offset, codons) in frames {
+                assert!(codons.eq(dna[offset..].iter().codons()));
+            }
+
+            let rc: Vec<Nucleotide> = dna.iter().reverse_complement().collect();
+            let frames = dna.iter().enumerate_all_reading_frames();
+            assert_eq!(frames.len(), 2 * len.saturating_sub(2).min(3));
+            for ((strand, offset), codons) in frames {
+                let expected = match strand {
+                    Strand::Forward => &dna[offset..],
+                    Strand::ReverseComplement => &rc[offset..],
+                };
+                assert!(codons.eq(expected.iter().codons()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_translate_matches_translate_dna() {
+        use Nucleotide::*;
+        let dna = [A, T, G, T, G, G, T, A, A, C];
+        let tables = [TranslationTable::Ncbi1, TranslationTable::Ncbi11];
+        for table in tables.into_iter().filter(|table| table.is_available()) {
+            for len in 0..=dna.len() {
+                let expected = table.translate_dna(&dna[..len]);
+                let aas: Vec<u8> = dna[..len].iter().translate(table).collect();
+                assert_eq!(aas, expected);
+                let rev: Vec<u8> = dna[..len].iter().translate(table).rev().collect();
+                assert!(rev.into_iter().eq(expected.into_iter().rev()));
+            }
+        }
+    }
+
     #[test]
     fn test_reverse_codons() {
         use Nucleotide::*;
@@ -499,4 +1159,62 @@ mod test {
         let expected = [[T, C, C].into(), [A, A, T].into()];
         assert_eq!(rev_codons, expected);
     }
+
+    #[test]
+    fn test_gc_windows() {
+        use NucleotideAmbiguous::*;
+        let dna = [G, S, N, W, B, A];
+        let gc: Vec<_> = dna.iter().gc_windows(3).collect();
+        let expected = [
+            2.5 / 3.0,
+            1.5 / 3.0,
+            (0.5 + 2.0 / 3.0) / 3.0,
+            (2.0 / 3.0) / 3.0,
+        ];
+        assert_eq!(gc.len(), expected.len());
+        for (actual, expected) in gc.iter().zip(expected) {
+            assert!((actual - expected).abs() < 1e-12, "{actual} != {expected}");
+        }
+
+        assert_eq!(dna.iter().gc_windows(6).len(), 1);
+        assert_eq!(dna.iter().gc_windows(7).len(), 0);
+        assert_eq!(dna.iter().gc_windows(7).next(), None);
+    }
+
+    #[test]
+    fn test_codon_expansions() {
+        use NucleotideAmbiguous::*;
+        let dna = [N, G, G, A, T, G, C];
+        let expansions = dna.iter().codon_expansions();
+        assert_eq!(expansions.len(), 2);
+        let expansions: Vec<_> = expansions.collect();
+        assert_eq!(expansions[0].len(), 4);
+        assert!(expansions[0]
+            .iter()
+            .all(|codon| codon.0[1..] == [Nucleotide::G; 2]));
+        assert_eq!(expansions[1].as_slice(), ["ATG".parse::<Codon>().unwrap()]);
+
+        let last = dna.iter().codon_expansions().next_back().unwrap();
+        assert_eq!(last, expansions[1]);
+
+        let strict = [Nucleotide::T, Nucleotide::G, Nucleotide::A];
+        let expected: SmallVec<[Codon; 4]> = smallvec::smallvec![Codon(strict)];
+        assert!(strict.iter().codon_expansions().eq([expected]));
+    }
+
+    #[test]
+    fn test_codon_windows() {
+        use NucleotideAmbiguous::*;
+        let dna = [A, N, T, C];
+        let windows: Vec<_> = dna.iter().codon_windows().collect();
+        let expected = [[A, N, T].into(), [N, T, C].into()];
+        assert_eq!(windows, expected);
+
+        for len in 0..6 {
+            let dna = vec![Nucleotide::A; len];
+            let windows = dna.iter().codon_windows();
+            assert_eq!(windows.len(), len.saturating_sub(2));
+            assert_eq!(windows.count(), len.saturating_sub(2));
+        }
+    }
 }
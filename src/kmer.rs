@@ -0,0 +1,562 @@
+// Copyright 2021-2024 SecureDNA Stiftung (SecureDNA Foundation) <licensing@securedna.org>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Iterators over 2-bit packed k-mers of unambiguous DNA.
+//!
+//! Each [`Nucleotide`] is packed into 2 bits according to its position in [`Nucleotide::ALL`]
+//! (`A` = 0, `T` = 1, `C` = 2, `G` = 3), with the first nucleotide of a k-mer in the most
+//! significant bits. Comparing packed k-mers as integers is therefore the same as comparing
+//! them lexically under [`Nucleotide`]'s ordering, and complementing a nucleotide is just
+//! flipping its low bit.
+
+use std::collections::{HashSet, VecDeque};
+
+use crate::{DnaSequenceStrict, Nucleotide};
+
+/// Largest k-mer size that fits in a [`u64`].
+pub const MAX_K: usize = 32;
+
+/// Pack a nucleotide into 2 bits.
+#[inline]
+pub fn encode(nucleotide: Nucleotide) -> u64 {
+    match nucleotide {
+        Nucleotide::A => 0,
+        Nucleotide::T => 1,
+        Nucleotide::C => 2,
+        Nucleotide::G => 3,
+    }
+}
+
+/// Unpack a nucleotide from the low 2 bits of `bits`.
+#[inline]
+pub fn decode(bits: u64) -> Nucleotide {
+    Nucleotide::ALL[(bits & 0b11) as usize]
+}
+
+/// Reverse complement a packed k-mer, given in the low `2 * k` bits of `kmer`.
+///
+/// # Panics
+///
+/// Panics if `k` is 0 or greater than [`MAX_K`].
+pub fn reverse_complement(kmer: u64, k: usize) -> u64 {
+    assert!(
+        (1..=MAX_K).contains(&k),
+        "k must be between 1 and {MAX_K}, got {k}"
+    );
+    (0..k).fold(0, |rc, i| (rc << 2) | ((kmer >> (2 * i)) & 0b11) ^ 1)
+}
+
+/// Rolling packed k-mer over a stream of nucleotides, for when they don't come from an
+/// iterator.
+///
+/// Each [`push`](Self::push) updates the k-mer and its reverse complement in constant time,
+/// so nothing but the two packed k-mers is kept in memory.
+#[derive(Clone, Debug)]
+pub struct RollingCanonicalKmer {
+    k: usize,
+    mask: u64,
+    forward: u64,
+    reverse_complement: u64,
+    filled: usize,
+}
+
+impl RollingCanonicalKmer {
+    /// Create an empty rolling k-mer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is 0 or greater than [`MAX_K`].
+    pub fn new(k: usize) -> Self {
+        assert!(
+            (1..=MAX_K).contains(&k),
+            "k must be between 1 and {MAX_K}, got {k}"
+        );
+        Self {
+            k,
+            mask: u64::MAX >> (64 - 2 * k),
+            forward: 0,
+            reverse_complement: 0,
+            filled: 0,
+        }
+    }
+
+    /// Append a nucleotide, returning the canonical packed k-mer of the last `k` nucleotides
+    /// once at least `k` have been pushed.
+    #[inline]
+    pub fn push(&mut self, nucleotide: Nucleotide) -> Option<u64> {
+        self.push_pair(nucleotide).map(|(fw, rc)| fw.min(rc))
+    }
+
+    /// Forget every pushed nucleotide, e.g. to restart after an ambiguous one.
+    pub fn reset(&mut self) {
+        self.forward = 0;
+        self.reverse_complement = 0;
+        self.filled = 0;
+    }
+
+    /// Append a nucleotide, returning the k-mer along with its reverse complement once at
+    /// least `k` nucleotides have been pushed.
+    #[inline]
+    fn push_pair(&mut self, nucleotide: Nucleotide) -> Option<(u64, u64)> {
+        let bits = encode(nucleotide);
+        self.forward = ((self.forward << 2) | bits) & self.mask;
+        self.reverse_complement =
+            (self.reverse_complement >> 2) | ((bits ^ 1) << (2 * (self.k - 1)));
+        if self.filled + 1 >= self.k {
+            return Some((self.forward, self.reverse_complement));
+        }
+        self.filled += 1;
+        None
+    }
+}
+
+/// Iterator of packed k-mers starting at every position of a nucleotide sequence.
+///
+/// The reverse complement of each k-mer is maintained alongside it, so
+/// [`CanonicalKmers`] costs no more than this.
+#[derive(Clone, Debug)]
+pub struct Kmers<I> {
+    inner: I,
+    rolling: RollingCanonicalKmer,
+}
+
+impl<I> Kmers<I>
+where
+    I: Iterator<Item = Nucleotide>,
+{
+    /// Create iter of packed k-mers of `iterable`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is 0 or greater than [`MAX_K`].
+    pub fn new(iterable: impl IntoIterator<IntoIter = I>, k: usize) -> Self {
+        Self {
+            inner: iterable.into_iter(),
+            rolling: RollingCanonicalKmer::new(k),
+        }
+    }
+
+    /// Advance to the next k-mer, returning it along with its reverse complement.
+    fn next_pair(&mut self) -> Option<(u64, u64)> {
+        loop {
+            if let Some(pair) = self.rolling.push_pair(self.inner.next()?) {
+                return Some(pair);
+            }
+        }
+    }
+
+    fn remaining(&self, n: usize) -> usize {
+        (n + self.rolling.filled + 1).saturating_sub(self.rolling.k)
+    }
+}
+
+impl<I> Iterator for Kmers<I>
+where
+    I: Iterator<Item = Nucleotide>,
+{
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_pair().map(|(forward, _)| forward)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (min, max) = self.inner.size_hint();
+        (self.remaining(min), max.map(|max| self.remaining(max)))
+    }
+}
+
+impl<I: ExactSizeIterator<Item = Nucleotide>> ExactSizeIterator for Kmers<I> {}
+
+/// Iterator of canonical packed k-mers starting at every position of a nucleotide sequence.
+///
+/// The canonical k-mer is the lesser of the k-mer and its reverse complement, so a sequence
+/// and its reverse complement contain the same canonical k-mers (in reverse order).
+#[derive(Clone, Debug)]
+pub struct CanonicalKmers<I>(Kmers<I>);
+
+impl<I> CanonicalKmers<I>
+where
+    I: Iterator<Item = Nucleotide>,
+{
+    /// Create iter of canonical packed k-mers of `iterable`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is 0 or greater than [`MAX_K`].
+    pub fn new(iterable: impl IntoIterator<IntoIter = I>, k: usize) -> Self {
+        Self(Kmers::new(iterable, k))
+    }
+}
+
+impl<I> Iterator for CanonicalKmers<I>
+where
+    I: Iterator<Item = Nucleotide>,
+{
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next_pair().map(|(fw, rc)| fw.min(rc))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<I: ExactSizeIterator<Item = Nucleotide>> ExactSizeIterator for CanonicalKmers<I> {}
+
+/// Iterator of `(position, canonical_kmer)` minimizers of a nucleotide sequence.
+///
+/// For each window of `w` consecutive k-mers, the minimizer is the least canonical k-mer
+/// in the window (the leftmost one, in case of ties). Since adjacent windows usually share a
+/// minimizer, consecutive duplicates are only yielded once.
+#[derive(Clone, Debug)]
+pub struct Minimizers<I> {
+    kmers: std::iter::Enumerate<CanonicalKmers<I>>,
+    w: usize,
+    // Candidates for the current and upcoming windows' minimizers, in increasing order
+    // of both position and k-mer.
+    candidates: VecDeque<(usize, u64)>,
+    last: Option<(usize, u64)>,
+}
+
+impl<I> Minimizers<I>
+where
+    I: Iterator<Item = Nucleotide>,
+{
+    /// Create iter of minimizers of `iterable`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `w` is 0, or if `k` is 0 or greater than [`MAX_K`].
+    pub fn new(iterable: impl IntoIterator<IntoIter = I>, k: usize, w: usize) -> Self {
+        assert!(w > 0, "window size must be non-zero");
+        Self {
+            kmers: CanonicalKmers::new(iterable, k).enumerate(),
+            w,
+            candidates: VecDeque::with_capacity(w),
+            last: None,
+        }
+    }
+}
+
+impl<I> Iterator for Minimizers<I>
+where
+    I: Iterator<Item = Nucleotide>,
+{
+    type Item = (usize, u64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (pos, kmer) = self.kmers.next()?;
+            while self.candidates.back().is_some_and(|&(_, c)| c > kmer) {
+                self.candidates.pop_back();
+            }
+            self.candidates.push_back((pos, kmer));
+            while self
+                .candidates
+                .front()
+                .is_some_and(|&(p, _)| p + self.w <= pos)
+            {
+                self.candidates.pop_front();
+            }
+
+            if pos + 1 >= self.w {
+                let minimizer = self.candidates[0];
+                if self.last != Some(minimizer) {
+                    self.last = Some(minimizer);
+                    return Some(minimizer);
+                }
+            }
+        }
+    }
+}
+
+/// Set of packed k-mers of a fixed size, for containment queries and deduplication.
+///
+/// A canonical set stores the lesser of each k-mer and its reverse complement, and looks up
+/// k-mers the same way, so it doesn't distinguish between the two strands.
+///
+/// # Examples
+///
+/// ```
+/// use quickdna::{kmer::{Kmers, KmerSet}, DnaSequenceStrict};
+///
+/// let dna: DnaSequenceStrict = "GATTACA".parse().unwrap();
+/// let set = KmerSet::from_sequence(&dna, 3, false);
+/// assert_eq!(set.len(), 5);
+///
+/// let query: DnaSequenceStrict = "TTAC".parse().unwrap();
+/// assert!(Kmers::new(query.iter(), 3).all(|kmer| set.contains(kmer)));
+///
+/// // TGT is the reverse complement of ACA.
+/// let tgt = Kmers::new("TGT".parse::<DnaSequenceStrict>().unwrap().iter(), 3).next().unwrap();
+/// assert!(!set.contains(tgt));
+/// assert!(KmerSet::from_sequence(&dna, 3, true).contains(tgt));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KmerSet {
+    k: usize,
+    canonical: bool,
+    kmers: HashSet<u64>,
+}
+
+impl KmerSet {
+    /// Create an empty set of k-mers, which stores canonical k-mers if `canonical` is true.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is 0 or greater than [`MAX_K`].
+    pub fn new(k: usize, canonical: bool) -> Self {
+        assert!(
+            (1..=MAX_K).contains(&k),
+            "k must be between 1 and {MAX_K}, got {k}"
+        );
+        Self {
+            k,
+            canonical,
+            kmers: HashSet::new(),
+        }
+    }
+
+    /// Create a set of the k-mers of `dna`, which stores canonical k-mers if `canonical` is
+    /// true.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is 0 or greater than [`MAX_K`].
+    pub fn from_sequence(dna: &DnaSequenceStrict, k: usize, canonical: bool) -> Self {
+        let mut set = Self::new(k, canonical);
+        set.insert_sequence(dna);
+        set
+    }
+
+    /// The size of the k-mers in this set.
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// Whether this set stores canonical k-mers.
+    pub fn is_canonical(&self) -> bool {
+        self.canonical
+    }
+
+    /// Add a packed k-mer to this set, returning whether it was newly inserted.
+    ///
+    /// Only the low `2 * k` bits of `kmer` are used.
+    pub fn insert(&mut self, kmer: u64) -> bool {
+        self.kmers.insert(self.key(kmer))
+    }
+
+    /// Add every k-mer of `dna` to this set.
+    pub fn insert_sequence(&mut self, dna: &DnaSequenceStrict) {
+        let kmers = Kmers::new(dna.iter(), self.k);
+        if self.canonical {
+            self.kmers.extend(CanonicalKmers(kmers));
+        } else {
+            self.kmers.extend(kmers);
+        }
+    }
+
+    /// Whether this set contains a packed k-mer (or, for a canonical set, its reverse
+    /// complement).
+    ///
+    /// Only the low `2 * k` bits of `kmer` are used.
+    pub fn contains(&self, kmer: u64) -> bool {
+        self.kmers.contains(&self.key(kmer))
+    }
+
+    /// Number of distinct k-mers in this set.
+    pub fn len(&self) -> usize {
+        self.kmers.len()
+    }
+
+    /// Whether this set has no k-mers.
+    pub fn is_empty(&self) -> bool {
+        self.kmers.is_empty()
+    }
+
+    /// Iterate over the k-mers in this set (canonicalized, for a canonical set), in
+    /// arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = u64> + '_ {
+        self.kmers.iter().copied()
+    }
+
+    /// The k-mer as stored in this set.
+    fn key(&self, kmer: u64) -> u64 {
+        let kmer = kmer & (u64::MAX >> (64 - 2 * self.k));
+        if self.canonical {
+            kmer.min(reverse_complement(kmer, self.k))
+        } else {
+            kmer
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use quickcheck::quickcheck;
+
+    use crate::{BaseSequence, DnaSequenceStrict};
+
+    fn dna(dna: &str) -> DnaSequenceStrict {
+        dna.parse().unwrap()
+    }
+
+    fn pack(src_dna: &str) -> u64 {
+        dna(src_dna)
+            .iter()
+            .fold(0, |acc, nuc| (acc << 2) | encode(nuc))
+    }
+
+    #[test]
+    fn encoding_round_trips() {
+        for nuc in Nucleotide::ALL {
+            assert_eq!(decode(encode(nuc)), nuc);
+        }
+    }
+
+    #[test]
+    fn kmers_are_packed_in_order() {
+        let src = dna("ATCGGA");
+        let kmers: Vec<_> = Kmers::new(src.iter(), 3).collect();
+        assert_eq!(kmers, ["ATC", "TCG", "CGG", "GGA"].map(pack));
+        assert_eq!(
+            Kmers::new(src.iter(), 6).collect::<Vec<_>>(),
+            [pack("ATCGGA")]
+        );
+        assert_eq!(Kmers::new(src.iter(), 7).next(), None);
+    }
+
+    #[test]
+    fn kmers_size_hint() {
+        let src = dna("ATCGGATT");
+        for k in 1..10 {
+            let mut kmers = Kmers::new(src.as_slice().iter().copied(), k);
+            let mut expected = src.len().saturating_sub(k - 1);
+            assert_eq!(kmers.len(), expected);
+            while kmers.next().is_some() {
+                expected -= 1;
+                assert_eq!(kmers.len(), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn kmers_of_max_size() {
+        let src = dna(&"G".repeat(33));
+        let kmers: Vec<_> = Kmers::new(src.iter(), MAX_K).collect();
+        assert_eq!(kmers, [u64::MAX, u64::MAX]);
+    }
+
+    #[test]
+    fn reverse_complement_kmers() {
+        assert_eq!(reverse_complement(pack("GGA"), 3), pack("TCC"));
+        assert_eq!(reverse_complement(pack("A"), 1), pack("T"));
+        let src = "GATTACAGATTACAGATTACAGATTACAGATT";
+        let rc = dna(src).reverse_complement().to_string();
+        assert_eq!(reverse_complement(pack(src), MAX_K), pack(&rc));
+    }
+
+    #[test]
+    fn kmer_sets() {
+        let mut set = KmerSet::new(2, false);
+        assert!(set.is_empty());
+        assert!(set.insert(pack("AC")));
+        assert!(!set.insert(pack("AC")));
+        // Bits above the k-mer are ignored.
+        assert!(set.contains(pack("GAC")));
+        assert!(!set.contains(pack("GT")));
+        set.insert_sequence(&dna("ACACGT"));
+        let mut kmers: Vec<_> = set.iter().collect();
+        kmers.sort();
+        assert_eq!(kmers, ["AC", "CA", "CG", "GT"].map(pack));
+
+        let canonical = KmerSet::from_sequence(&dna("ACACGT"), 2, true);
+        assert!(canonical.is_canonical());
+        assert_eq!(canonical.k(), 2);
+        // GT and AC are reverse complements, as are CA and TG.
+        assert_eq!(canonical.len(), 3);
+        assert!(canonical.contains(pack("TG")));
+        assert!(!set.contains(pack("TG")));
+    }
+
+    #[test]
+    fn canonical_kmers_choose_lesser_strand() {
+        // GGA's reverse complement is TCC, which sorts before it.
+        let kmers: Vec<_> = CanonicalKmers::new(dna("GGA").iter(), 3).collect();
+        assert_eq!(kmers, [pack("TCC")]);
+        let kmers: Vec<_> = CanonicalKmers::new(dna("ATC").iter(), 3).collect();
+        assert_eq!(kmers, [pack("ATC")]);
+    }
+
+    #[test]
+    fn minimizers_dedupe_consecutive() {
+        // Every window of 3 1-mers over "GGAGG" contains the single A.
+        let minimizers: Vec<_> = Minimizers::new(dna("GGAGG").iter(), 1, 3).collect();
+        assert_eq!(minimizers, [(2, pack("A"))]);
+
+        // G and C are both canonicalized to C, so ties go to the leftmost position.
+        let minimizers: Vec<_> = Minimizers::new(dna("CACGC").iter(), 1, 2).collect();
+        assert_eq!(minimizers, [(1, pack("A")), (2, pack("C")), (3, pack("C"))]);
+    }
+
+    #[test]
+    fn rolling_canonical_kmer_matches_iterator() {
+        let src = dna("GGATCGGATC");
+        let mut rolling = RollingCanonicalKmer::new(4);
+        let pushed: Vec<_> = src.iter().filter_map(|nuc| rolling.push(nuc)).collect();
+        assert_eq!(
+            pushed,
+            CanonicalKmers::new(src.iter(), 4).collect::<Vec<_>>()
+        );
+
+        rolling.reset();
+        assert_eq!(rolling.push(Nucleotide::G), None);
+        assert_eq!(rolling.push(Nucleotide::G), None);
+        assert_eq!(rolling.push(Nucleotide::A), None);
+        assert_eq!(rolling.push(Nucleotide::T), Some(pack("ATCC")));
+    }
+
+    fn naive_minimizers(dna: &DnaSequenceStrict, k: usize, w: usize) -> Vec<(usize, u64)> {
+        let kmers: Vec<_> = CanonicalKmers::new(dna.iter(), k).collect();
+        let mut result: Vec<(usize, u64)> = vec![];
+        for start in 0..(kmers.len() + 1).saturating_sub(w) {
+            let window = &kmers[start..start + w];
+            let min = *window.iter().min().unwrap();
+            let pos = start + window.iter().position(|&kmer| kmer == min).unwrap();
+            if result.last() != Some(&(pos, min)) {
+                result.push((pos, min));
+            }
+        }
+        result
+    }
+
+    quickcheck! {
+        fn canonical_kmer_sets_are_strand_independent(src: DnaSequenceStrict) -> bool {
+            let k = 5;
+            let forward = KmerSet::from_sequence(&src, k, true);
+            let reverse = KmerSet::from_sequence(&src.reverse_complement(), k, true);
+            forward == reverse && Kmers::new(src.iter(), k).all(|kmer| {
+                forward.contains(kmer) && forward.contains(reverse_complement(kmer, k))
+            })
+        }
+
+        fn canonical_kmers_are_strand_independent(dna: DnaSequenceStrict) -> bool {
+            let k = 5;
+            let mut rc_kmers: Vec<_> =
+                CanonicalKmers::new(dna.reverse_complement().iter(), k).collect();
+            rc_kmers.reverse();
+            CanonicalKmers::new(dna.iter(), k).eq(rc_kmers)
+        }
+
+        fn minimizers_match_naive_implementation(dna: DnaSequenceStrict) -> bool {
+            [(1, 1), (3, 4), (5, 2), (7, 10)].into_iter().all(|(k, w)| {
+                Minimizers::new(dna.iter(), k, w).eq(naive_minimizers(&dna, k, w))
+            })
+        }
+    }
+}
@@ -1,32 +1,97 @@
 #![allow(non_snake_case)]
+#![cfg_attr(not(feature = "std"), no_std)]
 // Copyright 2021-2024 SecureDNA Stiftung (SecureDNA Foundation) <licensing@securedna.org>
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
+extern crate alloc;
 extern crate core;
 
+// Without the `std` feature, only the modules that need nothing beyond `alloc` are built.
+
+#[cfg(feature = "std")]
+mod amino_acid;
 mod errors;
 mod nucleotide;
 pub mod trans_table; // needs to be public for bin/gen_table
 
+pub use errors::{LengthMismatch, Located, Positioned, TooManyExpansions, TranslationError};
+pub use nucleotide::{
+    Codon, CodonAmbiguous, Nucleotide, NucleotideAmbiguous, NucleotideGapped, NucleotideLike,
+};
+pub use trans_table::{CodonClass, TranslationSymbols, TranslationTable};
+
+#[cfg(feature = "std")]
+pub mod ambiguity;
+
+#[cfg(feature = "std")]
+pub mod ascii;
+
 pub mod canonical;
 
+#[cfg(any(feature = "md5", feature = "sha1"))]
+mod checksum;
+
+#[cfg(feature = "std")]
+pub mod codon_usage;
+
+#[cfg(feature = "std")]
+pub mod diff;
+
+#[cfg(feature = "std")]
 mod extendable;
+#[cfg(feature = "std")]
 pub use extendable::*;
 
 pub mod expansions;
 
+#[cfg(feature = "std")]
+pub mod isoelectric;
+
+#[cfg(feature = "std")]
+pub mod kmer;
+
+#[cfg(feature = "std")]
+pub mod twobit;
+
+#[cfg(feature = "std")]
+pub mod masked;
+
+#[cfg(feature = "std")]
+pub mod mass;
+
+#[cfg(feature = "std")]
+pub mod melting;
+
+#[cfg(any(feature = "serde", feature = "prost"))]
+mod bitpack;
+
+#[cfg(feature = "serde")]
+pub mod packed;
+
+#[cfg(feature = "prost")]
+pub mod proto;
+
+#[cfg(feature = "std")]
 mod fasta;
+#[cfg(feature = "std")]
 pub use fasta::*;
 
+#[cfg(feature = "std")]
 mod iter;
+#[cfg(feature = "std")]
 pub use iter::*;
 
+#[cfg(feature = "std")]
 mod rust_api;
+#[cfg(feature = "std")]
 pub use rust_api::*;
 
 #[cfg(feature = "python-support")]
 mod python_api;
 
+#[cfg(feature = "wasm")]
+pub mod wasm_api;
+
 #[cfg(any(feature = "quickcheck", test))]
 mod quickcheck;
 
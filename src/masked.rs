@@ -0,0 +1,279 @@
+// Copyright 2021-2024 SecureDNA Stiftung (SecureDNA Foundation) <licensing@securedna.org>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! DNA sequences that keep track of soft-masked positions.
+//!
+//! Genomic FASTA files conventionally use lowercase letters to mark repeat-masked regions.
+//! Parsing into a [`DnaSequence`] discards the case, while parsing into a
+//! [`MaskedDnaSequence`] remembers which positions were lowercase.
+
+use std::fmt::{self, Write};
+use std::ops::Range;
+use std::str::FromStr;
+
+use crate::{
+    BaseSequence, CasePolicy, DnaSequence, DnaSequenceAmbiguous, Extendable, NucleotideAmbiguous,
+    NucleotideLike, Positioned, TranslationError, WhitespacePolicy,
+};
+
+const BITS_PER_WORD: usize = 64;
+
+/// A DNA sequence, plus a mask of which of its positions are soft-masked.
+///
+/// # Examples
+///
+/// ```
+/// use quickdna::{masked::MaskedDnaSequence, NucleotideAmbiguous};
+///
+/// let dna: MaskedDnaSequence<NucleotideAmbiguous> = "ACgtNa".parse().unwrap();
+/// assert!(!dna.is_masked(1));
+/// assert!(dna.is_masked(2));
+/// assert_eq!(dna.dna().to_string(), "ACGTNA");
+/// assert_eq!(dna.soft_masked_string(), "ACgtNa");
+/// assert_eq!(dna.hard_mask().to_string(), "ACNNNN");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, std::hash::Hash)]
+pub struct MaskedDnaSequence<T: NucleotideLike> {
+    dna: DnaSequence<T>,
+    /// One bit per nucleotide, set if that nucleotide is masked.
+    mask: Vec<u64>,
+}
+
+impl<T: NucleotideLike> Default for MaskedDnaSequence<T> {
+    fn default() -> Self {
+        Self {
+            dna: DnaSequence::default(),
+            mask: vec![],
+        }
+    }
+}
+
+impl<T: NucleotideLike> MaskedDnaSequence<T> {
+    /// The underlying (uppercase) sequence, without its mask.
+    pub fn dna(&self) -> &DnaSequence<T> {
+        &self.dna
+    }
+
+    /// Number of nucleotides in this sequence.
+    pub fn len(&self) -> usize {
+        self.dna.len()
+    }
+
+    /// Whether this sequence has no nucleotides.
+    pub fn is_empty(&self) -> bool {
+        self.dna.is_empty()
+    }
+
+    /// Parse a DNA sequence from ASCII bytes, handling whitespace according to `whitespace`
+    /// and lowercase nucleotides according to `case`. Lowercase nucleotides are only masked
+    /// under [`CasePolicy::PreserveCase`], which is how the [`FromStr`] impl parses.
+    ///
+    /// On error, the position is the byte offset of the offending byte in `bytes`.
+    ///
+    /// ```
+    /// use quickdna::{masked::MaskedDnaSequence, CasePolicy, Nucleotide, WhitespacePolicy};
+    ///
+    /// let parse = |case| MaskedDnaSequence::<Nucleotide>::from_bytes_with(b"ACgt", WhitespacePolicy::default(), case);
+    /// assert_eq!(parse(CasePolicy::PreserveCase).unwrap().soft_masked_string(), "ACgt");
+    /// assert_eq!(parse(CasePolicy::AllowLowercase).unwrap().soft_masked_string(), "ACGT");
+    /// assert_eq!(parse(CasePolicy::RejectLowercase).unwrap_err().position, 2);
+    /// ```
+    pub fn from_bytes_with(
+        bytes: &[u8],
+        whitespace: WhitespacePolicy,
+        case: CasePolicy,
+    ) -> Result<Self, Positioned<TranslationError>> {
+        let mut dna = Vec::with_capacity(bytes.len());
+        let mut mask = vec![];
+        for (position, &b) in bytes.iter().enumerate() {
+            if !whitespace.skips(b) {
+                case.check(position, b)?;
+                let index = dna.len();
+                if index % BITS_PER_WORD == 0 {
+                    mask.push(0);
+                }
+                if case == CasePolicy::PreserveCase && b.is_ascii_lowercase() {
+                    *mask.last_mut().unwrap() |= 1 << (index % BITS_PER_WORD);
+                }
+                dna.push(T::try_from(b).map_err(|error| Positioned { position, error })?);
+            }
+        }
+        Ok(Self {
+            dna: DnaSequence::new(dna),
+            mask,
+        })
+    }
+
+    /// Whether the nucleotide at `index` is masked.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn is_masked(&self, index: usize) -> bool {
+        assert!(index < self.len(), "index {index} out of bounds");
+        self.mask[index / BITS_PER_WORD] & (1 << (index % BITS_PER_WORD)) != 0
+    }
+
+    /// Mask all nucleotides in `range`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds.
+    pub fn mask_range(&mut self, range: Range<usize>) {
+        assert!(range.end <= self.len(), "range {range:?} out of bounds");
+        for index in range {
+            self.mask[index / BITS_PER_WORD] |= 1 << (index % BITS_PER_WORD);
+        }
+    }
+
+    /// This sequence as a string, with masked nucleotides in lowercase.
+    pub fn soft_masked_string(&self) -> String {
+        self.to_string()
+    }
+
+    /// Replace every masked nucleotide with `N`.
+    pub fn hard_mask(&self) -> DnaSequenceAmbiguous
+    where
+        T: Into<NucleotideAmbiguous>,
+    {
+        let dna = self
+            .dna
+            .as_slice()
+            .iter()
+            .enumerate()
+            .map(|(i, &nuc)| {
+                if self.is_masked(i) {
+                    NucleotideAmbiguous::N
+                } else {
+                    nuc.into()
+                }
+            })
+            .collect();
+        DnaSequenceAmbiguous::new(dna)
+    }
+}
+
+impl<T: NucleotideLike> From<DnaSequence<T>> for MaskedDnaSequence<T> {
+    /// Wrap `dna` with nothing masked.
+    fn from(dna: DnaSequence<T>) -> Self {
+        let mask = vec![0; dna.len().div_ceil(BITS_PER_WORD)];
+        Self { dna, mask }
+    }
+}
+
+/// Lets FASTA files be parsed into soft-masked sequences, keeping the mask across lines.
+impl<T: NucleotideLike> Extendable for MaskedDnaSequence<T> {
+    fn is_blank(&self) -> bool {
+        self.is_empty()
+    }
+
+    fn extend(&mut self, other: Self) {
+        let start = self.len();
+        self.mask
+            .resize((start + other.len()).div_ceil(BITS_PER_WORD), 0);
+        for i in (0..other.len()).filter(|&i| other.is_masked(i)) {
+            let index = start + i;
+            self.mask[index / BITS_PER_WORD] |= 1 << (index % BITS_PER_WORD);
+        }
+        Extendable::extend(&mut self.dna, other.dna);
+    }
+
+    fn ignore_case(&mut self) {
+        self.mask.fill(0);
+    }
+}
+
+impl<T: NucleotideLike> TryFrom<&[u8]> for MaskedDnaSequence<T> {
+    type Error = Positioned<TranslationError>;
+
+    /// Parse like [`DnaSequence`], masking each lowercase nucleotide.
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        Self::from_bytes_with(
+            value,
+            WhitespacePolicy::SkipSpacesTabs,
+            CasePolicy::PreserveCase,
+        )
+    }
+}
+
+impl<T: NucleotideLike> FromStr for MaskedDnaSequence<T> {
+    type Err = Positioned<TranslationError>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s.as_bytes())
+    }
+}
+
+impl<T: NucleotideLike> fmt::Display for MaskedDnaSequence<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, &nuc) in self.dna.as_slice().iter().enumerate() {
+            let c: char = nuc.into();
+            if self.is_masked(i) {
+                f.write_char(c.to_ascii_lowercase())?;
+            } else {
+                f.write_char(c)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::{DnaSequenceStrict, Nucleotide};
+
+    #[test]
+    fn parses_mask_across_words() {
+        let src = format!("{}{}{}", "A".repeat(63), "cg", "T".repeat(70));
+        let dna: MaskedDnaSequence<Nucleotide> = src.parse().unwrap();
+        assert_eq!(dna.len(), 135);
+        let masked: Vec<usize> = (0..dna.len()).filter(|&i| dna.is_masked(i)).collect();
+        assert_eq!(masked, [63, 64]);
+        assert_eq!(dna.soft_masked_string(), src);
+        assert_eq!(dna.dna().to_string(), src.to_uppercase());
+    }
+
+    #[test]
+    fn mask_range_and_hard_mask() {
+        let dna: DnaSequenceStrict = "GATTACA".parse().unwrap();
+        let mut masked = MaskedDnaSequence::from(dna);
+        assert_eq!(masked.soft_masked_string(), "GATTACA");
+        masked.mask_range(1..3);
+        masked.mask_range(6..7);
+        assert_eq!(masked.soft_masked_string(), "GatTACa");
+        assert_eq!(masked.hard_mask().to_string(), "GNNTACN");
+        masked.mask_range(7..7);
+    }
+
+    #[test]
+    fn rejects_bad_input() {
+        let err = "ACGN".parse::<MaskedDnaSequence<Nucleotide>>().unwrap_err();
+        assert_eq!(err.position, 3);
+        let dna: MaskedDnaSequence<NucleotideAmbiguous> = "a Cn\tG".parse().unwrap();
+        assert_eq!(dna.soft_masked_string(), "aCnG");
+        assert!(""
+            .parse::<MaskedDnaSequence<Nucleotide>>()
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn extend_across_words() {
+        let mut dna: MaskedDnaSequence<Nucleotide> = "a".repeat(63).parse().unwrap();
+        dna.extend("Cg".parse().unwrap());
+        dna.extend(MaskedDnaSequence::default());
+        dna.extend("tA".parse().unwrap());
+        assert_eq!(dna.len(), 67);
+        let masked: Vec<usize> = (60..dna.len()).filter(|&i| dna.is_masked(i)).collect();
+        assert_eq!(masked, [60, 61, 62, 64, 65]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn mask_range_out_of_bounds() {
+        let mut dna: MaskedDnaSequence<Nucleotide> = "ACGT".parse().unwrap();
+        dna.mask_range(2..5);
+    }
+}
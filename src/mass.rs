@@ -0,0 +1,144 @@
+// Copyright 2021-2024 SecureDNA Stiftung (SecureDNA Foundation) <licensing@securedna.org>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Average molecular masses of nucleotides and amino acids, in daltons (g/mol).
+//!
+//! Sequence weights are the sum of their monomers' masses, minus one [`WATER`] for each
+//! phosphodiester or peptide bond formed between them.
+
+use crate::{Nucleotide, NucleotideLike};
+
+/// Mass of water, lost in each condensation reaction.
+pub const WATER: f64 = 18.01528;
+
+/// Mass of deoxyadenosine monophosphate.
+pub const DAMP: f64 = 331.2218;
+
+/// Mass of deoxythymidine monophosphate.
+pub const DTMP: f64 = 322.2085;
+
+/// Mass of deoxycytidine monophosphate.
+pub const DCMP: f64 = 307.1971;
+
+/// Mass of deoxyguanosine monophosphate.
+pub const DGMP: f64 = 347.2212;
+
+/// Masses of the free amino acids, by one-letter code.
+///
+/// Includes the 20 standard amino acids, plus selenocysteine (`U`) and pyrrolysine (`O`).
+pub const AMINO_ACID_MASSES: [(u8, f64); 22] = [
+    (b'A', 89.0932),
+    (b'C', 121.1582),
+    (b'D', 133.1027),
+    (b'E', 147.1293),
+    (b'F', 165.1891),
+    (b'G', 75.0666),
+    (b'H', 155.1546),
+    (b'I', 131.1729),
+    (b'K', 146.1876),
+    (b'L', 131.1729),
+    (b'M', 149.2113),
+    (b'N', 132.1179),
+    (b'O', 255.3134),
+    (b'P', 115.1305),
+    (b'Q', 146.1445),
+    (b'R', 174.2010),
+    (b'S', 105.0926),
+    (b'T', 119.1192),
+    (b'U', 168.0532),
+    (b'V', 117.1463),
+    (b'W', 204.2252),
+    (b'Y', 181.1885),
+];
+
+/// Mass of a nucleotide monophosphate.
+///
+/// Ambiguous nucleotides have the average mass of their possibilities, and gaps, which
+/// aren't monomers at all, have no mass.
+pub fn nucleotide_mass(nucleotide: impl NucleotideLike) -> f64 {
+    if nucleotide.is_gap() {
+        return 0.0;
+    }
+    let bits = nucleotide.bits();
+    let total: f64 = Nucleotide::ALL
+        .into_iter()
+        .filter(|&nuc| bits & nuc as u8 != 0)
+        .map(|nuc| match nuc {
+            Nucleotide::A => DAMP,
+            Nucleotide::T => DTMP,
+            Nucleotide::C => DCMP,
+            Nucleotide::G => DGMP,
+        })
+        .sum();
+    total / bits.count_ones() as f64
+}
+
+/// Mass of an amino acid, given its (uppercase) one-letter code.
+///
+/// The ambiguity codes `B` (`D`/`N`), `Z` (`E`/`Q`) and `J` (`I`/`L`) have the average mass
+/// of their possibilities, and `X` has the average mass of the 20 standard amino acids.
+/// Returns `None` for any other byte.
+pub fn amino_acid_mass(aa: u8) -> Option<f64> {
+    let average = |codes: &[u8]| {
+        let total: f64 = codes.iter().filter_map(|&aa| amino_acid_mass(aa)).sum();
+        total / codes.len() as f64
+    };
+    match aa {
+        b'B' => Some(average(b"DN")),
+        b'Z' => Some(average(b"EQ")),
+        b'J' => Some(average(b"IL")),
+        b'X' => Some(average(b"ACDEFGHIKLMNPQRSTVWY")),
+        _ => AMINO_ACID_MASSES
+            .iter()
+            .find(|&&(code, _)| code == aa)
+            .map(|&(_, mass)| mass),
+    }
+}
+
+/// Molecular weight of a polymer with the given monomer masses.
+pub(crate) fn polymer_weight(masses: impl Iterator<Item = f64>) -> f64 {
+    let (total, count) = masses.fold((0.0, 0), |(total, count), mass| (total + mass, count + 1));
+    if count == 0 {
+        0.0
+    } else {
+        total - (count - 1) as f64 * WATER
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::{NucleotideAmbiguous, NucleotideGapped};
+
+    #[test]
+    fn ambiguous_nucleotide_masses() {
+        assert_eq!(nucleotide_mass(NucleotideAmbiguous::A), DAMP);
+        assert_eq!(nucleotide_mass(NucleotideAmbiguous::S), (DCMP + DGMP) / 2.0);
+        assert_eq!(
+            nucleotide_mass(NucleotideAmbiguous::N),
+            (DAMP + DTMP + DCMP + DGMP) / 4.0
+        );
+        assert_eq!(nucleotide_mass(NucleotideGapped::Gap), 0.0);
+        assert_eq!(nucleotide_mass(NucleotideGapped::S), (DCMP + DGMP) / 2.0);
+    }
+
+    #[test]
+    fn ambiguous_amino_acid_masses() {
+        assert_eq!(amino_acid_mass(b'W'), Some(204.2252));
+        assert_eq!(amino_acid_mass(b'J'), Some(131.1729));
+        assert!(amino_acid_mass(b'X').is_some());
+        assert_eq!(amino_acid_mass(b'*'), None);
+        assert_eq!(amino_acid_mass(b'w'), None);
+    }
+
+    #[test]
+    fn polymer_weight_subtracts_water() {
+        assert_eq!(polymer_weight(std::iter::empty()), 0.0);
+        assert_eq!(polymer_weight([DAMP].into_iter()), DAMP);
+        assert_eq!(
+            polymer_weight([DAMP, DTMP].into_iter()),
+            DAMP + DTMP - WATER
+        );
+    }
+}
@@ -0,0 +1,183 @@
+// Copyright 2021-2024 SecureDNA Stiftung (SecureDNA Foundation) <licensing@securedna.org>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Melting temperature estimation for unambiguous DNA oligos.
+//!
+//! The nearest-neighbor model uses the unified parameters from SantaLucia (1998),
+//! "A unified view of polymer, dumbbell, and oligonucleotide DNA nearest-neighbor
+//! thermodynamics", PNAS 95(4), with its entropic salt correction.
+
+use crate::{Nucleotide, NucleotideLike};
+
+/// Gas constant, in cal/(K·mol).
+const R: f64 = 1.987;
+
+/// 0 °C, in kelvin.
+const ZERO_CELSIUS: f64 = 273.15;
+
+/// Conditions for [`melting_temp_nn`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TmParams {
+    /// Concentration of each strand, in nM. The default value is 50 nM.
+    oligo_concentration_nm: f64,
+
+    /// Concentration of monovalent cations, in mM. The default value is 50 mM.
+    sodium_concentration_mm: f64,
+}
+
+// "Builder-lite" pattern: https://matklad.github.io/2022/05/29/builder-lite.html
+impl TmParams {
+    /// Initializes conditions to their defaults: 50 nM of each strand in 50 mM Na⁺.
+    pub fn new() -> Self {
+        Self {
+            oligo_concentration_nm: 50.0,
+            sodium_concentration_mm: 50.0,
+        }
+    }
+
+    /// Changes the setting for [`Self::oligo_concentration_nm`]
+    pub fn oligo_concentration_nm(mut self, oligo_concentration_nm: f64) -> Self {
+        self.oligo_concentration_nm = oligo_concentration_nm;
+        self
+    }
+
+    /// Changes the setting for [`Self::sodium_concentration_mm`]
+    pub fn sodium_concentration_mm(mut self, sodium_concentration_mm: f64) -> Self {
+        self.sodium_concentration_mm = sodium_concentration_mm;
+        self
+    }
+}
+
+impl Default for TmParams {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Estimate melting temperature in °C with the Wallace rule, 2·(A+T) + 4·(G+C).
+///
+/// This is only a rough estimate, intended for oligos of roughly 14-20 bases.
+pub fn melting_temp_wallace(dna: &[Nucleotide]) -> f64 {
+    dna.iter()
+        .map(|nuc| match nuc {
+            Nucleotide::A | Nucleotide::T => 2.0,
+            Nucleotide::C | Nucleotide::G => 4.0,
+        })
+        .sum()
+}
+
+/// Estimate melting temperature in °C of `dna` hybridized to its exact complement, using
+/// the nearest-neighbor model.
+///
+/// Returns NaN if `dna` has fewer than 2 bases.
+pub fn melting_temp_nn(dna: &[Nucleotide], params: TmParams) -> f64 {
+    let [first, .., last] = *dna else {
+        return f64::NAN;
+    };
+
+    let (mut dh, mut ds) = initiation(first);
+    let (init_dh, init_ds) = initiation(last);
+    dh += init_dh;
+    ds += init_ds;
+    for pair in dna.windows(2) {
+        let (stack_dh, stack_ds) = stack(pair[0], pair[1]);
+        dh += stack_dh;
+        ds += stack_ds;
+    }
+
+    let self_complementary = dna
+        .iter()
+        .zip(dna.iter().rev())
+        .all(|(&a, &b)| a == b.complement());
+    let strand_concentration = params.oligo_concentration_nm * 1e-9;
+    let k = if self_complementary {
+        ds -= 1.4;
+        strand_concentration
+    } else {
+        strand_concentration / 2.0
+    };
+
+    let phosphates = (dna.len() - 1) as f64;
+    ds += 0.368 * phosphates * (params.sodium_concentration_mm * 1e-3).ln();
+
+    1000.0 * dh / (ds + R * k.ln()) - ZERO_CELSIUS
+}
+
+/// Initiation ΔH° (kcal/mol) and ΔS° (cal/(K·mol)) for a terminal base pair.
+fn initiation(terminal: Nucleotide) -> (f64, f64) {
+    match terminal {
+        Nucleotide::A | Nucleotide::T => (2.3, 4.1),
+        Nucleotide::C | Nucleotide::G => (0.1, -2.8),
+    }
+}
+
+/// Stacking ΔH° (kcal/mol) and ΔS° (cal/(K·mol)) for the 5'→3' dinucleotide `ab`.
+fn stack(a: Nucleotide, b: Nucleotide) -> (f64, f64) {
+    use Nucleotide::*;
+    match (a, b) {
+        (A, A) | (T, T) => (-7.9, -22.2),
+        (A, T) => (-7.2, -20.4),
+        (T, A) => (-7.2, -21.3),
+        (C, A) | (T, G) => (-8.5, -22.7),
+        (G, T) | (A, C) => (-8.4, -22.4),
+        (C, T) | (A, G) => (-7.8, -21.0),
+        (G, A) | (T, C) => (-8.2, -22.2),
+        (C, G) => (-10.6, -27.2),
+        (G, C) => (-9.8, -24.4),
+        (G, G) | (C, C) => (-8.0, -19.9),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use quickcheck::quickcheck;
+
+    use crate::{BaseSequence, DnaSequenceStrict};
+
+    fn dna(dna: &str) -> DnaSequenceStrict {
+        dna.parse().unwrap()
+    }
+
+    #[test]
+    fn wallace_rule() {
+        assert_eq!(melting_temp_wallace(dna("").as_slice()), 0.0);
+        assert_eq!(melting_temp_wallace(dna("ATTA").as_slice()), 8.0);
+        assert_eq!(melting_temp_wallace(dna("ACGTGCA").as_slice()), 22.0);
+    }
+
+    #[test]
+    fn nearest_neighbor_needs_two_bases() {
+        assert!(melting_temp_nn(dna("").as_slice(), TmParams::new()).is_nan());
+        assert!(melting_temp_nn(dna("G").as_slice(), TmParams::new()).is_nan());
+    }
+
+    #[test]
+    fn nearest_neighbor_known_value() {
+        // ΔH° = -153.3 kcal/mol, ΔS° = -412.6 cal/(K·mol) before salt correction.
+        let tm = melting_temp_nn(dna("AGCGTCCATGAACTGGATCA").as_slice(), TmParams::new());
+        assert!((tm - 54.19).abs() < 0.01, "{tm}");
+    }
+
+    #[test]
+    fn nearest_neighbor_conditions() {
+        let oligo = dna("AGCGTCCATGAACTGGATCA");
+        let tm = |params| melting_temp_nn(oligo.as_slice(), params);
+        let base = tm(TmParams::new());
+        assert!(tm(TmParams::new().sodium_concentration_mm(500.0)) > base);
+        assert!(tm(TmParams::new().oligo_concentration_nm(1000.0)) > base);
+        assert!(
+            melting_temp_nn(dna("ATATATATAT").as_slice(), TmParams::new())
+                < melting_temp_nn(dna("GCGCGCGCGC").as_slice(), TmParams::new())
+        );
+    }
+
+    quickcheck! {
+        fn nearest_neighbor_is_strand_independent(dna: DnaSequenceStrict) -> bool {
+            let fw = melting_temp_nn(dna.as_slice(), TmParams::new());
+            let rc = melting_temp_nn(dna.reverse_complement().as_slice(), TmParams::new());
+            (fw.is_nan() && rc.is_nan()) || (fw - rc).abs() < 1e-9
+        }
+    }
+}
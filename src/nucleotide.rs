@@ -1,12 +1,14 @@
 // Copyright 2021-2024 SecureDNA Stiftung (SecureDNA Foundation) <licensing@securedna.org>
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
-use std::{
+use alloc::vec::Vec;
+use core::{
     fmt::{self, Write},
     str::FromStr,
 };
 
-use crate::errors::{CodonError, TranslationError};
+use crate::errors::{CodonError, Positioned, TranslationError};
+use crate::trans_table::TranslationTable;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -17,7 +19,7 @@ use crate::serde_utils;
 /// A DNA nucleotide.
 ///
 /// Sorts in ATCG order, not alphabetical.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, std::hash::Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, core::hash::Hash)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 #[repr(u8)]
 pub enum Nucleotide {
@@ -28,7 +30,7 @@ pub enum Nucleotide {
 }
 
 /// A DNA nucleotide, or an IUPAC ambiguity code representing a set of possible nucleotides.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, std::hash::Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, core::hash::Hash)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 #[repr(u8)]
 pub enum NucleotideAmbiguous {
@@ -52,6 +54,37 @@ pub enum NucleotideAmbiguous {
     N = Nucleotide::A as u8 | Nucleotide::T as u8 | Nucleotide::C as u8 | Nucleotide::G as u8,
 }
 
+/// A DNA nucleotide or IUPAC ambiguity code, or a gap in an alignment.
+///
+/// Parses `-` and `.` as [`Self::Gap`], which is its own complement, and translates to `-`
+/// as part of any codon. Every other variant has the same bits as the
+/// [`NucleotideAmbiguous`] of the same name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, core::hash::Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[repr(u8)]
+pub enum NucleotideGapped {
+    Gap = 0,
+
+    A = NucleotideAmbiguous::A as u8,
+    T = NucleotideAmbiguous::T as u8,
+    C = NucleotideAmbiguous::C as u8,
+    G = NucleotideAmbiguous::G as u8,
+
+    W = NucleotideAmbiguous::W as u8,
+    M = NucleotideAmbiguous::M as u8,
+    R = NucleotideAmbiguous::R as u8,
+    Y = NucleotideAmbiguous::Y as u8,
+    S = NucleotideAmbiguous::S as u8,
+    K = NucleotideAmbiguous::K as u8,
+
+    B = NucleotideAmbiguous::B as u8,
+    V = NucleotideAmbiguous::V as u8,
+    D = NucleotideAmbiguous::D as u8,
+    H = NucleotideAmbiguous::H as u8,
+
+    N = NucleotideAmbiguous::N as u8,
+}
+
 pub trait NucleotideLike:
     Copy + Eq + Into<u8> + Into<char> + TryFrom<u8, Error = TranslationError>
 {
@@ -61,6 +94,71 @@ pub trait NucleotideLike:
     fn bits(self) -> u8;
     fn to_ascii(self) -> u8;
     fn is_ambiguous(self) -> bool;
+
+    /// Whether this is a gap in an alignment, which stands for no nucleotide at all.
+    ///
+    /// Only a [`NucleotideGapped`] can be a gap.
+    fn is_gap(self) -> bool {
+        false
+    }
+
+    /// Parse a whole slice of ASCII nucleotides at once.
+    ///
+    /// On failure, the error is the one [`TryFrom<u8>`] would give for the first invalid
+    /// byte, along with that byte's position. By default, this converts each byte with
+    /// [`TryFrom<u8>`], but this crate's nucleotide types override it to validate the slice in
+    /// bulk, which is faster.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quickdna::{Nucleotide, NucleotideLike, TranslationError};
+    ///
+    /// use Nucleotide::*;
+    /// assert_eq!(Nucleotide::try_pack_ascii(b"GaTc").unwrap(), [G, A, T, C]);
+    ///
+    /// let err = Nucleotide::try_pack_ascii(b"GATNC").unwrap_err();
+    /// assert_eq!(err.position, 3);
+    /// assert!(matches!(err.error, TranslationError::UnexpectedAmbiguousNucleotide('N')));
+    /// ```
+    fn try_pack_ascii(ascii: &[u8]) -> Result<Vec<Self>, Positioned<TranslationError>> {
+        ascii
+            .iter()
+            .enumerate()
+            .map(|(position, &b)| Self::try_from(b).map_err(|error| Positioned { position, error }))
+            .collect()
+    }
+}
+
+/// Pack `ascii` using `table`, deferring error handling until after the loop so that the
+/// common all-valid case doesn't branch per byte.
+fn try_pack_ascii_with<T: NucleotideLike>(
+    table: &[Option<T>; 256],
+    filler: T,
+    ascii: &[u8],
+) -> Result<Vec<T>, Positioned<TranslationError>> {
+    let mut valid = true;
+    let packed = ascii
+        .iter()
+        .map(|&b| {
+            let nucleotide = table[b as usize];
+            valid &= nucleotide.is_some();
+            nucleotide.unwrap_or(filler)
+        })
+        .collect();
+    if valid {
+        return Ok(packed);
+    }
+
+    let position = ascii
+        .iter()
+        .position(|&b| table[b as usize].is_none())
+        .unwrap();
+    let error = match T::try_from(ascii[position]) {
+        Err(error) => error,
+        Ok(_) => unreachable!("packing table disagrees with TryFrom<u8>"),
+    };
+    Err(Positioned { position, error })
 }
 
 const fn ascii_to_nucleotide_table() -> [Option<NucleotideAmbiguous>; 256] {
@@ -100,6 +198,42 @@ const fn ascii_to_nucleotide_table() -> [Option<NucleotideAmbiguous>; 256] {
 
 const ASCII_TO_NUCLEOTIDE: [Option<NucleotideAmbiguous>; 256] = ascii_to_nucleotide_table();
 
+const fn ascii_to_strict_nucleotide_table() -> [Option<Nucleotide>; 256] {
+    let mut strict_table = [None; 256];
+    let mut i = 0;
+    while i < 256 {
+        strict_table[i] = match ASCII_TO_NUCLEOTIDE[i] {
+            Some(NucleotideAmbiguous::A) => Some(Nucleotide::A),
+            Some(NucleotideAmbiguous::T) => Some(Nucleotide::T),
+            Some(NucleotideAmbiguous::C) => Some(Nucleotide::C),
+            Some(NucleotideAmbiguous::G) => Some(Nucleotide::G),
+            _ => None,
+        };
+        i += 1;
+    }
+    strict_table
+}
+
+const ASCII_TO_STRICT_NUCLEOTIDE: [Option<Nucleotide>; 256] = ascii_to_strict_nucleotide_table();
+
+const fn ascii_to_gapped_nucleotide_table() -> [Option<NucleotideGapped>; 256] {
+    let mut gapped_table = [None; 256];
+    let mut i = 0;
+    while i < 256 {
+        gapped_table[i] = match ASCII_TO_NUCLEOTIDE[i] {
+            Some(nuc) => Some(NucleotideGapped::from_ambiguous(nuc)),
+            None => None,
+        };
+        i += 1;
+    }
+    gapped_table[b'-' as usize] = Some(NucleotideGapped::Gap);
+    gapped_table[b'.' as usize] = Some(NucleotideGapped::Gap);
+    gapped_table
+}
+
+const ASCII_TO_GAPPED_NUCLEOTIDE: [Option<NucleotideGapped>; 256] =
+    ascii_to_gapped_nucleotide_table();
+
 impl Nucleotide {
     pub const ALL: [Self; 4] = [Self::A, Self::T, Self::C, Self::G];
 
@@ -117,6 +251,58 @@ impl Nucleotide {
     pub const H_AMBIGUITY: [Self; 3] = [Self::A, Self::C, Self::T];
     pub const D_AMBIGUITY: [Self; 3] = [Self::A, Self::G, Self::T];
     pub const B_AMBIGUITY: [Self; 3] = [Self::C, Self::G, Self::T];
+
+    /// The nucleotide with the given [`bits`](NucleotideLike::bits), or `None` if `bits`
+    /// doesn't have exactly one of the 4 nucleotide bits set.
+    ///
+    /// ```
+    /// use quickdna::{Nucleotide, NucleotideLike};
+    ///
+    /// assert_eq!(Nucleotide::from_bits(Nucleotide::C.bits()), Some(Nucleotide::C));
+    /// assert_eq!(Nucleotide::from_bits(Nucleotide::C as u8 | Nucleotide::G as u8), None);
+    /// ```
+    pub const fn from_bits(bits: u8) -> Option<Self> {
+        match bits {
+            0b0001 => Some(Self::A),
+            0b0010 => Some(Self::T),
+            0b0100 => Some(Self::C),
+            0b1000 => Some(Self::G),
+            _ => None,
+        }
+    }
+
+    /// Count the `G`, `C`, `g` and `c` bytes of unparsed ASCII DNA, 16 bytes at a time.
+    ///
+    /// Other bytes are ignored, so for valid DNA this agrees with the G and C counts of
+    /// [`DnaSequence::base_counts`](crate::DnaSequence::base_counts).
+    ///
+    /// ```
+    /// use quickdna::Nucleotide;
+    ///
+    /// assert_eq!(Nucleotide::gc_count_simd(b"GATTACAgattacaNNNNcgcg"), 8);
+    /// ```
+    #[cfg(feature = "simd")]
+    pub fn gc_count_simd(ascii: &[u8]) -> usize {
+        use wide::u8x16;
+
+        // Setting the case bit maps `G` to `g` and `C` to `c`, and no other bytes to either.
+        let lowercase = u8x16::splat(0x20);
+        let (g, c) = (u8x16::splat(b'g'), u8x16::splat(b'c'));
+
+        let mut chunks = ascii.chunks_exact(16);
+        let mut count = 0;
+        for chunk in &mut chunks {
+            let bytes = u8x16::from(<[u8; 16]>::try_from(chunk).unwrap()) | lowercase;
+            let is_gc = bytes.cmp_eq(g) | bytes.cmp_eq(c);
+            count += is_gc.move_mask().count_ones() as usize;
+        }
+        count
+            + chunks
+                .remainder()
+                .iter()
+                .filter(|&&b| matches!(b | 0x20, b'g' | b'c'))
+                .count()
+    }
 }
 
 impl NucleotideLike for Nucleotide {
@@ -147,6 +333,10 @@ impl NucleotideLike for Nucleotide {
     fn is_ambiguous(self) -> bool {
         false
     }
+
+    fn try_pack_ascii(ascii: &[u8]) -> Result<Vec<Self>, Positioned<TranslationError>> {
+        try_pack_ascii_with(&ASCII_TO_STRICT_NUCLEOTIDE, Self::A, ascii)
+    }
 }
 
 impl NucleotideAmbiguous {
@@ -168,6 +358,77 @@ impl NucleotideAmbiguous {
         Self::N,
     ];
 
+    /// The ambiguity code with the given [`bits`](NucleotideLike::bits), or `None` if `bits`
+    /// isn't a non-empty set of nucleotides (i.e. it's 0, or above 15).
+    ///
+    /// ```
+    /// use quickdna::{Nucleotide, NucleotideAmbiguous, NucleotideLike};
+    ///
+    /// let bits = Nucleotide::A as u8 | Nucleotide::G as u8;
+    /// assert_eq!(NucleotideAmbiguous::from_bits(bits), Some(NucleotideAmbiguous::R));
+    /// assert_eq!(NucleotideAmbiguous::from_bits(0), None);
+    /// assert_eq!(NucleotideAmbiguous::from_bits(16), None);
+    /// ```
+    pub const fn from_bits(bits: u8) -> Option<Self> {
+        match bits {
+            // ALL is in ascending order of bits, covering every non-zero 4-bit pattern
+            1..=15 => Some(Self::ALL[bits as usize - 1]),
+            _ => None,
+        }
+    }
+
+    /// Whether `n` is one of the possibilities of this ambiguity code.
+    ///
+    /// ```
+    /// use quickdna::{Nucleotide, NucleotideAmbiguous};
+    ///
+    /// assert!(NucleotideAmbiguous::R.contains(Nucleotide::G));
+    /// assert!(!NucleotideAmbiguous::R.contains(Nucleotide::C));
+    /// ```
+    pub const fn contains(self, n: Nucleotide) -> bool {
+        self as u8 & n as u8 != 0
+    }
+
+    /// The number of possibilities of this ambiguity code, from 1 to 4.
+    ///
+    /// This is the same as `self.possibilities().len()`.
+    #[allow(clippy::len_without_is_empty)] // never empty
+    pub const fn len(self) -> usize {
+        (self as u8).count_ones() as usize
+    }
+
+    /// The ambiguity code covering the possibilities of both `self` and `other`.
+    ///
+    /// ```
+    /// use quickdna::NucleotideAmbiguous;
+    ///
+    /// use NucleotideAmbiguous::*;
+    /// assert_eq!(A.union(G), R);
+    /// assert_eq!(R.union(Y), N);
+    /// assert_eq!(S.union(C), S);
+    /// ```
+    pub const fn union(self, other: Self) -> Self {
+        match Self::from_bits(self as u8 | other as u8) {
+            Some(union) => union,
+            None => unreachable!(),
+        }
+    }
+
+    /// The ambiguity code covering the possibilities shared by `self` and `other`, or `None`
+    /// if they have none in common.
+    ///
+    /// ```
+    /// use quickdna::NucleotideAmbiguous;
+    ///
+    /// use NucleotideAmbiguous::*;
+    /// assert_eq!(R.intersection(M), Some(A));
+    /// assert_eq!(V.intersection(B), Some(S));
+    /// assert_eq!(R.intersection(Y), None);
+    /// ```
+    pub const fn intersection(self, other: Self) -> Option<Self> {
+        Self::from_bits(self as u8 & other as u8)
+    }
+
     pub const fn possibilities(self) -> &'static [Nucleotide] {
         match self {
             Self::A => &[Nucleotide::A],
@@ -239,6 +500,70 @@ impl NucleotideLike for NucleotideAmbiguous {
     fn is_ambiguous(self) -> bool {
         (self as usize).count_ones() > 1
     }
+
+    fn try_pack_ascii(ascii: &[u8]) -> Result<Vec<Self>, Positioned<TranslationError>> {
+        try_pack_ascii_with(&ASCII_TO_NUCLEOTIDE, Self::N, ascii)
+    }
+}
+
+impl NucleotideGapped {
+    /// Every variant, in ascending order of bits.
+    pub const ALL: [Self; 16] = [
+        Self::Gap,
+        Self::A,
+        Self::T,
+        Self::W,
+        Self::C,
+        Self::M,
+        Self::Y,
+        Self::H,
+        Self::G,
+        Self::R,
+        Self::K,
+        Self::D,
+        Self::S,
+        Self::V,
+        Self::B,
+        Self::N,
+    ];
+
+    const fn from_ambiguous(nuc: NucleotideAmbiguous) -> Self {
+        Self::ALL[nuc as usize]
+    }
+}
+
+impl NucleotideLike for NucleotideGapped {
+    type Codon = [Self; 3];
+
+    fn complement(self) -> Self {
+        match NucleotideAmbiguous::try_from(self) {
+            Ok(nuc) => Self::from(nuc.complement()),
+            Err(_) => Self::Gap,
+        }
+    }
+
+    fn bits(self) -> u8 {
+        self as u8
+    }
+
+    fn to_ascii(self) -> u8 {
+        match NucleotideAmbiguous::try_from(self) {
+            Ok(nuc) => nuc.to_ascii(),
+            Err(_) => b'-',
+        }
+    }
+
+    fn is_ambiguous(self) -> bool {
+        (self as usize).count_ones() > 1
+    }
+
+    fn is_gap(self) -> bool {
+        self == Self::Gap
+    }
+
+    fn try_pack_ascii(ascii: &[u8]) -> Result<Vec<Self>, Positioned<TranslationError>> {
+        try_pack_ascii_with(&ASCII_TO_GAPPED_NUCLEOTIDE, Self::Gap, ascii)
+    }
 }
 
 impl From<Nucleotide> for NucleotideAmbiguous {
@@ -270,6 +595,38 @@ impl TryFrom<NucleotideAmbiguous> for Nucleotide {
     }
 }
 
+impl From<Nucleotide> for NucleotideGapped {
+    #[inline(always)]
+    fn from(value: Nucleotide) -> Self {
+        Self::from(NucleotideAmbiguous::from(value))
+    }
+}
+
+impl From<NucleotideAmbiguous> for NucleotideGapped {
+    #[inline(always)]
+    fn from(value: NucleotideAmbiguous) -> Self {
+        Self::from_ambiguous(value)
+    }
+}
+
+impl TryFrom<NucleotideGapped> for NucleotideAmbiguous {
+    type Error = TranslationError;
+
+    #[inline(always)]
+    fn try_from(value: NucleotideGapped) -> Result<Self, Self::Error> {
+        Self::from_bits(value as u8).ok_or(TranslationError::BadNucleotide('-'))
+    }
+}
+
+impl TryFrom<NucleotideGapped> for Nucleotide {
+    type Error = TranslationError;
+
+    #[inline(always)]
+    fn try_from(value: NucleotideGapped) -> Result<Self, Self::Error> {
+        Self::try_from(NucleotideAmbiguous::try_from(value)?)
+    }
+}
+
 impl TryFrom<u8> for Nucleotide {
     type Error = TranslationError;
 
@@ -302,6 +659,18 @@ impl TryFrom<u8> for NucleotideAmbiguous {
     }
 }
 
+impl TryFrom<u8> for NucleotideGapped {
+    type Error = TranslationError;
+
+    #[inline(always)]
+    fn try_from(u: u8) -> Result<Self, Self::Error> {
+        match u {
+            b'-' | b'.' => Ok(Self::Gap),
+            _ => NucleotideAmbiguous::try_from(u).map(Self::from),
+        }
+    }
+}
+
 impl From<Nucleotide> for u8 {
     fn from(n: Nucleotide) -> Self {
         n.to_ascii()
@@ -326,6 +695,18 @@ impl From<NucleotideAmbiguous> for char {
     }
 }
 
+impl From<NucleotideGapped> for u8 {
+    fn from(n: NucleotideGapped) -> Self {
+        n.to_ascii()
+    }
+}
+
+impl From<NucleotideGapped> for char {
+    fn from(n: NucleotideGapped) -> Self {
+        n.to_ascii() as char
+    }
+}
+
 impl fmt::Display for Nucleotide {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.write_char((*self).into())
@@ -338,7 +719,13 @@ impl fmt::Display for NucleotideAmbiguous {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, std::hash::Hash)]
+impl fmt::Display for NucleotideGapped {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_char((*self).into())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, core::hash::Hash)]
 pub struct Codon(pub [Nucleotide; 3]);
 
 impl TryFrom<[u8; 3]> for Codon {
@@ -365,6 +752,20 @@ impl From<Codon> for [Nucleotide; 3] {
     }
 }
 
+impl Codon {
+    /// Translate this codon with `table`. See [`TranslationTable::translate_codon`].
+    ///
+    /// ```
+    /// use quickdna::{Codon, TranslationTable};
+    ///
+    /// let codon: Codon = "ATG".parse().unwrap();
+    /// assert_eq!(codon.translate(TranslationTable::Ncbi1), b'M');
+    /// ```
+    pub fn translate(self, table: TranslationTable) -> u8 {
+        table.translate_codon(self)
+    }
+}
+
 impl fmt::Display for Codon {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}{}{}", self.0[0], self.0[1], self.0[2])
@@ -380,7 +781,7 @@ impl FromStr for Codon {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, std::hash::Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, core::hash::Hash)]
 pub struct CodonAmbiguous(pub [NucleotideAmbiguous; 3]);
 
 impl TryFrom<[u8; 3]> for CodonAmbiguous {
@@ -423,6 +824,11 @@ impl FromStr for CodonAmbiguous {
 }
 
 impl CodonAmbiguous {
+    /// Translate this codon with `table`. See [`TranslationTable::translate_codon_ambiguous`].
+    pub fn translate(self, table: TranslationTable) -> u8 {
+        table.translate_codon_ambiguous(self)
+    }
+
     pub fn possibilities(&self) -> impl Iterator<Item = Codon> + '_ {
         self.0[0].possibilities().iter().flat_map(move |&a| {
             self.0[1].possibilities().iter().flat_map(move |&b| {
@@ -507,4 +913,142 @@ mod tests {
         let amb_codon = CodonAmbiguous::from_str("BAT").unwrap();
         assert!(Codon::try_from(amb_codon).is_err());
     }
+
+    type PackResult<T> = Result<Vec<T>, (usize, String)>;
+
+    fn per_byte_pack<T: NucleotideLike>(ascii: &[u8]) -> PackResult<T> {
+        ascii
+            .iter()
+            .enumerate()
+            .map(|(i, &b)| T::try_from(b).map_err(|e| (i, e.to_string())))
+            .collect()
+    }
+
+    fn bulk_pack<T: NucleotideLike>(ascii: &[u8]) -> PackResult<T> {
+        T::try_pack_ascii(ascii).map_err(|e| (e.position, e.error.to_string()))
+    }
+
+    #[test]
+    fn from_bits_round_trips() {
+        for bits in 0..=u8::MAX {
+            if let Some(n) = Nucleotide::from_bits(bits) {
+                assert_eq!(n.bits(), bits);
+            }
+            if let Some(n) = NucleotideAmbiguous::from_bits(bits) {
+                assert_eq!(n.bits(), bits);
+            }
+        }
+        for n in Nucleotide::ALL {
+            assert_eq!(Nucleotide::from_bits(n.bits()), Some(n));
+        }
+        for n in NucleotideAmbiguous::ALL {
+            assert_eq!(NucleotideAmbiguous::from_bits(n.bits()), Some(n));
+        }
+        assert_eq!((0..=u8::MAX).filter_map(Nucleotide::from_bits).count(), 4);
+        assert_eq!(
+            (0..=u8::MAX)
+                .filter_map(NucleotideAmbiguous::from_bits)
+                .count(),
+            15
+        );
+    }
+
+    #[test]
+    fn contains_and_len_match_possibilities() {
+        for a in NucleotideAmbiguous::ALL {
+            assert_eq!(a.len(), a.possibilities().len());
+            for n in Nucleotide::ALL {
+                assert_eq!(a.contains(n), a.possibilities().contains(&n));
+            }
+        }
+    }
+
+    #[test]
+    fn union_and_intersection_match_possibilities() {
+        for a in NucleotideAmbiguous::ALL {
+            for b in NucleotideAmbiguous::ALL {
+                let union = a.union(b);
+                assert!(union
+                    .possibilities()
+                    .iter()
+                    .all(|n| a.possibilities().contains(n) || b.possibilities().contains(n)));
+                assert!(a
+                    .possibilities()
+                    .iter()
+                    .all(|n| union.possibilities().contains(n)));
+                assert!(b
+                    .possibilities()
+                    .iter()
+                    .all(|n| union.possibilities().contains(n)));
+
+                let shared: Vec<_> = a
+                    .possibilities()
+                    .iter()
+                    .filter(|n| b.possibilities().contains(n))
+                    .collect();
+                match a.intersection(b) {
+                    Some(i) => assert_eq!(i.possibilities().len(), shared.len()),
+                    None => assert!(shared.is_empty()),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn gapped_nucleotides() {
+        use NucleotideGapped::*;
+        assert_eq!(
+            NucleotideGapped::try_pack_ascii(b"a-C.nR").unwrap(),
+            [A, Gap, C, Gap, N, R]
+        );
+        assert!(matches!(
+            NucleotideGapped::try_from(b'U'),
+            Err(TranslationError::BadNucleotide('U'))
+        ));
+        for (bits, n) in NucleotideGapped::ALL.into_iter().enumerate() {
+            assert_eq!(n.bits() as usize, bits);
+            assert_eq!(n.complement().complement(), n);
+            assert_eq!(NucleotideGapped::try_from(n.to_ascii()).unwrap(), n);
+            assert_eq!(n.is_gap(), n == Gap);
+        }
+        for n in NucleotideAmbiguous::ALL {
+            let gapped = NucleotideGapped::from(n);
+            assert_eq!(gapped.to_ascii(), n.to_ascii());
+            assert_eq!(gapped.complement(), NucleotideGapped::from(n.complement()));
+            assert_eq!(gapped.is_ambiguous(), n.is_ambiguous());
+        }
+        assert_eq!(Gap.complement(), Gap);
+        assert!(!Gap.is_ambiguous());
+        assert!(Nucleotide::try_from(Gap).is_err());
+        assert!(Nucleotide::try_from(R).is_err());
+        assert!(NucleotideAmbiguous::try_from(Gap).is_err());
+    }
+
+    quickcheck::quickcheck! {
+        fn bulk_pack_matches_per_byte(ascii: Vec<u8>) -> bool {
+            bulk_pack::<Nucleotide>(&ascii) == per_byte_pack(&ascii)
+                && bulk_pack::<NucleotideAmbiguous>(&ascii) == per_byte_pack(&ascii)
+                && bulk_pack::<NucleotideGapped>(&ascii) == per_byte_pack(&ascii)
+        }
+
+        fn bulk_pack_accepts_valid_ascii(dna: Vec<NucleotideAmbiguous>) -> bool {
+            let ascii: Vec<u8> = dna.iter().map(|n| n.to_ascii()).collect();
+            NucleotideAmbiguous::try_pack_ascii(&ascii).unwrap() == dna
+        }
+
+        #[cfg(feature = "simd")]
+        fn gc_count_simd_matches_base_counts(dna: crate::DnaSequenceStrict) -> bool {
+            let counts = dna.base_counts();
+            let gc = counts.get(&Nucleotide::G).unwrap_or(&0) + counts.get(&Nucleotide::C).unwrap_or(&0);
+            let ascii = dna.to_string();
+            Nucleotide::gc_count_simd(ascii.as_bytes()) == gc
+                && Nucleotide::gc_count_simd(ascii.to_lowercase().as_bytes()) == gc
+        }
+
+        #[cfg(feature = "simd")]
+        fn gc_count_simd_matches_scalar(ascii: Vec<u8>) -> bool {
+            let scalar = ascii.iter().filter(|&&b| matches!(b, b'G' | b'C' | b'g' | b'c')).count();
+            Nucleotide::gc_count_simd(&ascii) == scalar
+        }
+    }
 }
@@ -0,0 +1,176 @@
+// Copyright 2021-2024 SecureDNA Stiftung (SecureDNA Foundation) <licensing@securedna.org>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Compact binary serialization of DNA sequences.
+//!
+//! [`DnaSequence`]'s own serde impls use its string form, which is readable but spends a
+//! whole byte per nucleotide. Wrapping a sequence in [`PackedDna`] instead serializes it
+//! as a tuple of its length and its nucleotides packed into bytes: 2 bits per
+//! [`Nucleotide`] (in the order of [`Nucleotide::ALL`]), or 4 bits per
+//! [`NucleotideAmbiguous`] (its [`NucleotideLike::bits`]). The first nucleotide is stored in
+//! the most significant bits of the first byte, and any unused trailing bits are zero.
+//!
+//! [`Nucleotide`]: crate::Nucleotide
+//! [`Nucleotide::ALL`]: crate::Nucleotide::ALL
+//! [`NucleotideAmbiguous`]: crate::NucleotideAmbiguous
+//!
+//! ```
+//! use quickdna::{packed::PackedDna, DnaSequenceStrict, Nucleotide};
+//!
+//! let dna: DnaSequenceStrict = "GATTACA".parse().unwrap();
+//! let json = serde_json::to_string(&PackedDna(dna.clone())).unwrap();
+//! assert_eq!(json, "[7,[197,32]]");
+//!
+//! let round_trip: PackedDna<Nucleotide> = serde_json::from_str(&json).unwrap();
+//! assert_eq!(round_trip.0, dna);
+//! ```
+
+use std::fmt;
+
+use serde::de::{self, SeqAccess, Visitor};
+use serde::ser::SerializeTuple;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::bitpack::{self, BitPacked, UnpackError};
+use crate::{BaseSequence, DnaSequence, NucleotideLike};
+
+/// Wrapper that serializes a [`DnaSequence`] in packed binary form.
+/// See the [module documentation](self) for the format.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, std::hash::Hash)]
+pub struct PackedDna<T: NucleotideLike>(pub DnaSequence<T>);
+
+impl<T: NucleotideLike> From<DnaSequence<T>> for PackedDna<T> {
+    fn from(value: DnaSequence<T>) -> Self {
+        Self(value)
+    }
+}
+
+impl<T: NucleotideLike> From<PackedDna<T>> for DnaSequence<T> {
+    fn from(value: PackedDna<T>) -> Self {
+        value.0
+    }
+}
+
+impl<T: NucleotideLike + BitPacked> Serialize for PackedDna<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut tuple = serializer.serialize_tuple(2)?;
+        tuple.serialize_element(&(self.0.len() as u64))?;
+        tuple.serialize_element(&Bytes(&bitpack::pack(self.0.as_ref())))?;
+        tuple.end()
+    }
+}
+
+impl<'de, T: NucleotideLike + BitPacked> Deserialize<'de> for PackedDna<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (len, ByteBuf(bytes)) = <(u64, ByteBuf)>::deserialize(deserializer)?;
+        let len = usize::try_from(len).map_err(de::Error::custom)?;
+        let dna = bitpack::unpack(len, &bytes).map_err(|err| match err {
+            UnpackError::Length(actual) => {
+                de::Error::invalid_length(actual, &"enough bytes for the packed nucleotides")
+            }
+            UnpackError::Bits(bits) => de::Error::invalid_value(
+                de::Unexpected::Unsigned(bits.into()),
+                &"packed nucleotide",
+            ),
+        })?;
+        Ok(Self(DnaSequence::new(dna)))
+    }
+}
+
+/// Serializes a byte slice with [`Serializer::serialize_bytes`], rather than as a sequence.
+struct Bytes<'a>(&'a [u8]);
+
+impl Serialize for Bytes<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self.0)
+    }
+}
+
+/// Deserializes bytes from either a byte buffer or a sequence (as in human-readable formats).
+struct ByteBuf(Vec<u8>);
+
+impl<'de> Deserialize<'de> for ByteBuf {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_byte_buf(ByteBufVisitor)
+    }
+}
+
+struct ByteBufVisitor;
+
+impl<'de> Visitor<'de> for ByteBufVisitor {
+    type Value = ByteBuf;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "packed bytes")
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(ByteBuf(v.to_vec()))
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+        Ok(ByteBuf(v))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(byte) = seq.next_element()? {
+            bytes.push(byte);
+        }
+        Ok(ByteBuf(bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use quickcheck::quickcheck;
+
+    use crate::{DnaSequenceAmbiguous, DnaSequenceStrict, Nucleotide, NucleotideAmbiguous};
+
+    #[test]
+    fn test_packed_json() {
+        let dna: DnaSequenceAmbiguous = "ANGT".parse().unwrap();
+        let json = serde_json::to_value(PackedDna(dna.clone())).unwrap();
+        assert_eq!(json, serde_json::json!([4, [0x1f, 0x82]]));
+        let round_trip: PackedDna<NucleotideAmbiguous> = serde_json::from_value(json).unwrap();
+        assert_eq!(round_trip.0, dna);
+
+        let empty: PackedDna<Nucleotide> = serde_json::from_str("[0,[]]").unwrap();
+        assert_eq!(empty.0, DnaSequenceStrict::default());
+    }
+
+    #[test]
+    fn test_packed_rejects_bad_input() {
+        assert!(serde_json::from_str::<PackedDna<Nucleotide>>("[5,[0]]").is_err());
+        assert!(serde_json::from_str::<PackedDna<Nucleotide>>("[1,[0,0]]").is_err());
+        // A zero nibble isn't a nucleotide.
+        assert!(serde_json::from_str::<PackedDna<NucleotideAmbiguous>>("[2,[16]]").is_err());
+    }
+
+    #[test]
+    fn test_packed_bincode_is_compact() {
+        let dna: DnaSequenceStrict = "ACGT".repeat(25).parse().unwrap();
+        let packed = bincode::serialize(&PackedDna(dna.clone())).unwrap();
+        let unpacked = bincode::serialize(&dna).unwrap();
+        assert_eq!(packed.len(), 8 + 8 + 25);
+        assert_eq!(unpacked.len(), 8 + 100);
+    }
+
+    quickcheck! {
+        fn packed_strict_round_trips(dna: DnaSequenceStrict) -> bool {
+            let bytes = bincode::serialize(&PackedDna(dna.clone())).unwrap();
+            let round_trip: PackedDna<Nucleotide> = bincode::deserialize(&bytes).unwrap();
+            let json = serde_json::to_value(&dna).unwrap();
+            round_trip.0 == dna && serde_json::to_value(&round_trip.0).unwrap() == json
+        }
+
+        fn packed_ambiguous_round_trips(dna: DnaSequenceAmbiguous) -> bool {
+            let bytes = bincode::serialize(&PackedDna(dna.clone())).unwrap();
+            let round_trip: PackedDna<NucleotideAmbiguous> = bincode::deserialize(&bytes).unwrap();
+            let json = serde_json::to_value(&dna).unwrap();
+            round_trip.0 == dna && serde_json::to_value(&round_trip.0).unwrap() == json
+        }
+    }
+}
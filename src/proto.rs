@@ -0,0 +1,131 @@
+// Copyright 2021-2024 SecureDNA Stiftung (SecureDNA Foundation) <licensing@securedna.org>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Protocol Buffers messages for DNA sequences, for exchanging them with services in other
+//! languages.
+//!
+//! The messages are defined in `proto/quickdna.proto`, and [`DnaSequence`] is generated from
+//! it by `prost-build` (see `src/proto/quickdna.rs` for how to regenerate it). Nucleotides
+//! are packed the same way as in [`packed`](crate::packed): 2 bits per [`Nucleotide`], or 4
+//! bits per [`NucleotideAmbiguous`].
+//!
+//! ```
+//! use prost::Message;
+//! use quickdna::{proto, DnaSequenceStrict};
+//!
+//! let dna: DnaSequenceStrict = "GATTACA".parse().unwrap();
+//! let bytes = proto::DnaSequence::from(dna.clone()).encode_to_vec();
+//!
+//! let message = proto::DnaSequence::decode(&bytes[..]).unwrap();
+//! assert_eq!(DnaSequenceStrict::try_from(message).unwrap(), dna);
+//! ```
+
+use thiserror::Error;
+
+use crate::bitpack::{self, BitPacked, UnpackError};
+use crate::{BaseSequence, NucleotideLike};
+#[cfg(doc)]
+use crate::{Nucleotide, NucleotideAmbiguous};
+
+include!("proto/quickdna.rs");
+
+/// Returned when a [`DnaSequence`] message doesn't hold a valid sequence of the requested
+/// nucleotide type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum ProtoError {
+    #[error("expected {expected} bits per nucleotide, got {actual}")]
+    BitsPerNucleotide { expected: u32, actual: u32 },
+    #[error("{bytes} packed bytes don't hold {length} nucleotides")]
+    Length { length: u64, bytes: usize },
+    #[error("bad packed nucleotide: {0:#x}")]
+    BadNucleotide(u8),
+}
+
+impl<T: NucleotideLike + BitPacked> From<crate::DnaSequence<T>> for DnaSequence {
+    fn from(value: crate::DnaSequence<T>) -> Self {
+        Self {
+            length: value.len() as u64,
+            packed: bitpack::pack(value.as_ref()),
+            bits_per_nucleotide: T::WIDTH as u32,
+        }
+    }
+}
+
+impl<T: NucleotideLike + BitPacked> TryFrom<DnaSequence> for crate::DnaSequence<T> {
+    type Error = ProtoError;
+
+    fn try_from(value: DnaSequence) -> Result<Self, Self::Error> {
+        if value.bits_per_nucleotide != T::WIDTH as u32 {
+            return Err(ProtoError::BitsPerNucleotide {
+                expected: T::WIDTH as u32,
+                actual: value.bits_per_nucleotide,
+            });
+        }
+        let length_error = ProtoError::Length {
+            length: value.length,
+            bytes: value.packed.len(),
+        };
+        let length = usize::try_from(value.length).map_err(|_| length_error)?;
+        let dna = bitpack::unpack(length, &value.packed).map_err(|err| match err {
+            UnpackError::Length(_) => length_error,
+            UnpackError::Bits(bits) => ProtoError::BadNucleotide(bits),
+        })?;
+        Ok(Self::new(dna))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use prost::Message;
+    use quickcheck::quickcheck;
+
+    use crate::{DnaSequenceAmbiguous, DnaSequenceStrict};
+
+    #[test]
+    fn test_proto_rejects_bad_input() {
+        let dna: DnaSequenceAmbiguous = "ANGT".parse().unwrap();
+        let message = DnaSequence::from(dna.clone());
+        assert_eq!(message.packed, [0x1f, 0x82]);
+        assert_eq!(
+            DnaSequenceStrict::try_from(message.clone()),
+            Err(ProtoError::BitsPerNucleotide {
+                expected: 2,
+                actual: 4
+            })
+        );
+
+        let mut short = message.clone();
+        short.length = 5;
+        assert_eq!(
+            DnaSequenceAmbiguous::try_from(short),
+            Err(ProtoError::Length {
+                length: 5,
+                bytes: 2
+            })
+        );
+
+        // A zero nibble isn't a nucleotide.
+        let mut gap = message;
+        gap.packed[1] = 0x80;
+        assert_eq!(
+            DnaSequenceAmbiguous::try_from(gap),
+            Err(ProtoError::BadNucleotide(0))
+        );
+    }
+
+    quickcheck! {
+        fn proto_strict_round_trips(dna: DnaSequenceStrict) -> bool {
+            let bytes = DnaSequence::from(dna.clone()).encode_to_vec();
+            let message = DnaSequence::decode(&bytes[..]).unwrap();
+            DnaSequenceStrict::try_from(message) == Ok(dna)
+        }
+
+        fn proto_ambiguous_round_trips(dna: DnaSequenceAmbiguous) -> bool {
+            let bytes = DnaSequence::from(dna.clone()).encode_to_vec();
+            let message = DnaSequence::decode(&bytes[..]).unwrap();
+            DnaSequenceAmbiguous::try_from(message) == Ok(dna)
+        }
+    }
+}
@@ -0,0 +1,29 @@
+// Copyright 2021-2024 SecureDNA Stiftung (SecureDNA Foundation) <licensing@securedna.org>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+// Regenerate this file after changing `proto/quickdna.proto` by running prost-build 0.14
+// with its default config (which derives `Eq` and `Hash` for messages that allow them)
+// from the repository root, with `protoc` on the `PATH`:
+//
+//     prost_build::Config::new()
+//         .out_dir("src/proto")
+//         .compile_protos(&["proto/quickdna.proto"], &["proto"])?;
+//
+// and then restore the license header above.
+
+// This file is @generated by prost-build.
+/// A DNA sequence, packed into bytes with the first nucleotide in the most significant bits
+/// of the first byte. Any unused trailing bits are zero.
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct DnaSequence {
+    /// The number of nucleotides.
+    #[prost(uint64, tag = "1")]
+    pub length: u64,
+    /// The packed nucleotides.
+    #[prost(bytes = "vec", tag = "2")]
+    pub packed: ::prost::alloc::vec::Vec<u8>,
+    /// Either 2, for unambiguous nucleotides packed as A = 0, T = 1, C = 2, G = 3,
+    /// or 4, for IUPAC ambiguity codes packed as the union of A = 1, T = 2, C = 4, G = 8.
+    #[prost(uint32, tag = "3")]
+    pub bits_per_nucleotide: u32,
+}
@@ -2,12 +2,15 @@
                                     // Copyright 2021-2024 SecureDNA Stiftung (SecureDNA Foundation) <licensing@securedna.org>
                                     // SPDX-License-Identifier: MIT OR Apache-2.0
 
+use std::collections::HashMap;
+
 use pyo3::{exceptions::PyValueError, prelude::*, types::PyBytes};
 
 use crate::{
-    errors::TranslationError,
+    errors::{Positioned, TranslationError},
     trans_table::{reverse_complement_bytes, TranslationTable},
-    Nucleotide, NucleotideAmbiguous,
+    BaseSequence, DnaSequence, FastaParseSettings, FastaParser, Nucleotide, NucleotideAmbiguous,
+    ProteinSequence,
 };
 
 impl From<TranslationError> for PyErr {
@@ -16,6 +19,12 @@ impl From<TranslationError> for PyErr {
     }
 }
 
+impl From<Positioned<TranslationError>> for PyErr {
+    fn from(err: Positioned<TranslationError>) -> PyErr {
+        PyValueError::new_err(err.to_string())
+    }
+}
+
 #[pyfunction]
 fn _check_table(table: u8) -> PyResult<()> {
     let _ = TranslationTable::try_from(table)?;
@@ -44,10 +53,83 @@ fn _translate(py: Python, table: u8, dna: &PyBytes) -> PyResult<PyObject> {
 #[pyfunction]
 fn _translate_strict(py: Python, table: u8, dna: &PyBytes) -> PyResult<PyObject> {
     let table = TranslationTable::try_from(table)?;
-    let bytes = table.translate_dna_bytes::<Nucleotide>(dna.as_bytes())?;
+    let bytes = table
+        .translate_dna_bytes_positioned::<Nucleotide>(dna.as_bytes())
+        .map_err(|err| {
+            let codon = err.position / 3;
+            PyValueError::new_err(format!("at codon {codon}: {}", err.error))
+        })?;
     Ok(PyBytes::new(py, &bytes).into())
 }
 
+/// Translate a bytestring of DNA nucleotides into up to 3 bytestrings of amino acids, one for
+/// each reading frame on this sense.
+///
+/// The input string is allowed to contain IUPAC ambiguity codes. Fewer than 3 proteins are
+/// returned for sequences shorter than 5 nucleotides.
+///
+/// * `translate_self_frames(b"AAAGGGAAA")` returns `[b"KGK", b"KG", b"RE"]`
+#[pyfunction]
+fn _translate_self_frames(py: Python, table: u8, dna: &PyBytes) -> PyResult<Vec<Py<PyBytes>>> {
+    let table = TranslationTable::try_from(table)?;
+    let dna = DnaSequence::<NucleotideAmbiguous>::try_from(dna.as_bytes())?;
+    Ok(proteins_to_py(py, dna.translate_self_frames(table)))
+}
+
+/// Translate a bytestring of DNA nucleotides into up to 3 bytestrings of amino acids, one for
+/// each reading frame on this sense.
+///
+/// The input string is validated to consist of unambiguous nucleotides (no IUPAC ambiguity codes).
+#[pyfunction]
+fn _translate_self_frames_strict(
+    py: Python,
+    table: u8,
+    dna: &PyBytes,
+) -> PyResult<Vec<Py<PyBytes>>> {
+    let table = TranslationTable::try_from(table)?;
+    let dna = DnaSequence::<Nucleotide>::try_from(dna.as_bytes())?;
+    Ok(proteins_to_py(py, dna.translate_self_frames(table)))
+}
+
+/// Translate a bytestring of DNA nucleotides into up to 6 bytestrings of amino acids, one for
+/// each reading frame on this sense, followed by one for each on the reverse complement.
+///
+/// The input string is allowed to contain IUPAC ambiguity codes. Fewer than 6 proteins are
+/// returned for sequences shorter than 5 nucleotides.
+///
+/// * `translate_all_frames(b"GGGG")` returns `[b"G", b"G", b"P", b"P"]`
+#[pyfunction]
+fn _translate_all_frames(py: Python, table: u8, dna: &PyBytes) -> PyResult<Vec<Py<PyBytes>>> {
+    let table = TranslationTable::try_from(table)?;
+    let dna = DnaSequence::<NucleotideAmbiguous>::try_from(dna.as_bytes())?;
+    Ok(proteins_to_py(py, dna.translate_all_frames(table)))
+}
+
+/// Translate a bytestring of DNA nucleotides into up to 6 bytestrings of amino acids, one for
+/// each reading frame on this sense, followed by one for each on the reverse complement.
+///
+/// The input string is validated to consist of unambiguous nucleotides (no IUPAC ambiguity codes).
+#[pyfunction]
+fn _translate_all_frames_strict(
+    py: Python,
+    table: u8,
+    dna: &PyBytes,
+) -> PyResult<Vec<Py<PyBytes>>> {
+    let table = TranslationTable::try_from(table)?;
+    let dna = DnaSequence::<Nucleotide>::try_from(dna.as_bytes())?;
+    Ok(proteins_to_py(py, dna.translate_all_frames(table)))
+}
+
+fn proteins_to_py(
+    py: Python,
+    proteins: impl IntoIterator<Item = ProteinSequence>,
+) -> Vec<Py<PyBytes>> {
+    proteins
+        .into_iter()
+        .map(|protein| PyBytes::new(py, protein.as_slice()).into())
+        .collect()
+}
+
 /// Get the reverse complement of a bytestring of DNA nucleotides.
 ///
 /// The input string is allowed to contain IUPAC ambiguity codes.
@@ -71,13 +153,79 @@ fn _reverse_complement_strict(py: Python, dna: &PyBytes) -> PyResult<PyObject> {
     Ok(PyBytes::new(py, &bytes).into())
 }
 
+/// Get the fraction of a bytestring of DNA nucleotides that is G or C.
+///
+/// The input string is allowed to contain IUPAC ambiguity codes, which count fractionally by
+/// the share of their possibilities that are G or C.
+///
+/// * `gc_content(b"GATN")` returns `0.375`
+#[pyfunction]
+fn _gc_content(dna: &PyBytes) -> PyResult<f64> {
+    let dna = DnaSequence::<NucleotideAmbiguous>::try_from(dna.as_bytes())?;
+    Ok(dna.gc_content())
+}
+
+/// Count occurrences of each nucleotide in a bytestring of DNA nucleotides.
+///
+/// The input string is allowed to contain IUPAC ambiguity codes, which are counted separately.
+///
+/// * `base_counts(b"GATTACA")` returns `{"A": 3, "C": 1, "G": 1, "T": 2}`
+#[pyfunction]
+fn _base_counts(dna: &PyBytes) -> PyResult<HashMap<char, usize>> {
+    let dna = DnaSequence::<NucleotideAmbiguous>::try_from(dna.as_bytes())?;
+    Ok(dna
+        .base_counts()
+        .into_iter()
+        .map(|(n, count)| (n.into(), count))
+        .collect())
+}
+
+/// A FASTA record as a `(header, contents, line_range)` tuple.
+type PyFastaRecord = (String, Py<PyBytes>, (usize, usize));
+
+/// Parse a bytestring in FASTA format into a list of `(header, contents, line_range)` tuples.
+///
+/// Record contents are returned with line breaks removed, and are not validated.
+/// `line_range` is the 1-indexed, end-exclusive range of lines the record spans.
+///
+/// * `parse_fasta(b">a\nAC\nGT\n", True, False)` returns `[("a", b"ACGT", (1, 4))]`
+#[pyfunction]
+fn _parse_fasta(
+    py: Python,
+    data: &PyBytes,
+    concatenate_headers: bool,
+    allow_preceding_comment: bool,
+) -> PyResult<Vec<PyFastaRecord>> {
+    let settings = FastaParseSettings::new()
+        .concatenate_headers(concatenate_headers)
+        .allow_preceding_comment(allow_preceding_comment);
+    let file = FastaParser::<String>::new(settings)
+        .parse(data.as_bytes())
+        .map_err(|err| PyValueError::new_err(err.to_string()))?;
+    Ok(file
+        .records
+        .into_iter()
+        .map(|record| {
+            let contents = PyBytes::new(py, record.contents.as_bytes()).into();
+            (record.header, contents, record.line_range)
+        })
+        .collect())
+}
+
 #[pymodule]
 fn quickdna(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(_check_table, m)?)?;
     m.add_function(wrap_pyfunction!(_translate, m)?)?;
     m.add_function(wrap_pyfunction!(_translate_strict, m)?)?;
+    m.add_function(wrap_pyfunction!(_translate_self_frames, m)?)?;
+    m.add_function(wrap_pyfunction!(_translate_self_frames_strict, m)?)?;
+    m.add_function(wrap_pyfunction!(_translate_all_frames, m)?)?;
+    m.add_function(wrap_pyfunction!(_translate_all_frames_strict, m)?)?;
     m.add_function(wrap_pyfunction!(_reverse_complement, m)?)?;
     m.add_function(wrap_pyfunction!(_reverse_complement_strict, m)?)?;
+    m.add_function(wrap_pyfunction!(_gc_content, m)?)?;
+    m.add_function(wrap_pyfunction!(_base_counts, m)?)?;
+    m.add_function(wrap_pyfunction!(_parse_fasta, m)?)?;
 
     Ok(())
 }
@@ -3,7 +3,10 @@
 
 use quickcheck::{Arbitrary, Gen};
 
-use crate::{Codon, CodonAmbiguous, DnaSequence, Nucleotide, NucleotideAmbiguous, NucleotideLike};
+use crate::{
+    Codon, CodonAmbiguous, DnaSequence, Nucleotide, NucleotideAmbiguous, NucleotideGapped,
+    NucleotideLike,
+};
 
 impl Arbitrary for Nucleotide {
     fn arbitrary(g: &mut Gen) -> Self {
@@ -35,6 +38,13 @@ impl Arbitrary for NucleotideAmbiguous {
     }
 }
 
+impl Arbitrary for NucleotideGapped {
+    fn arbitrary(g: &mut Gen) -> Self {
+        *g.choose(&Self::ALL)
+            .expect("Gen should be able to choose a NucleotideGapped")
+    }
+}
+
 impl Arbitrary for Codon {
     fn arbitrary(g: &mut Gen) -> Self {
         Self([
@@ -2,21 +2,32 @@
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
 use core::fmt;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt::Write;
 use std::str::FromStr;
 
 use smallvec::SmallVec;
 
-pub use crate::errors::TranslationError;
-pub use crate::nucleotide::{
-    Codon, CodonAmbiguous, Nucleotide, NucleotideAmbiguous, NucleotideLike,
+pub use crate::amino_acid::{AminoAcid, AminoAcidClass};
+use crate::{
+    Codon, Codons, Complement, Extendable, LengthMismatch, Nucleotide, NucleotideAmbiguous,
+    NucleotideGapped, NucleotideIter, NucleotideLike, Positioned, TooManyExpansions,
+    TranslationError, TranslationTable,
 };
-pub use crate::trans_table::TranslationTable;
-use crate::Extendable;
 
-use crate::canonical::Canonical;
+use crate::ambiguity::AmbiguityPolicy;
+use crate::canonical::{Canonical, ForwardCanonical, Remap, PERMUTATIONS};
+#[cfg(any(feature = "md5", feature = "sha1"))]
+use crate::checksum;
+use crate::codon_usage::{synonym_counts, CodonUsageTable, RelativeAdaptednessTable};
+use crate::diff::Edit;
 use crate::expansions::Expansions;
-use crate::trans_table::reverse_complement;
+use crate::isoelectric;
+use crate::iter::{gc_twelfths, reading_frame_count};
+use crate::kmer::Minimizers;
+use crate::mass::{amino_acid_mass, nucleotide_mass, polymer_weight};
+use crate::melting::{melting_temp_nn, melting_temp_wallace, TmParams};
+use crate::trans_table::CodonIdx;
 
 #[cfg(feature = "serde")]
 use std::marker::PhantomData;
@@ -37,6 +48,19 @@ pub trait BaseSequence: std::marker::Sized {
     fn is_empty(&self) -> bool {
         self.len() == 0
     }
+
+    /// Number of windows of the given length in this sequence.
+    ///
+    /// This is the length of the iterator returned by `windows(length)`, computed without
+    /// constructing it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `length` is 0, like `windows(0)`.
+    fn windows_count(&self, length: usize) -> usize {
+        assert!(length > 0, "window length must be nonzero");
+        (self.len() + 1).saturating_sub(length)
+    }
 }
 
 macro_rules! impls {
@@ -92,20 +116,320 @@ impl Extendable for ProteinSequence {
 #[cfg(feature = "serde")]
 serde_utils::impl_stringlike!(ProteinSequence);
 
+/// Which symbols other than the 20 standard amino acids are allowed by
+/// [`ProteinSequence::validate`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ProteinValidation {
+    /// Allow the stop codon symbol `*`. The default value is `false`.
+    allow_stop: bool,
+
+    /// Allow the unknown amino acid symbol `X`. The default value is `false`.
+    allow_unknown: bool,
+
+    /// Allow the ambiguity codes `B` (`D`/`N`), `Z` (`E`/`Q`) and `J` (`I`/`L`).
+    /// The default value is `false`.
+    allow_ambiguous: bool,
+
+    /// Allow selenocysteine, `U`. The default value is `false`.
+    allow_selenocysteine: bool,
+
+    /// Allow pyrrolysine, `O`. The default value is `false`.
+    allow_pyrrolysine: bool,
+
+    /// Allow the gap symbol `-`. The default value is `false`.
+    allow_gap: bool,
+}
+
+// "Builder-lite" pattern: https://matklad.github.io/2022/05/29/builder-lite.html
+impl ProteinValidation {
+    /// Initializes settings to their defaults: only the 20 standard amino acids are allowed.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allows the whole extended IUPAC amino acid alphabet: the 20 standard amino acids plus
+    /// `*`, `X`, `B`, `Z`, `J`, `U`, `O` and `-`.
+    pub fn iupac() -> Self {
+        Self {
+            allow_stop: true,
+            allow_unknown: true,
+            allow_ambiguous: true,
+            allow_selenocysteine: true,
+            allow_pyrrolysine: true,
+            allow_gap: true,
+        }
+    }
+
+    /// Changes the setting for [`Self::allow_stop`]
+    pub fn allow_stop(mut self, allow_stop: bool) -> Self {
+        self.allow_stop = allow_stop;
+        self
+    }
+
+    /// Changes the setting for [`Self::allow_unknown`]
+    pub fn allow_unknown(mut self, allow_unknown: bool) -> Self {
+        self.allow_unknown = allow_unknown;
+        self
+    }
+
+    /// Changes the setting for [`Self::allow_ambiguous`]
+    pub fn allow_ambiguous(mut self, allow_ambiguous: bool) -> Self {
+        self.allow_ambiguous = allow_ambiguous;
+        self
+    }
+
+    /// Changes the setting for [`Self::allow_selenocysteine`]
+    pub fn allow_selenocysteine(mut self, allow_selenocysteine: bool) -> Self {
+        self.allow_selenocysteine = allow_selenocysteine;
+        self
+    }
+
+    /// Changes the setting for [`Self::allow_pyrrolysine`]
+    pub fn allow_pyrrolysine(mut self, allow_pyrrolysine: bool) -> Self {
+        self.allow_pyrrolysine = allow_pyrrolysine;
+        self
+    }
+
+    /// Changes the setting for [`Self::allow_gap`]
+    pub fn allow_gap(mut self, allow_gap: bool) -> Self {
+        self.allow_gap = allow_gap;
+        self
+    }
+
+    fn allows(&self, aa: u8) -> bool {
+        match aa {
+            b'*' => self.allow_stop,
+            b'X' => self.allow_unknown,
+            b'B' | b'Z' | b'J' => self.allow_ambiguous,
+            b'U' => self.allow_selenocysteine,
+            b'O' => self.allow_pyrrolysine,
+            b'-' => self.allow_gap,
+            _ => AminoAcid::try_from(aa).is_ok(),
+        }
+    }
+}
+
 impl ProteinSequence {
     fn new_unchecked(amino_acids: Vec<u8>) -> Self {
         Self { amino_acids }
     }
 
-    pub fn windows(&self, length: usize) -> impl Iterator<Item = Self> + '_ {
-        self.amino_acids
-            .windows(length)
-            .map(|w| Self::new_unchecked(w.to_vec()))
+    /// Construct an empty ProteinSequence with room for at least `capacity` residues.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::new_unchecked(Vec::with_capacity(capacity))
+    }
+
+    /// Returns an iterator over all overlapping windows of the given length.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `length` is 0.
+    pub fn windows(&self, length: usize) -> ProteinWindows<'_> {
+        ProteinWindows(self.amino_acids.windows(length))
     }
 
     pub fn push(&mut self, aa: u8) {
         self.amino_acids.push(aa);
     }
+
+    /// Reserve room for at least `additional` more residues.
+    pub fn reserve(&mut self, additional: usize) {
+        self.amino_acids.reserve(additional);
+    }
+
+    /// Consume this protein, returning its residues as ASCII bytes without copying them.
+    ///
+    /// ```
+    /// use quickdna::ProteinSequence;
+    ///
+    /// let protein: ProteinSequence = "mkv*".parse().unwrap();
+    /// assert_eq!(protein.into_bytes(), b"MKV*");
+    /// ```
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.amino_acids
+    }
+
+    /// Returns a raw pointer to the residues' ASCII bytes, for passing this protein over FFI
+    /// along with its [`len`](BaseSequence::len).
+    ///
+    /// The pointer is only valid while this protein is alive and unmodified.
+    pub fn as_ptr(&self) -> *const u8 {
+        self.amino_acids.as_ptr()
+    }
+
+    /// Returns an iterator over the indices at which the residue `aa` occurs, ignoring case.
+    ///
+    /// ```
+    /// use quickdna::ProteinSequence;
+    ///
+    /// let protein: ProteinSequence = "MK*V*".parse().unwrap();
+    /// assert!(protein.positions_of(b'*').eq([2, 4]));
+    /// assert!(protein.positions_of(b'k').eq([1]));
+    /// ```
+    pub fn positions_of(&self, aa: u8) -> impl Iterator<Item = usize> + '_ {
+        let aa = aa.to_ascii_uppercase();
+        positions(&self.amino_acids, move |&other| other == aa)
+    }
+
+    /// Returns an iterator over the residues of this protein as [`AminoAcid`]s, yielding
+    /// an error for each residue that isn't one of the 20 standard amino acids.
+    pub fn amino_acids(&self) -> impl Iterator<Item = Result<AminoAcid, TranslationError>> + '_ {
+        self.amino_acids.iter().map(|&aa| AminoAcid::try_from(aa))
+    }
+
+    /// Check that every residue is allowed by `validation`.
+    pub fn validate(&self, validation: ProteinValidation) -> Result<(), TranslationError> {
+        match self.amino_acids.iter().find(|&&aa| !validation.allows(aa)) {
+            Some(&aa) => Err(TranslationError::BadAminoAcid(aa.into())),
+            None => Ok(()),
+        }
+    }
+
+    /// Check that every residue is one of the 20 standard amino acids.
+    ///
+    /// ```
+    /// use quickdna::ProteinSequence;
+    ///
+    /// let protein: ProteinSequence = "MKV".parse().unwrap();
+    /// assert!(protein.validate_standard().is_ok());
+    ///
+    /// let protein: ProteinSequence = "MKV*".parse().unwrap();
+    /// assert!(protein.validate_standard().is_err());
+    /// ```
+    pub fn validate_standard(&self) -> Result<(), TranslationError> {
+        self.validate(ProteinValidation::new())
+    }
+
+    /// Parse a protein sequence, rejecting any residue not allowed by `validation`.
+    ///
+    /// Unlike [`FromStr`], which accepts any ASCII, this only accepts the 20 standard amino
+    /// acids (in either case), plus whatever extra symbols `validation` allows.
+    ///
+    /// ```
+    /// use quickdna::{ProteinSequence, ProteinValidation};
+    ///
+    /// assert!(ProteinSequence::from_str_strict("mkv*", ProteinValidation::new()).is_err());
+    ///
+    /// let with_stop = ProteinValidation::new().allow_stop(true);
+    /// let protein = ProteinSequence::from_str_strict("mkv*", with_stop).unwrap();
+    /// assert_eq!(protein.to_string(), "MKV*");
+    /// ```
+    pub fn from_str_strict(
+        s: &str,
+        validation: ProteinValidation,
+    ) -> Result<Self, TranslationError> {
+        let protein: Self = s.parse()?;
+        protein.validate(validation)?;
+        Ok(protein)
+    }
+
+    /// Parse a protein sequence in the extended IUPAC amino acid alphabet (in either case),
+    /// as found in protein FASTA files. See [`ProteinValidation::iupac`].
+    ///
+    /// Returns [`TranslationError::BadAminoAcid`] for the first residue outside it.
+    ///
+    /// ```
+    /// use quickdna::{ProteinSequence, TranslationError};
+    ///
+    /// let protein = ProteinSequence::from_str_iupac("MKU*OX-bzj").unwrap();
+    /// assert_eq!(protein.to_string(), "MKU*OX-BZJ");
+    ///
+    /// let err = ProteinSequence::from_str_iupac("MK.V").unwrap_err();
+    /// assert!(matches!(err, TranslationError::BadAminoAcid('.')));
+    /// ```
+    pub fn from_str_iupac(s: &str) -> Result<Self, TranslationError> {
+        Self::from_str_strict(s, ProteinValidation::iupac())
+    }
+
+    /// Back-translate this protein into DNA, choosing for each residue the synonymous codon
+    /// in `table` with the highest weight in `usage`. Ties are broken in favor of the first
+    /// codon in the order of [`Nucleotide::ALL`].
+    ///
+    /// Returns an error for any residue that no codon in `table` translates to, such as `X`.
+    ///
+    /// ```
+    /// use quickdna::{codon_usage::CodonUsageTable, DnaSequenceStrict, ProteinSequence, TranslationTable};
+    ///
+    /// let reference: DnaSequenceStrict = "CTGCTGTTAAAGTGA".parse().unwrap();
+    /// let usage = CodonUsageTable::from_dna(&reference);
+    ///
+    /// let protein: ProteinSequence = "MLK*".parse().unwrap();
+    /// let dna = protein.optimize_codons(&usage, TranslationTable::Ncbi1).unwrap();
+    /// assert_eq!(dna.to_string(), "ATGCTGAAGTGA");
+    /// assert_eq!(dna.translate(TranslationTable::Ncbi1), protein);
+    /// ```
+    pub fn optimize_codons(
+        &self,
+        usage: &CodonUsageTable,
+        table: TranslationTable,
+    ) -> Result<DnaSequenceStrict, TranslationError> {
+        let best = usage.best_codons(table);
+        let mut dna = Vec::with_capacity(3 * self.amino_acids.len());
+        for &aa in &self.amino_acids {
+            let codon = best[aa as usize].ok_or(TranslationError::BadAminoAcid(aa.into()))?;
+            dna.extend(codon.0);
+        }
+        Ok(DnaSequenceStrict::new(dna))
+    }
+
+    /// Average molecular weight of this protein, in daltons.
+    ///
+    /// Stop codons (`*`) and gaps (`-`) are skipped. Returns NaN if the sequence contains
+    /// any other residue without a known mass; see [`amino_acid_mass`].
+    ///
+    /// ```
+    /// use quickdna::{mass, ProteinSequence};
+    ///
+    /// let glycine = mass::amino_acid_mass(b'G').unwrap();
+    /// let protein: ProteinSequence = "GG*".parse().unwrap();
+    /// assert_eq!(protein.molecular_weight(), 2.0 * glycine - mass::WATER);
+    /// ```
+    pub fn molecular_weight(&self) -> f64 {
+        polymer_weight(
+            self.amino_acids
+                .iter()
+                .filter(|&&aa| aa != b'*' && aa != b'-')
+                .map(|&aa| amino_acid_mass(aa).unwrap_or(f64::NAN)),
+        )
+    }
+
+    /// Returns how many times each residue occurs in this protein.
+    ///
+    /// Residues that don't occur are omitted.
+    ///
+    /// ```
+    /// use quickdna::ProteinSequence;
+    ///
+    /// let protein: ProteinSequence = "MKKV*".parse().unwrap();
+    /// let composition = protein.composition();
+    /// assert_eq!(composition[&b'K'], 2);
+    /// assert_eq!(composition.get(&b'*'), Some(&1));
+    /// assert_eq!(composition.len(), 4);
+    /// ```
+    pub fn composition(&self) -> BTreeMap<u8, usize> {
+        let mut counts = BTreeMap::new();
+        for &aa in &self.amino_acids {
+            *counts.entry(aa).or_default() += 1;
+        }
+        counts
+    }
+
+    /// Estimated isoelectric point (pI) of this protein: the pH at which it has no net
+    /// charge. See [`isoelectric::isoelectric_point`].
+    ///
+    /// Residues without an ionizable side chain, including `X` and `*`, don't affect it.
+    ///
+    /// ```
+    /// use quickdna::ProteinSequence;
+    ///
+    /// let basic: ProteinSequence = "MKRK".parse().unwrap();
+    /// let acidic: ProteinSequence = "MDED".parse().unwrap();
+    /// assert!(basic.isoelectric_point() > 7.0);
+    /// assert!(acidic.isoelectric_point() < 7.0);
+    /// ```
+    pub fn isoelectric_point(&self) -> f64 {
+        isoelectric::isoelectric_point(&self.amino_acids)
+    }
 }
 
 impl BaseSequence for ProteinSequence {
@@ -122,6 +446,95 @@ impl BaseSequence for ProteinSequence {
 
 impls!(ProteinSequence);
 
+/// Iterator over overlapping windows of a [`ProteinSequence`].
+///
+/// This `struct` is created by [`ProteinSequence::windows`].
+#[derive(Clone, Debug)]
+pub struct ProteinWindows<'a>(std::slice::Windows<'a, u8>);
+
+impl ProteinWindows<'_> {
+    /// Returns the window `index` positions ahead, without advancing the iterator.
+    pub fn get(&self, index: usize) -> Option<ProteinSequence> {
+        self.0
+            .clone()
+            .nth(index)
+            .map(|w| ProteinSequence::new_unchecked(w.to_vec()))
+    }
+}
+
+impl Iterator for ProteinWindows<'_> {
+    type Item = ProteinSequence;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0
+            .next()
+            .map(|w| ProteinSequence::new_unchecked(w.to_vec()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.0
+            .nth(n)
+            .map(|w| ProteinSequence::new_unchecked(w.to_vec()))
+    }
+}
+
+impl DoubleEndedIterator for ProteinWindows<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0
+            .next_back()
+            .map(|w| ProteinSequence::new_unchecked(w.to_vec()))
+    }
+}
+
+impl ExactSizeIterator for ProteinWindows<'_> {}
+
+/// A protein made up of only the 20 standard amino acids.
+///
+/// This is a type-safe alternative to [`ProteinSequence`], convenient for pattern matching.
+///
+/// ```
+/// use quickdna::{AminoAcid, ProteinSequence, TypedProtein};
+///
+/// let protein: ProteinSequence = "MKV".parse().unwrap();
+/// let typed = TypedProtein::try_from(&protein).unwrap();
+/// assert_eq!(typed.0, [AminoAcid::M, AminoAcid::K, AminoAcid::V]);
+/// assert_eq!(ProteinSequence::from(typed), protein);
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, std::hash::Hash)]
+pub struct TypedProtein(pub Vec<AminoAcid>);
+
+impl From<TypedProtein> for ProteinSequence {
+    fn from(value: TypedProtein) -> Self {
+        Self::new_unchecked(value.0.into_iter().map(u8::from).collect())
+    }
+}
+
+impl TryFrom<&ProteinSequence> for TypedProtein {
+    type Error = TranslationError;
+
+    fn try_from(value: &ProteinSequence) -> Result<Self, Self::Error> {
+        value.amino_acids().collect::<Result<_, _>>().map(Self)
+    }
+}
+
+impl TryFrom<ProteinSequence> for TypedProtein {
+    type Error = TranslationError;
+
+    fn try_from(value: ProteinSequence) -> Result<Self, Self::Error> {
+        Self::try_from(&value)
+    }
+}
+
+impl fmt::Display for TypedProtein {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|aa| write!(f, "{aa}"))
+    }
+}
+
 impl fmt::Display for ProteinSequence {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let str = String::from_utf8_lossy(&self.amino_acids);
@@ -160,8 +573,98 @@ impl FromStr for ProteinSequence {
     }
 }
 
+impl std::ops::Deref for ProteinSequence {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        &self.amino_acids
+    }
+}
+
+impl AsRef<[u8]> for ProteinSequence {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+impl<'a> IntoIterator for &'a ProteinSequence {
+    type Item = u8;
+    type IntoIter = std::iter::Copied<std::slice::Iter<'a, u8>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.amino_acids.iter().copied()
+    }
+}
+
+/// How whitespace is handled when parsing a [`DnaSequence`] with
+/// [`DnaSequence::from_bytes_with`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, std::hash::Hash)]
+pub enum WhitespacePolicy {
+    /// Skip all ASCII whitespace, including line breaks.
+    Skip,
+    /// Skip spaces and tabs only. This is how [`DnaSequence`]'s [`FromStr`] impl parses.
+    #[default]
+    SkipSpacesTabs,
+    /// Treat any whitespace as an invalid nucleotide.
+    Reject,
+}
+
+impl WhitespacePolicy {
+    /// Whether `byte` is skipped under this policy.
+    pub fn skips(self, byte: u8) -> bool {
+        match self {
+            Self::Skip => byte.is_ascii_whitespace(),
+            Self::SkipSpacesTabs => byte == b' ' || byte == b'\t',
+            Self::Reject => false,
+        }
+    }
+}
+
+/// How lowercase nucleotides are handled when parsing a [`DnaSequence`] with
+/// [`DnaSequence::from_bytes_with_case`], or a FASTA file with
+/// [`FastaParseSettings::case_policy`](crate::FastaParseSettings::case_policy).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, std::hash::Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum CasePolicy {
+    /// Accept lowercase nucleotides as if they were uppercase. This is how
+    /// [`DnaSequence`]'s [`FromStr`] impl parses.
+    #[default]
+    AllowLowercase,
+    /// Treat any lowercase byte as an error.
+    RejectLowercase,
+    /// Accept lowercase nucleotides, keeping their case where the parsed type can: a
+    /// [`MaskedDnaSequence`](crate::masked::MaskedDnaSequence) masks them, while a
+    /// [`DnaSequence`] (which only holds uppercase nucleotides) parses them like
+    /// [`Self::AllowLowercase`].
+    PreserveCase,
+}
+
+impl CasePolicy {
+    /// Whether `byte` is rejected under this policy.
+    pub fn rejects(self, byte: u8) -> bool {
+        self == Self::RejectLowercase && byte.is_ascii_lowercase()
+    }
+
+    /// Check `byte` at `position` against this policy.
+    pub(crate) fn check(
+        self,
+        position: usize,
+        byte: u8,
+    ) -> Result<(), Positioned<TranslationError>> {
+        if self.rejects(byte) {
+            Err(Positioned {
+                position,
+                error: TranslationError::UnexpectedLowercase(byte.into()),
+            })
+        } else {
+            Ok(())
+        }
+    }
+}
+
 pub type DnaSequenceStrict = DnaSequence<Nucleotide>;
 pub type DnaSequenceAmbiguous = DnaSequence<NucleotideAmbiguous>;
+pub type DnaSequenceGapped = DnaSequence<NucleotideGapped>;
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, std::hash::Hash)]
 pub struct DnaSequence<T: NucleotideLike> {
@@ -207,10 +710,74 @@ impl<T: NucleotideLike> serde::Serialize for DnaSequence<T> {
 
 impl<T: NucleotideLike> DnaSequence<T> {
     /// Construct a new DnaSequence from a Vec of nucleotides
+    ///
+    /// To parse trusted ASCII quickly, pack it with [`NucleotideLike::try_pack_ascii`], which
+    /// validates it with a lookup table, and pass the result here.
     pub fn new(dna: Vec<T>) -> Self {
         Self { dna }
     }
 
+    /// Construct an empty DnaSequence with room for at least `capacity` nucleotides.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::new(Vec::with_capacity(capacity))
+    }
+
+    /// Parse a DNA sequence from ASCII bytes, handling whitespace according to `whitespace`.
+    ///
+    /// On error, the position is the byte offset of the offending byte in `bytes`.
+    ///
+    /// ```
+    /// use quickdna::{DnaSequenceStrict, WhitespacePolicy};
+    ///
+    /// let bytes = b"ACG\nT A";
+    /// let dna = DnaSequenceStrict::from_bytes_with(bytes, WhitespacePolicy::Skip).unwrap();
+    /// assert_eq!(dna.to_string(), "ACGTA");
+    ///
+    /// let err = DnaSequenceStrict::from_bytes_with(bytes, WhitespacePolicy::SkipSpacesTabs);
+    /// assert_eq!(err.unwrap_err().position, 3);
+    /// let err = DnaSequenceStrict::from_bytes_with(b"T A", WhitespacePolicy::Reject);
+    /// assert_eq!(err.unwrap_err().position, 1);
+    /// ```
+    pub fn from_bytes_with(
+        bytes: &[u8],
+        whitespace: WhitespacePolicy,
+    ) -> Result<Self, Positioned<TranslationError>> {
+        Self::from_bytes_with_case(bytes, whitespace, CasePolicy::AllowLowercase)
+    }
+
+    /// Parse a DNA sequence from ASCII bytes, handling whitespace according to `whitespace`
+    /// and lowercase nucleotides according to `case`.
+    ///
+    /// On error, the position is the byte offset of the offending byte in `bytes`.
+    ///
+    /// ```
+    /// use quickdna::{CasePolicy, DnaSequenceStrict, TranslationError, WhitespacePolicy};
+    ///
+    /// let whitespace = WhitespacePolicy::default();
+    /// let dna = DnaSequenceStrict::from_bytes_with_case(b"ACgt", whitespace, CasePolicy::AllowLowercase);
+    /// assert_eq!(dna.unwrap().to_string(), "ACGT");
+    ///
+    /// let err = DnaSequenceStrict::from_bytes_with_case(b"ACgt", whitespace, CasePolicy::RejectLowercase)
+    ///     .unwrap_err();
+    /// assert_eq!(err.position, 2);
+    /// assert!(matches!(err.error, TranslationError::UnexpectedLowercase('g')));
+    /// ```
+    pub fn from_bytes_with_case(
+        bytes: &[u8],
+        whitespace: WhitespacePolicy,
+        case: CasePolicy,
+    ) -> Result<Self, Positioned<TranslationError>> {
+        let mut vec = Vec::with_capacity(bytes.len());
+        for (position, &b) in bytes.iter().enumerate() {
+            if !whitespace.skips(b) {
+                case.check(position, b)?;
+                let nuc = T::try_from(b).map_err(|error| Positioned { position, error })?;
+                vec.push(nuc);
+            }
+        }
+        Ok(Self::new(vec))
+    }
+
     /// Translate this DNA sequence into a protein sequence, using the specified
     /// translation table.
     pub fn translate(&self, table: TranslationTable) -> ProteinSequence {
@@ -218,6 +785,64 @@ impl<T: NucleotideLike> DnaSequence<T> {
         ProteinSequence::new_unchecked(amino_acids)
     }
 
+    /// Translate this DNA sequence in its first reading frame, split at every stop codon,
+    /// returning the peptides between stops (without their `*`).
+    ///
+    /// Unlike [`Self::translate`], which keeps each stop as `*`, this is useful when a long
+    /// sequence holds several ORFs in the same frame. Empty peptides, e.g. between
+    /// consecutive stops, are dropped. The peptide before the first stop and the one after
+    /// the last are included, even though they may be partial ORFs. Ambiguous codons that
+    /// may or may not be stops translate to `X`, and don't split peptides.
+    ///
+    /// ```
+    /// use quickdna::{DnaSequenceStrict, TranslationTable};
+    ///
+    /// let dna: DnaSequenceStrict = "ATGAAATAGTGAGGGCCCTAAATGC".parse().unwrap();
+    /// assert_eq!(dna.translate(TranslationTable::Ncbi1).to_string(), "MK**GP*M");
+    /// let peptides = dna.translate_orf(TranslationTable::Ncbi1);
+    /// let peptides: Vec<String> = peptides.iter().map(|p| p.to_string()).collect();
+    /// assert_eq!(peptides, ["MK", "GP", "M"]);
+    /// ```
+    pub fn translate_orf(&self, table: TranslationTable) -> Vec<ProteinSequence> {
+        self.translate(table)
+            .amino_acids
+            .split(|&aa| aa == b'*')
+            .filter(|peptide| !peptide.is_empty())
+            .map(|peptide| ProteinSequence::new_unchecked(peptide.to_vec()))
+            .collect()
+    }
+
+    /// Translate this DNA sequence into a protein sequence, using multiple threads.
+    ///
+    /// The result is identical to [`Self::translate`], which this falls back to for sequences
+    /// too short to benefit from parallelism.
+    #[cfg(feature = "rayon")]
+    pub fn par_translate(&self, table: TranslationTable) -> ProteinSequence
+    where
+        T: Send + Sync,
+    {
+        use rayon::prelude::*;
+
+        /// Number of codons translated by each task.
+        const CODONS_PER_TASK: usize = 1 << 15;
+
+        if self.dna.len() < 2 * 3 * CODONS_PER_TASK {
+            return self.translate(table);
+        }
+
+        let translate_codon = table.to_fn::<T, [T; 3]>();
+        let mut amino_acids = vec![0; self.dna.len() / 3];
+        amino_acids
+            .par_chunks_mut(CODONS_PER_TASK)
+            .zip(self.dna.par_chunks(3 * CODONS_PER_TASK))
+            .for_each(|(amino_acids, dna)| {
+                for (aa, codon) in amino_acids.iter_mut().zip(dna.chunks_exact(3)) {
+                    *aa = translate_codon([codon[0], codon[1], codon[2]]);
+                }
+            });
+        ProteinSequence::new_unchecked(amino_acids)
+    }
+
     /// Translate this DNA sequence into up to 3 protein sequences, one for each possible
     /// reading frame on this sense.
     ///
@@ -226,31 +851,11 @@ impl<T: NucleotideLike> DnaSequence<T> {
     /// and a sequence of length 2 has none.
     pub fn translate_self_frames(&self, table: TranslationTable) -> SmallVec<[ProteinSequence; 3]> {
         let mut result = SmallVec::with_capacity(3);
-
-        // avoid empty translations & multiple branches
-        if self.len() >= 5 {
-            result.push(ProteinSequence {
-                amino_acids: table.translate_dna(&self.dna[0..]),
-            });
-            result.push(ProteinSequence {
-                amino_acids: table.translate_dna(&self.dna[1..]),
-            });
-            result.push(ProteinSequence {
-                amino_acids: table.translate_dna(&self.dna[2..]),
-            });
-        } else if self.len() == 4 {
+        for offset in 0..reading_frame_count(self.len()) {
             result.push(ProteinSequence {
-                amino_acids: table.translate_dna(&self.dna[0..]),
-            });
-            result.push(ProteinSequence {
-                amino_acids: table.translate_dna(&self.dna[1..]),
-            });
-        } else if self.len() == 3 {
-            result.push(ProteinSequence {
-                amino_acids: table.translate_dna(&self.dna[0..]),
+                amino_acids: table.translate_dna(&self.dna[offset..]),
             });
         }
-
         result
     }
 
@@ -264,39 +869,609 @@ impl<T: NucleotideLike> DnaSequence<T> {
         let mut result = SmallVec::with_capacity(6);
 
         result.append(&mut self.translate_self_frames(table));
-        result.append(&mut self.reverse_complement().translate_self_frames(table));
+        for offset in 0..3 {
+            if self.len() >= offset + 3 {
+                let rc = self.reverse_complement_iter().skip(offset);
+                result.push(ProteinSequence::new_unchecked(
+                    rc.translate(table).collect(),
+                ));
+            }
+        }
 
         result
     }
 
     /// Takes the reverse complement of a DNA sequence.
     pub fn reverse_complement(&self) -> Self {
-        Self::new(reverse_complement(&self.dna))
+        Self::new(self.reverse_complement_iter().collect())
     }
 
-    pub fn windows(&self, length: usize) -> impl Iterator<Item = Self> + '_ {
-        self.dna.windows(length).map(|w| Self::new(w.to_vec()))
+    /// Returns an iterator over the reverse complement of this sequence, without
+    /// allocating a new sequence.
+    ///
+    /// ```
+    /// use quickdna::{DnaSequenceStrict, NucleotideIter, TranslationTable};
+    ///
+    /// let dna: DnaSequenceStrict = "ATGGCCTAA".parse().unwrap();
+    /// assert!(dna.reverse_complement_iter().eq(dna.reverse_complement().iter()));
+    ///
+    /// let rc_protein: Vec<u8> = dna
+    ///     .reverse_complement_iter()
+    ///     .translate(TranslationTable::Ncbi1)
+    ///     .collect();
+    /// assert_eq!(rc_protein, b"LGH");
+    /// ```
+    pub fn reverse_complement_iter(
+        &self,
+    ) -> Complement<std::iter::Rev<std::iter::Copied<std::slice::Iter<'_, T>>>> {
+        self.dna.iter().copied().reverse_complement()
     }
 
-    pub fn push(&mut self, n: T) {
-        self.dna.push(n);
+    /// Returns an iterator over all overlapping windows of the given length.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `length` is 0.
+    pub fn windows(&self, length: usize) -> DnaWindows<'_, T> {
+        DnaWindows(self.dna.windows(length))
     }
-}
-
-impl<T: NucleotideLike> BaseSequence for DnaSequence<T> {
-    type Item = T;
 
-    fn as_slice(&self) -> &[Self::Item] {
-        &self.dna
+    /// Like [`Self::windows`], but also yields the index of each window's first nucleotide.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `length` is 0.
+    ///
+    /// ```
+    /// use quickdna::DnaSequenceStrict;
+    ///
+    /// let dna: DnaSequenceStrict = "GATTACA".parse().unwrap();
+    /// let (start, window) = dna.indexed_windows(3).last().unwrap();
+    /// assert_eq!((start, window.to_string().as_str()), (4, "ACA"));
+    /// ```
+    pub fn indexed_windows(&self, length: usize) -> std::iter::Enumerate<DnaWindows<'_, T>> {
+        self.windows(length).enumerate()
     }
 
-    fn as_mut_slice(&mut self) -> &mut [Self::Item] {
-        &mut self.dna
-    }
+    /// Returns an iterator over all overlapping windows of the given length, borrowed from
+    /// this sequence rather than copied, along with the index of each window's first
+    /// nucleotide.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `length` is 0.
+    ///
+    /// ```
+    /// use quickdna::{DnaSequenceStrict, Nucleotide::*};
+    ///
+    /// let dna: DnaSequenceStrict = "GATTACA".parse().unwrap();
+    /// let mut windows = dna.window_slices(3);
+    /// assert_eq!(windows.next(), Some((0, &[G, A, T][..])));
+    /// assert_eq!(windows.nth(2), Some((3, &[T, A, C][..])));
+    /// ```
+    pub fn window_slices(&self, length: usize) -> std::iter::Enumerate<std::slice::Windows<'_, T>> {
+        self.dna.windows(length).enumerate()
+    }
+
+    /// Returns the `codon_len` codons starting at codon `codon_start` of the first reading
+    /// frame, i.e. nucleotides `3 * codon_start` up to `3 * (codon_start + codon_len)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slice extends past the end of this sequence.
+    ///
+    /// ```
+    /// use quickdna::DnaSequenceStrict;
+    ///
+    /// let dna: DnaSequenceStrict = "ATGGCCTAAGG".parse().unwrap();
+    /// assert_eq!(dna.slice_codons(1, 2).to_string(), "GCCTAA");
+    /// ```
+    pub fn slice_codons(&self, codon_start: usize, codon_len: usize) -> Self {
+        Self::new(self.dna[3 * codon_start..3 * (codon_start + codon_len)].to_vec())
+    }
+
+    /// Returns this sequence without the up to 2 trailing nucleotides that don't form a
+    /// whole codon, like [`NucleotideIter::trimmed_to_codon`].
+    ///
+    /// ```
+    /// use quickdna::DnaSequenceStrict;
+    ///
+    /// let dna: DnaSequenceStrict = "ATGGCCTAAGG".parse().unwrap();
+    /// assert_eq!(dna.trim_to_codon().to_string(), "ATGGCCTAA");
+    /// ```
+    pub fn trim_to_codon(&self) -> Self {
+        Self::new(self.dna[..self.dna.len() - self.dna.len() % 3].to_vec())
+    }
+
+    /// Returns an iterator over the codons of the first reading frame of this sequence.
+    ///
+    /// This is the same as [`NucleotideIter::codons`] on the nucleotides of this sequence;
+    /// any excess nucleotides at the end are discarded.
+    ///
+    /// ```
+    /// use quickdna::{DnaSequenceStrict, Nucleotide};
+    ///
+    /// use Nucleotide::*;
+    /// let dna: DnaSequenceStrict = "GATTACA".parse().unwrap();
+    /// assert!(dna.codons().eq([[G, A, T].into(), [T, A, C].into()]));
+    /// ```
+    pub fn codons(&self) -> Codons<std::iter::Copied<std::slice::Iter<'_, T>>> {
+        self.dna.iter().copied().codons()
+    }
+
+    /// Returns up to 3 non-empty codon iterators, one for each reading frame on this sense.
+    ///
+    /// This is the same as [`NucleotideIter::self_reading_frames`] on the nucleotides of this
+    /// sequence, and gives the codons translated by [`Self::translate_self_frames`].
+    ///
+    /// ```
+    /// use quickdna::DnaSequenceStrict;
+    ///
+    /// let dna: DnaSequenceStrict = "GATTACA".parse().unwrap();
+    /// let frames = dna.reading_frames();
+    /// assert_eq!(frames.len(), 3);
+    /// assert_eq!(frames[2].len(), 1);
+    /// ```
+    pub fn reading_frames(
+        &self,
+    ) -> SmallVec<[Codons<std::iter::Copied<std::slice::Iter<'_, T>>>; 3]> {
+        self.dna.iter().copied().self_reading_frames()
+    }
+
+    pub fn push(&mut self, n: T) {
+        self.dna.push(n);
+    }
+
+    /// Reserve room for at least `additional` more nucleotides.
+    pub fn reserve(&mut self, additional: usize) {
+        self.dna.reserve(additional);
+    }
+
+    /// Consume this sequence, returning its nucleotides without copying them.
+    ///
+    /// ```
+    /// use quickdna::{DnaSequenceStrict, Nucleotide};
+    ///
+    /// let dna: DnaSequenceStrict = "GAT".parse().unwrap();
+    /// assert_eq!(dna.into_vec(), [Nucleotide::G, Nucleotide::A, Nucleotide::T]);
+    /// ```
+    pub fn into_vec(self) -> Vec<T> {
+        self.dna
+    }
+
+    /// Returns a raw pointer to the nucleotides, for passing this sequence over FFI along
+    /// with its [`len`](BaseSequence::len).
+    ///
+    /// The pointer is only valid while this sequence is alive and unmodified.
+    pub fn as_ptr(&self) -> *const T {
+        self.dna.as_ptr()
+    }
+
+    /// Returns an iterator over the indices at which the nucleotide `nuc` occurs.
+    ///
+    /// For ambiguous sequences this only matches the exact same ambiguity code; see
+    /// [`Self::positions_matching`] to match overlapping codes.
+    ///
+    /// ```
+    /// use quickdna::{DnaSequenceAmbiguous, NucleotideAmbiguous};
+    ///
+    /// let dna: DnaSequenceAmbiguous = "GANTNCA".parse().unwrap();
+    /// assert!(dna.positions_of(NucleotideAmbiguous::N).eq([2, 4]));
+    /// ```
+    pub fn positions_of(&self, nuc: T) -> impl Iterator<Item = usize> + '_ {
+        positions(&self.dna, move |&other| other == nuc)
+    }
+
+    /// Returns the `(start, end)` spans (with `end` exclusive) of each maximal run of
+    /// consecutive occurrences of `nuc`, in order.
+    ///
+    /// ```
+    /// use quickdna::{DnaSequenceAmbiguous, NucleotideAmbiguous};
+    ///
+    /// // e.g. to split a scaffold at its assembly gaps
+    /// let scaffold: DnaSequenceAmbiguous = "ACNNNNGTNA".parse().unwrap();
+    /// assert_eq!(scaffold.runs(NucleotideAmbiguous::N), [(2, 6), (8, 9)]);
+    /// ```
+    pub fn runs(&self, nuc: T) -> Vec<(usize, usize)> {
+        self.runs_where(|other| other == nuc)
+    }
+
+    /// Returns the `(start, end)` spans (with `end` exclusive) of each maximal run of
+    /// consecutive nucleotides satisfying `predicate`, in order.
+    ///
+    /// ```
+    /// use quickdna::{DnaSequenceAmbiguous, NucleotideLike};
+    ///
+    /// let dna: DnaSequenceAmbiguous = "ACRYNGTNA".parse().unwrap();
+    /// assert_eq!(dna.runs_where(|n| n.is_ambiguous()), [(2, 5), (7, 8)]);
+    /// ```
+    pub fn runs_where(&self, mut predicate: impl FnMut(T) -> bool) -> Vec<(usize, usize)> {
+        let mut runs = vec![];
+        let mut start = None;
+        for (i, &nuc) in self.dna.iter().enumerate() {
+            match (predicate(nuc), start) {
+                (true, None) => start = Some(i),
+                (false, Some(s)) => {
+                    runs.push((s, i));
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        if let Some(s) = start {
+            runs.push((s, self.dna.len()));
+        }
+        runs
+    }
+
+    /// Returns this sequence without its leading and trailing nucleotides that satisfy
+    /// `should_trim`. Nucleotides between the first and last ones that don't are kept.
+    ///
+    /// Returns an empty sequence if every nucleotide satisfies `should_trim`.
+    ///
+    /// ```
+    /// use quickdna::{DnaSequenceStrict, Nucleotide};
+    ///
+    /// let dna: DnaSequenceStrict = "AAGATTACAA".parse().unwrap();
+    /// assert_eq!(dna.trim_ends(|&n| n == Nucleotide::A).to_string(), "GATTAC");
+    /// ```
+    pub fn trim_ends<F: Fn(&T) -> bool>(&self, should_trim: F) -> Self {
+        let start = self.dna.iter().position(|n| !should_trim(n));
+        let end = self.dna.iter().rposition(|n| !should_trim(n));
+        match (start, end) {
+            (Some(start), Some(end)) => Self::new(self.dna[start..=end].to_vec()),
+            _ => Self::default(),
+        }
+    }
+
+    /// Fraction of this sequence that is G or C, or 0 for an empty sequence.
+    ///
+    /// Ambiguous nucleotides count fractionally by the share of their possibilities that are
+    /// G or C, as in [`NucleotideIter::gc_windows`](crate::NucleotideIter::gc_windows).
+    /// Gaps are skipped, so an aligned sequence has the G/C content of its ungapped form.
+    ///
+    /// ```
+    /// use quickdna::{DnaSequenceAmbiguous, DnaSequenceGapped};
+    ///
+    /// let dna: DnaSequenceAmbiguous = "GATN".parse().unwrap();
+    /// assert_eq!(dna.gc_content(), 1.5 / 4.0);
+    ///
+    /// let aligned: DnaSequenceGapped = "G-AT--N".parse().unwrap();
+    /// assert_eq!(aligned.gc_content(), dna.gc_content());
+    /// ```
+    pub fn gc_content(&self) -> f64 {
+        let (gc, len) = self
+            .dna
+            .iter()
+            .filter(|n| !n.is_gap())
+            .fold((0, 0), |(gc, len), &n| (gc + gc_twelfths(n), len + 1));
+        if len == 0 {
+            return 0.0;
+        }
+        gc as f64 / (12 * len) as f64
+    }
+
+    /// Count occurrences of each nucleotide (or ambiguity code) in this sequence.
+    ///
+    /// Nucleotides that don't occur are omitted.
+    ///
+    /// ```
+    /// use quickdna::{DnaSequenceStrict, Nucleotide};
+    ///
+    /// let dna: DnaSequenceStrict = "GATTACA".parse().unwrap();
+    /// let counts = dna.base_counts();
+    /// assert_eq!(counts[&Nucleotide::A], 3);
+    /// assert_eq!(counts.get(&Nucleotide::G), Some(&1));
+    /// assert_eq!(counts.len(), 4);
+    /// ```
+    pub fn base_counts(&self) -> BTreeMap<T, usize>
+    where
+        T: Ord,
+    {
+        let mut counts = BTreeMap::new();
+        for &n in &self.dna {
+            *counts.entry(n).or_default() += 1;
+        }
+        counts
+    }
+
+    /// Returns a shortest edit script that turns this sequence into `other`, as computed by
+    /// [`diff::diff`](crate::diff::diff). Unlike a Hamming distance, this handles insertions
+    /// and deletions.
+    ///
+    /// Nucleotides are compared as-is, so an ambiguity code only matches itself.
+    ///
+    /// ```
+    /// use quickdna::{diff::Edit, DnaSequenceStrict, Nucleotide};
+    ///
+    /// let a: DnaSequenceStrict = "GATTACA".parse().unwrap();
+    /// let b: DnaSequenceStrict = "GATTTACA".parse().unwrap();
+    /// assert_eq!(
+    ///     a.diff(&b),
+    ///     [
+    ///         Edit::Match { pos: 0, len: 4 },
+    ///         Edit::Insertion { pos: 4, dna: vec![Nucleotide::T] },
+    ///         Edit::Match { pos: 4, len: 3 },
+    ///     ]
+    /// );
+    /// ```
+    pub fn diff(&self, other: &Self) -> Vec<Edit<T>> {
+        crate::diff::diff(&self.dna, &other.dna)
+    }
+
+    /// The symmetric matrix of Hamming distances between each pair of `seqs`, where
+    /// `matrix[i][j]` is the number of positions at which `seqs[i]` and `seqs[j]` differ.
+    ///
+    /// Nucleotides are compared as-is, so an ambiguity code only matches itself.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LengthMismatch`] with the indices of the first sequence and the first
+    /// sequence whose length differs from it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quickdna::DnaSequenceStrict;
+    ///
+    /// let seqs: Vec<DnaSequenceStrict> = ["GATTACA", "GACTACA", "GACTGCA"]
+    ///     .into_iter()
+    ///     .map(|s| s.parse().unwrap())
+    ///     .collect();
+    /// let matrix = DnaSequenceStrict::pairwise_hamming(&seqs).unwrap();
+    /// assert_eq!(matrix, [[0, 1, 2], [1, 0, 1], [2, 1, 0]]);
+    ///
+    /// let err = DnaSequenceStrict::pairwise_hamming(&[seqs[0].clone(), "GAT".parse().unwrap()])
+    ///     .unwrap_err();
+    /// assert_eq!(err.indices, (0, 1));
+    /// ```
+    pub fn pairwise_hamming(seqs: &[Self]) -> Result<Vec<Vec<usize>>, LengthMismatch> {
+        check_lengths(seqs)?;
+        let upper = (0..seqs.len()).map(|i| hamming_row(seqs, i)).collect();
+        Ok(symmetric_matrix(upper))
+    }
+
+    /// Like [`Self::pairwise_hamming`], but computes rows of the matrix in parallel.
+    #[cfg(feature = "rayon")]
+    pub fn par_pairwise_hamming(seqs: &[Self]) -> Result<Vec<Vec<usize>>, LengthMismatch>
+    where
+        T: Send + Sync,
+    {
+        use rayon::prelude::*;
+
+        check_lengths(seqs)?;
+        let upper = (0..seqs.len())
+            .into_par_iter()
+            .map(|i| hamming_row(seqs, i))
+            .collect();
+        Ok(symmetric_matrix(upper))
+    }
+
+    /// Count occurrences of each codon in the given reading frame, which is the offset
+    /// (0, 1 or 2) of its first codon from the start of this sequence. Any trailing partial
+    /// codon is ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `frame` is greater than 2.
+    ///
+    /// ```
+    /// use quickdna::{Codon, DnaSequenceStrict};
+    ///
+    /// let dna: DnaSequenceStrict = "ATGAAAATGC".parse().unwrap();
+    /// let usage = dna.codon_usage(0);
+    /// assert_eq!(usage[&"ATG".parse::<Codon>().unwrap()], 2);
+    /// assert_eq!(usage[&"AAA".parse::<Codon>().unwrap()], 1);
+    /// assert_eq!(dna.codon_usage(1).len(), 3);
+    /// ```
+    pub fn codon_usage(&self, frame: usize) -> HashMap<T::Codon, usize>
+    where
+        T::Codon: Eq + std::hash::Hash,
+    {
+        assert!(frame < 3, "reading frame must be 0, 1 or 2");
+        let mut usage = HashMap::new();
+        let frame = self.dna.get(frame..).unwrap_or_default();
+        for codon in frame.iter().copied().codons() {
+            *usage.entry(codon).or_default() += 1;
+        }
+        usage
+    }
+
+    /// Relative frequency of each codon in the given reading frame, as a fraction of all
+    /// codons in that frame. See [`Self::codon_usage`].
+    pub fn codon_frequencies(&self, frame: usize) -> HashMap<T::Codon, f64>
+    where
+        T::Codon: Eq + std::hash::Hash,
+    {
+        let usage = self.codon_usage(frame);
+        let total: usize = usage.values().sum();
+        usage
+            .into_iter()
+            .map(|(codon, count)| (codon, count as f64 / total as f64))
+            .collect()
+    }
+
+    /// Shannon entropy of the distribution of nucleotides in this sequence, in bits.
+    ///
+    /// Each ambiguity code counts as a distinct symbol. This is 0 for an empty or
+    /// homopolymer sequence, and 2 for one with equal numbers of `A`, `T`, `C` and `G`.
+    ///
+    /// ```
+    /// use quickdna::DnaSequenceStrict;
+    ///
+    /// let dna: DnaSequenceStrict = "AAAA".parse().unwrap();
+    /// assert_eq!(dna.shannon_entropy(), 0.0);
+    /// let dna: DnaSequenceStrict = "AAAATTCG".parse().unwrap();
+    /// assert_eq!(dna.shannon_entropy(), 1.75);
+    /// ```
+    pub fn shannon_entropy(&self) -> f64 {
+        let mut counts = [0usize; 16];
+        for &n in &self.dna {
+            counts[n.bits() as usize] += 1;
+        }
+        let len = self.dna.len() as f64;
+        counts
+            .into_iter()
+            .filter(|&count| count > 0)
+            .map(|count| {
+                let p = count as f64 / len;
+                -p * p.log2()
+            })
+            .sum::<f64>()
+            // avoid returning -0.0 for homopolymers
+            .max(0.0)
+    }
+
+    /// Low-complexity score of this sequence, as used by the symmetric DUST algorithm.
+    ///
+    /// This is Σ *c*(*c* - 1) / 2 / (*l* - 1), where *c* ranges over the number of times each
+    /// distinct triplet occurs, and *l* is the number of (overlapping) triplets. Higher scores
+    /// are less complex; a homopolymer of length *n* scores (*n* - 2) / 2, while a random
+    /// sequence of 64 nucleotides typically scores below 1. Sequences with fewer than 4
+    /// nucleotides score 0.
+    ///
+    /// The score is intended for short windows, e.g. of 64 nucleotides from [`Self::windows`]:
+    /// DUST conventionally masks windows scoring above 20.
+    ///
+    /// ```
+    /// use quickdna::DnaSequenceStrict;
+    ///
+    /// let dna: DnaSequenceStrict = "AAAAAAAA".parse().unwrap();
+    /// assert_eq!(dna.dust_score(), 3.0);
+    /// let dna: DnaSequenceStrict = "GATTACAGGT".parse().unwrap();
+    /// assert_eq!(dna.dust_score(), 0.0);
+    /// ```
+    pub fn dust_score(&self) -> f64 {
+        let triplets = self.dna.len().saturating_sub(2);
+        if triplets < 2 {
+            return 0.0;
+        }
+        let mut counts = std::collections::HashMap::new();
+        for triplet in self.dna.windows(3) {
+            let idx = CodonIdx::from([triplet[0], triplet[1], triplet[2]]);
+            *counts.entry(usize::from(idx)).or_insert(0usize) += 1;
+        }
+        let pairs: usize = counts.values().map(|&c| c * (c - 1) / 2).sum();
+        pairs as f64 / (triplets - 1) as f64
+    }
+
+    /// The SEGUID checksum of this sequence: the base64-encoded SHA-1 digest of its uppercase
+    /// ASCII form, without `=` padding.
+    ///
+    /// This is the sequence identifier used by tools like Biopython's `seguid`.
+    ///
+    /// ```
+    /// use quickdna::DnaSequenceStrict;
+    ///
+    /// let dna: DnaSequenceStrict = "gattaca".parse().unwrap();
+    /// assert_eq!(dna.seguid(), "tp2jzeCM2e3W4yxtrrx09CMKa/8");
+    /// ```
+    #[cfg(feature = "sha1")]
+    pub fn seguid(&self) -> String {
+        use sha1::Digest;
+        checksum::base64_unpadded(&sha1::Sha1::digest(self.to_string()))
+    }
+
+    /// The MD5 digest of this sequence's uppercase ASCII form, in lowercase hexadecimal.
+    ///
+    /// ```
+    /// use quickdna::DnaSequenceStrict;
+    ///
+    /// let dna: DnaSequenceStrict = "gattaca".parse().unwrap();
+    /// assert_eq!(dna.md5_hex(), "61966c86d7c3bb28fff946c52eefff0b");
+    /// ```
+    #[cfg(feature = "md5")]
+    pub fn md5_hex(&self) -> String {
+        use md5::Digest;
+        checksum::hex(&md5::Md5::digest(self.to_string()))
+    }
+
+    /// Average molecular weight of this single strand of DNA, in daltons.
+    ///
+    /// Ambiguous nucleotides have the average mass of their possibilities, and gaps are
+    /// skipped.
+    ///
+    /// ```
+    /// use quickdna::{mass, DnaSequenceGapped, DnaSequenceStrict};
+    ///
+    /// let dna: DnaSequenceStrict = "AT".parse().unwrap();
+    /// assert_eq!(dna.molecular_weight(), mass::DAMP + mass::DTMP - mass::WATER);
+    ///
+    /// let aligned: DnaSequenceGapped = "-A--T".parse().unwrap();
+    /// assert_eq!(aligned.molecular_weight(), dna.molecular_weight());
+    /// ```
+    pub fn molecular_weight(&self) -> f64 {
+        let monomers = self.dna.iter().filter(|nuc| !nuc.is_gap());
+        polymer_weight(monomers.map(|&nuc| nucleotide_mass(nuc)))
+    }
+}
+
+/// Indices of the items of `slice` that satisfy `predicate`.
+fn positions<'a, T>(
+    slice: &'a [T],
+    mut predicate: impl FnMut(&T) -> bool + 'a,
+) -> impl Iterator<Item = usize> + 'a {
+    slice
+        .iter()
+        .enumerate()
+        .filter_map(move |(i, item)| predicate(item).then_some(i))
+}
+
+impl<T: NucleotideLike> BaseSequence for DnaSequence<T> {
+    type Item = T;
+
+    fn as_slice(&self) -> &[Self::Item] {
+        &self.dna
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [Self::Item] {
+        &mut self.dna
+    }
 }
 
 impls!(DnaSequence<Nucleotide>);
 impls!(DnaSequence<NucleotideAmbiguous>);
+impls!(DnaSequence<NucleotideGapped>);
+
+/// Iterator over overlapping windows of a [`DnaSequence`].
+///
+/// This `struct` is created by [`DnaSequence::windows`].
+#[derive(Clone, Debug)]
+pub struct DnaWindows<'a, T>(std::slice::Windows<'a, T>);
+
+impl<T: NucleotideLike> DnaWindows<'_, T> {
+    /// Returns the window `index` positions ahead, without advancing the iterator.
+    pub fn get(&self, index: usize) -> Option<DnaSequence<T>> {
+        self.0
+            .clone()
+            .nth(index)
+            .map(|w| DnaSequence::new(w.to_vec()))
+    }
+}
+
+impl<T: NucleotideLike> Iterator for DnaWindows<'_, T> {
+    type Item = DnaSequence<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|w| DnaSequence::new(w.to_vec()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.0.nth(n).map(|w| DnaSequence::new(w.to_vec()))
+    }
+}
+
+impl<T: NucleotideLike> DoubleEndedIterator for DnaWindows<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(|w| DnaSequence::new(w.to_vec()))
+    }
+}
+
+impl<T: NucleotideLike> ExactSizeIterator for DnaWindows<'_, T> {}
 
 impl<T: NucleotideLike> fmt::Display for DnaSequence<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -307,22 +1482,27 @@ impl<T: NucleotideLike> fmt::Display for DnaSequence<T> {
     }
 }
 
+/// Parses nucleotides, skipping spaces and tabs.
+///
+/// On failure, the error includes the byte offset of the offending byte.
+///
+/// ```
+/// use quickdna::{DnaSequenceStrict, TranslationError};
+///
+/// let err = "GAT TACxA".parse::<DnaSequenceStrict>().unwrap_err();
+/// assert_eq!(err.position, 7);
+/// assert!(matches!(err.error, TranslationError::BadNucleotide('x')));
+/// ```
 impl<T: NucleotideLike> TryFrom<&[u8]> for DnaSequence<T> {
-    type Error = TranslationError;
+    type Error = Positioned<TranslationError>;
 
     fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
-        let mut vec = Vec::with_capacity(value.len());
-        for &b in value {
-            if b != b' ' && b != b'\t' {
-                vec.push(T::try_from(b)?);
-            }
-        }
-        Ok(Self::new(vec))
+        Self::from_bytes_with(value, WhitespacePolicy::SkipSpacesTabs)
     }
 }
 
 impl<T: NucleotideLike> TryFrom<Vec<u8>> for DnaSequence<T> {
-    type Error = TranslationError;
+    type Error = Positioned<TranslationError>;
 
     fn try_from(value: Vec<u8>) -> Result<Self, Self::Error> {
         Self::try_from(&value[..])
@@ -330,7 +1510,7 @@ impl<T: NucleotideLike> TryFrom<Vec<u8>> for DnaSequence<T> {
 }
 
 impl<T: NucleotideLike> FromStr for DnaSequence<T> {
-    type Err = TranslationError;
+    type Err = Positioned<TranslationError>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Self::try_from(s.as_bytes())
@@ -343,7 +1523,113 @@ impl<T: NucleotideLike> AsRef<[T]> for DnaSequence<T> {
     }
 }
 
+impl<T: NucleotideLike> IntoIterator for DnaSequence<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.dna.into_iter()
+    }
+}
+
+impl<'a, T: NucleotideLike> IntoIterator for &'a DnaSequence<T> {
+    type Item = T;
+    type IntoIter = std::iter::Copied<std::slice::Iter<'a, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.dna.iter().copied()
+    }
+}
+
+impl From<DnaSequence<Nucleotide>> for DnaSequence<NucleotideAmbiguous> {
+    fn from(value: DnaSequence<Nucleotide>) -> Self {
+        Self::new(value.dna.into_iter().map(Into::into).collect())
+    }
+}
+
+/// Fails on the first ambiguous nucleotide, reporting its index.
+///
+/// ```
+/// use quickdna::{DnaSequenceAmbiguous, DnaSequenceStrict, TranslationError};
+///
+/// let dna: DnaSequenceAmbiguous = "GATTACA".parse().unwrap();
+/// assert!(DnaSequenceStrict::try_from(dna).is_ok());
+///
+/// let dna: DnaSequenceAmbiguous = "GATNACA".parse().unwrap();
+/// let err = DnaSequenceStrict::try_from(dna).unwrap_err();
+/// assert_eq!(err.position, 3);
+/// assert!(matches!(err.error, TranslationError::UnexpectedAmbiguousNucleotide('N')));
+/// ```
+impl TryFrom<DnaSequence<NucleotideAmbiguous>> for DnaSequence<Nucleotide> {
+    type Error = Positioned<TranslationError>;
+
+    fn try_from(value: DnaSequence<NucleotideAmbiguous>) -> Result<Self, Self::Error> {
+        let dna = value
+            .dna
+            .into_iter()
+            .enumerate()
+            .map(|(position, nuc)| {
+                Nucleotide::try_from(nuc).map_err(|error| Positioned { position, error })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self::new(dna))
+    }
+}
+
+/// An ambiguous sequence equals a strict one if it contains exactly the same nucleotides, and
+/// no ambiguity codes. See [`DnaSequenceAmbiguous::is_compatible_with`] for a looser comparison.
+impl PartialEq<DnaSequence<Nucleotide>> for DnaSequence<NucleotideAmbiguous> {
+    fn eq(&self, other: &DnaSequence<Nucleotide>) -> bool {
+        self.len() == other.len()
+            && self
+                .dna
+                .iter()
+                .zip(&other.dna)
+                .all(|(&a, &b)| a == NucleotideAmbiguous::from(b))
+    }
+}
+
+impl PartialEq<DnaSequence<NucleotideAmbiguous>> for DnaSequence<Nucleotide> {
+    fn eq(&self, other: &DnaSequence<NucleotideAmbiguous>) -> bool {
+        other == self
+    }
+}
+
 impl DnaSequence<Nucleotide> {
+    /// Fraction of codons in the given reading frame whose third nucleotide is G or C, where
+    /// the frame is the offset (0, 1 or 2) of its first codon from the start of this
+    /// sequence. Any trailing partial codon is ignored, and if there are no complete codons,
+    /// this returns 0.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `frame` is greater than 2.
+    ///
+    /// ```
+    /// use quickdna::DnaSequenceStrict;
+    ///
+    /// let dna: DnaSequenceStrict = "ATGAAGTTAGC".parse().unwrap();
+    /// // ATG AAG TTA
+    /// assert_eq!(dna.gc3_content(0), 2.0 / 3.0);
+    /// // TGA AGT TAG
+    /// assert_eq!(dna.gc3_content(1), 1.0 / 3.0);
+    /// ```
+    pub fn gc3_content(&self, frame: usize) -> f64 {
+        assert!(frame < 3, "reading frame must be 0, 1 or 2");
+        let frame = self.dna.get(frame..).unwrap_or_default();
+        let (mut codons, mut gc3) = (0, 0);
+        for Codon([_, _, third]) in frame.iter().copied().codons() {
+            codons += 1;
+            if matches!(third, Nucleotide::G | Nucleotide::C) {
+                gc3 += 1;
+            }
+        }
+        if codons == 0 {
+            return 0.0;
+        }
+        gc3 as f64 / codons as f64
+    }
+
     /// Return canonical isomorphic DNA sequence.
     ///
     /// This returns the lexical minimum of all sequences isomorphic to the original or its
@@ -351,45 +1637,693 @@ impl DnaSequence<Nucleotide> {
     /// they are isomorphic (or one is isomorphic to the reverse of the other).
     /// See [`Canonical`] for details.
     ///
-    /// # Caveat
+    /// # Caveat
+    ///
+    /// We define "lexical minimum" in terms of the ordering of [`Nucleotide`] which is currently
+    ///  [`A`](Nucleotide::A) [`T`](Nucleotide::T) [`C`](Nucleotide::C) [`G`](Nucleotide::G),
+    /// _not_ alphabetical.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quickdna::DnaSequenceStrict;
+    ///
+    /// let dna: DnaSequenceStrict = "CATTAG".parse().unwrap();
+    /// let expected: DnaSequenceStrict = "ATCCTG".parse().unwrap();
+    /// assert_eq!(dna.canonical(), expected);
+    ///
+    /// let dna: DnaSequenceStrict = "TAGACGTACGTAGTACGTTAGCTGAGCTGAGTACG".parse().unwrap();
+    /// // Reverse complement does not change the canonical sequence,
+    /// // because by definition its reverse is isomorphic to the original.
+    /// assert_eq!(dna.canonical(), dna.reverse_complement().canonical());
+    /// ```
+    pub fn canonical(&self) -> Self {
+        let canonical = Canonical::new(self.as_slice().iter().copied()).collect();
+        Self::new(canonical)
+    }
+
+    /// Return forward-canonical isomorphic DNA sequence.
+    ///
+    /// Like [`Self::canonical`], but never reverses the sequence, so two sequences have the
+    /// same forward-canonical sequence if and only if they are isomorphic.
+    /// See [`ForwardCanonical`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quickdna::DnaSequenceStrict;
+    ///
+    /// let dna: DnaSequenceStrict = "TTGT".parse().unwrap();
+    /// let expected: DnaSequenceStrict = "AATA".parse().unwrap();
+    /// assert_eq!(dna.forward_canonical(), expected);
+    ///
+    /// let dna: DnaSequenceStrict = "TGTT".parse().unwrap();
+    /// let expected: DnaSequenceStrict = "ATAA".parse().unwrap();
+    /// assert_eq!(dna.forward_canonical(), expected);
+    /// ```
+    pub fn forward_canonical(&self) -> Self {
+        let canonical = ForwardCanonical::new(self.as_slice().iter().copied()).collect();
+        Self::new(canonical)
+    }
+
+    /// Apply a permutation of nucleotides to this sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quickdna::{canonical::Remap, DnaSequenceStrict, Nucleotide::*};
+    ///
+    /// let dna: DnaSequenceStrict = "CATTAG".parse().unwrap();
+    /// let remapped = dna.remap(Remap::new([G, C, T, A]).unwrap());
+    /// assert_eq!(remapped.to_string(), "TGCCGA");
+    /// ```
+    pub fn remap(&self, remap: Remap) -> Self {
+        Self::new(remap.remap(&self.dna))
+    }
+
+    /// Return this sequence with each of the 24 [`PERMUTATIONS`] applied, in order.
+    ///
+    /// These are all the sequences isomorphic to this one. If this sequence doesn't contain
+    /// all four nucleotides, some of them will be duplicates.
+    pub fn all_permutations(&self) -> impl Iterator<Item = Self> + '_ {
+        PERMUTATIONS.iter().map(|&remap| self.remap(remap))
+    }
+
+    /// Return the lesser of this sequence and its reverse complement.
+    ///
+    /// A sequence and its reverse complement share the same key, so this is suitable as a
+    /// `HashMap` key for grouping sequences that represent the same double-stranded DNA.
+    /// Unlike [`Self::canonical`], this does not group merely isomorphic sequences.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quickdna::DnaSequenceStrict;
+    ///
+    /// let dna: DnaSequenceStrict = "GGAC".parse().unwrap();
+    /// let expected: DnaSequenceStrict = "GTCC".parse().unwrap();
+    /// assert_eq!(dna.canonical_key(), expected);
+    /// assert_eq!(dna.reverse_complement().canonical_key(), expected);
+    /// ```
+    pub fn canonical_key(&self) -> Self {
+        let rc = self.reverse_complement();
+        if rc < *self {
+            rc
+        } else {
+            self.clone()
+        }
+    }
+
+    /// The [SEGUID](DnaSequence::seguid) of [`Self::canonical_key`], so that a sequence
+    /// and its reverse complement share a checksum.
+    ///
+    /// This deliberately doesn't hash [`Self::canonical`], which would also give merely
+    /// isomorphic sequences like `AAAA` and `CCCC` the same checksum.
+    ///
+    /// ```
+    /// use quickdna::DnaSequenceStrict;
+    ///
+    /// let dna: DnaSequenceStrict = "GATTACA".parse().unwrap();
+    /// assert_eq!(dna.canonical_seguid(), dna.reverse_complement().canonical_seguid());
+    /// assert_eq!(dna.canonical_seguid(), dna.reverse_complement().seguid());
+    /// ```
+    #[cfg(feature = "sha1")]
+    pub fn canonical_seguid(&self) -> String {
+        self.canonical_key().seguid()
+    }
+
+    /// Return `(position, canonical_kmer)` minimizers for each window of `w` consecutive
+    /// k-mers, with consecutive duplicates removed.
+    ///
+    /// K-mers are packed 2 bits per nucleotide, and canonicalized as the lesser of the
+    /// k-mer and its reverse complement. See [`Minimizers`] and [`crate::kmer`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `w` is 0, or if `k` is 0 or greater than [`kmer::MAX_K`](crate::kmer::MAX_K).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quickdna::{kmer, DnaSequenceStrict};
+    ///
+    /// let dna: DnaSequenceStrict = "GGAGG".parse().unwrap();
+    /// let minimizers: Vec<_> = dna.minimizers(1, 3).collect();
+    /// assert_eq!(minimizers, [(2, kmer::encode(quickdna::Nucleotide::A))]);
+    /// ```
+    pub fn minimizers(&self, k: usize, w: usize) -> impl Iterator<Item = (usize, u64)> + '_ {
+        Minimizers::new(self.dna.iter().copied(), k, w)
+    }
+
+    /// Return `(position, mismatches)` for every window of this sequence whose Hamming
+    /// distance to `pattern` is at most `max_mismatches`, in order of position.
+    ///
+    /// An empty `pattern` matches at every position from 0 to `self.len()` inclusive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quickdna::{DnaSequenceStrict, Nucleotide::*};
+    ///
+    /// let dna: DnaSequenceStrict = "GATTACAGATCACA".parse().unwrap();
+    /// assert_eq!(dna.find_all_mismatch(&[G, A, T, T], 0), [(0, 0)]);
+    /// assert_eq!(dna.find_all_mismatch(&[G, A, T, T], 1), [(0, 0), (7, 1)]);
+    /// ```
+    pub fn find_all_mismatch(
+        &self,
+        pattern: &[Nucleotide],
+        max_mismatches: usize,
+    ) -> Vec<(usize, usize)> {
+        if pattern.len() > self.dna.len() {
+            return vec![];
+        }
+        (0..=self.dna.len() - pattern.len())
+            .filter_map(|position| {
+                let window = &self.dna[position..position + pattern.len()];
+                let mut mismatches = 0;
+                for (a, b) in window.iter().zip(pattern) {
+                    if a != b {
+                        mismatches += 1;
+                        if mismatches > max_mismatches {
+                            return None;
+                        }
+                    }
+                }
+                Some((position, mismatches))
+            })
+            .collect()
+    }
+
+    /// Estimate melting temperature in °C with the Wallace rule, 2·(A+T) + 4·(G+C).
+    ///
+    /// ```
+    /// use quickdna::DnaSequenceStrict;
+    ///
+    /// let primer: DnaSequenceStrict = "ACGTTGCA".parse().unwrap();
+    /// assert_eq!(primer.melting_temp_wallace(), 24.0);
+    /// ```
+    pub fn melting_temp_wallace(&self) -> f64 {
+        melting_temp_wallace(&self.dna)
+    }
+
+    /// Estimate melting temperature in °C with the nearest-neighbor model.
+    /// See [`melting_temp_nn`] for details.
+    ///
+    /// ```
+    /// use quickdna::{melting::TmParams, DnaSequenceStrict};
+    ///
+    /// let primer: DnaSequenceStrict = "AGCGTCCATGAACTGGATCA".parse().unwrap();
+    /// let tm = primer.melting_temp_nn(TmParams::new());
+    /// assert!((54.0..55.0).contains(&tm));
+    /// ```
+    pub fn melting_temp_nn(&self, params: TmParams) -> f64 {
+        melting_temp_nn(&self.dna, params)
+    }
+
+    /// The Codon Adaptation Index of the first reading frame of this sequence: the geometric
+    /// mean of the relative adaptedness of its codons.
+    ///
+    /// Following Sharp and Li (1987), stop codons and codons for amino acids with only one
+    /// codon in `table` (like `ATG` for `M` in the standard code) are skipped. Any counted
+    /// codon with a relative adaptedness of 0 makes the index 0, and a sequence with no
+    /// counted codons has an index of NaN.
+    ///
+    /// ```
+    /// use quickdna::{codon_usage::{CodonUsageTable, RelativeAdaptednessTable}, DnaSequenceStrict, TranslationTable};
+    ///
+    /// let table = TranslationTable::Ncbi1;
+    /// let reference: DnaSequenceStrict = "CTGCTGCTGCTGTTAAAG".parse().unwrap();
+    /// let adaptedness = RelativeAdaptednessTable::new(&CodonUsageTable::from_dna(&reference), table);
+    ///
+    /// let optimal: DnaSequenceStrict = "ATGCTGAAGTGA".parse().unwrap();
+    /// assert_eq!(optimal.cai(&adaptedness, table), 1.0);
+    /// let mixed: DnaSequenceStrict = "ATGCTGTTA".parse().unwrap();
+    /// assert_eq!(mixed.cai(&adaptedness, table), 0.5);
+    /// ```
+    pub fn cai(&self, weights: &RelativeAdaptednessTable, table: TranslationTable) -> f64 {
+        let translate = table.to_fn();
+        let synonyms = synonym_counts(table);
+        let (log_sum, count) = self
+            .codons()
+            .filter(|&codon| {
+                let aa = translate(codon);
+                aa != b'*' && synonyms[aa as usize] > 1
+            })
+            .fold((0.0, 0), |(log_sum, count), codon| {
+                (log_sum + weights.weight(codon).ln(), count + 1)
+            });
+        (log_sum / count as f64).exp()
+    }
+
+    /// Whether this sequence equals its own reverse complement, like the recognition sites
+    /// of most restriction enzymes. Only even-length sequences can be, since the middle
+    /// nucleotide of an odd-length sequence would have to be its own complement.
+    ///
+    /// ```
+    /// use quickdna::DnaSequenceStrict;
+    ///
+    /// let ecori: DnaSequenceStrict = "GAATTC".parse().unwrap();
+    /// assert!(ecori.is_palindrome());
+    /// let dna: DnaSequenceStrict = "GAATC".parse().unwrap();
+    /// assert!(!dna.is_palindrome());
+    /// ```
+    pub fn is_palindrome(&self) -> bool {
+        // The radius around the middle is at most half the length, rounded down, so this
+        // also rules out odd lengths.
+        2 * palindrome_radius(&self.dna, self.len() / 2) == self.len()
+    }
+
+    /// Returns the `(start, end)` spans (with `end` exclusive) of every palindromic
+    /// subsequence (see [`Self::is_palindrome`]) whose length is between `min_len` and
+    /// `max_len` inclusive, ordered by start and then by end.
+    ///
+    /// Palindromes nest around their center, so a palindrome of length 8 contains ones of
+    /// length 6, 4 and 2, and all of those within the length bounds are returned.
+    ///
+    /// ```
+    /// use quickdna::DnaSequenceStrict;
+    ///
+    /// let dna: DnaSequenceStrict = "CCGAATTCAT".parse().unwrap();
+    /// assert_eq!(dna.find_palindromes(4, 6), [(2, 8), (3, 7)]);
+    /// assert_eq!(dna.find_palindromes(2, 2), [(1, 3), (4, 6), (8, 10)]);
+    /// ```
+    pub fn find_palindromes(&self, min_len: usize, max_len: usize) -> Vec<(usize, usize)> {
+        let mut spans = vec![];
+        for center in 1..self.len() {
+            let radius = palindrome_radius(&self.dna, center).min(max_len / 2);
+            for half in min_len.div_ceil(2).max(1)..=radius {
+                spans.push((center - half, center + half));
+            }
+        }
+        spans.sort_unstable();
+        spans
+    }
+}
+
+impl DnaSequence<NucleotideAmbiguous> {
+    /// Translate this DNA sequence into protein using the specified translation table,
+    /// handling codons that could code for more than one amino acid according to `policy`.
+    /// See [`crate::ambiguity`] for the available policies.
+    ///
+    /// ```
+    /// use quickdna::ambiguity::{AllPossible, CollapseToX, Reject};
+    /// use quickdna::{DnaSequenceAmbiguous, TranslationTable};
+    ///
+    /// let dna: DnaSequenceAmbiguous = "GCNTRG".parse().unwrap();
+    /// let table = TranslationTable::Ncbi1;
+    /// assert_eq!(dna.translate_with(table, CollapseToX).to_string(), "AX");
+    /// assert_eq!(dna.translate_with(table, Reject).unwrap_err().position, 3);
+    /// assert_eq!(dna.translate_with(table, AllPossible)[1].as_slice(), b"*W");
+    /// ```
+    pub fn translate_with<P: AmbiguityPolicy>(
+        &self,
+        table: TranslationTable,
+        policy: P,
+    ) -> P::Output {
+        policy.translate(self, table)
+    }
+
+    /// Return all unambiguous expansions.
+    ///
+    /// Expansions are returned in lexicographic order based on the ordering of [`Nucleotide`]
+    /// (not currently alphabetical).
+    pub fn expansions(&self) -> Expansions {
+        Expansions::new(self.as_slice())
+    }
+
+    /// Return the number of unambiguous expansions, without producing them.
+    ///
+    /// Returns `None` if the count doesn't fit in a [`u128`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quickdna::DnaSequenceAmbiguous;
+    ///
+    /// let dna: DnaSequenceAmbiguous = "ATBCGYAC".parse().unwrap();
+    /// assert_eq!(dna.expansion_count(), Some(6));
+    /// ```
+    pub fn expansion_count(&self) -> Option<u128> {
+        self.dna.iter().try_fold(1u128, |count, nuc| {
+            count.checked_mul(nuc.possibilities().len() as u128)
+        })
+    }
+
+    /// Return the number of ambiguous nucleotides in this sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quickdna::DnaSequenceAmbiguous;
+    ///
+    /// let dna: DnaSequenceAmbiguous = "ATBCGYAC".parse().unwrap();
+    /// assert_eq!(dna.ambiguity_count(), 2);
+    /// ```
+    pub fn ambiguity_count(&self) -> usize {
+        self.dna.iter().ambiguity_count()
+    }
+
+    /// Return the length of the longest run of consecutive ambiguous nucleotides in this
+    /// sequence, e.g. to detect long degenerate stretches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quickdna::DnaSequenceAmbiguous;
+    ///
+    /// let dna: DnaSequenceAmbiguous = "ANNCGNNNAC".parse().unwrap();
+    /// assert_eq!(dna.max_run_of_ambiguities(), 3);
+    /// ```
+    pub fn max_run_of_ambiguities(&self) -> usize {
+        self.dna.iter().max_run_of_ambiguities()
+    }
+
+    /// Returns an iterator over the indices of nucleotides whose possibilities overlap
+    /// those of `nuc`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quickdna::{DnaSequenceAmbiguous, NucleotideAmbiguous};
+    ///
+    /// // R is A or G, and Y is C or T.
+    /// let dna: DnaSequenceAmbiguous = "GATYNCR".parse().unwrap();
+    /// assert!(dna.positions_matching(NucleotideAmbiguous::R).eq([0, 1, 4, 6]));
+    /// ```
+    pub fn positions_matching(&self, nuc: NucleotideAmbiguous) -> impl Iterator<Item = usize> + '_ {
+        positions(&self.dna, move |other| other.bits() & nuc.bits() != 0)
+    }
+
+    /// Merge aligned sequences column by column into a consensus, where each nucleotide is the
+    /// [`union`](NucleotideAmbiguous::union) of that column's nucleotides.
+    ///
+    /// Returns an empty sequence if `seqs` is empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sequences don't all have the same length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quickdna::{DnaSequenceAmbiguous, DnaSequenceStrict};
+    ///
+    /// let seqs: Vec<DnaSequenceStrict> = ["GATTACA", "GACTACA", "GATTGCA"]
+    ///     .into_iter()
+    ///     .map(|s| s.parse().unwrap())
+    ///     .collect();
+    /// let consensus = DnaSequenceAmbiguous::consensus(&seqs);
+    /// assert_eq!(consensus.to_string(), "GAYTRCA");
+    /// ```
+    pub fn consensus<T>(seqs: &[DnaSequence<T>]) -> Self
+    where
+        T: NucleotideLike + Into<NucleotideAmbiguous>,
+    {
+        let Some((first, rest)) = seqs.split_first() else {
+            return Self::default();
+        };
+        let mut dna: Vec<NucleotideAmbiguous> = first.dna.iter().map(|&n| n.into()).collect();
+        for seq in rest {
+            assert_eq!(seq.len(), dna.len(), "consensus of unaligned sequences");
+            for (merged, &n) in dna.iter_mut().zip(&seq.dna) {
+                *merged = merged.union(n.into());
+            }
+        }
+        Self::new(dna)
+    }
+
+    /// Whether `strict` is one of the expansions of this sequence, i.e. each of its nucleotides
+    /// is one of the possibilities of the corresponding nucleotide in this sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quickdna::{DnaSequenceAmbiguous, DnaSequenceStrict};
+    ///
+    /// let dna: DnaSequenceAmbiguous = "GANTYCA".parse().unwrap();
+    /// let strict: DnaSequenceStrict = "GAGTTCA".parse().unwrap();
+    /// assert!(dna.is_compatible_with(&strict));
+    /// assert!(dna != strict);
+    ///
+    /// let strict: DnaSequenceStrict = "GAGTACA".parse().unwrap();
+    /// assert!(!dna.is_compatible_with(&strict));
+    /// ```
+    pub fn is_compatible_with(&self, strict: &DnaSequenceStrict) -> bool {
+        self.len() == strict.len()
+            && self
+                .dna
+                .iter()
+                .zip(&strict.dna)
+                .all(|(&a, &b)| a.contains(b))
+    }
+
+    /// Replace every ambiguous nucleotide in this sequence with `with`, e.g. `A` for `N`.
+    ///
+    /// Unlike [`Self::expansions`], this is a single lossy cleanup: `with` is used even if
+    /// it isn't one of the ambiguous nucleotide's possibilities. Use
+    /// [`Self::replace_ambiguous_with`] to choose replacements position by position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quickdna::{DnaSequenceAmbiguous, DnaSequenceStrict, Nucleotide};
+    ///
+    /// let dna: DnaSequenceAmbiguous = "GANTYCA".parse().unwrap();
+    /// let expected: DnaSequenceStrict = "GAATACA".parse().unwrap();
+    /// assert_eq!(dna.replace_ambiguous(Nucleotide::A), expected);
+    /// ```
+    pub fn replace_ambiguous(&self, with: Nucleotide) -> DnaSequenceStrict {
+        self.replace_ambiguous_with(|_| with)
+    }
+
+    /// Replace every ambiguous nucleotide in this sequence with the result of calling
+    /// `replace` on it.
+    ///
+    /// `replace` is called once per ambiguous position, in order; unambiguous nucleotides are
+    /// kept as they are. For example, `|n| n.possibilities()[0]` picks the first possibility
+    /// (in the order of [`Nucleotide::ALL`]) of each ambiguity code.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quickdna::{DnaSequenceAmbiguous, DnaSequenceStrict};
+    ///
+    /// let dna: DnaSequenceAmbiguous = "GANTYCA".parse().unwrap();
+    /// let expected: DnaSequenceStrict = "GAATTCA".parse().unwrap();
+    /// assert_eq!(dna.replace_ambiguous_with(|n| n.possibilities()[0]), expected);
+    /// ```
+    pub fn replace_ambiguous_with(
+        &self,
+        mut replace: impl FnMut(NucleotideAmbiguous) -> Nucleotide,
+    ) -> DnaSequenceStrict {
+        let dna = self
+            .dna
+            .iter()
+            .map(|&nuc| Nucleotide::try_from(nuc).unwrap_or_else(|_| replace(nuc)))
+            .collect();
+        DnaSequenceStrict::new(dna)
+    }
+
+    /// Drop every ambiguous nucleotide from this sequence, keeping only the unambiguous ones.
+    ///
+    /// Note that this shifts the reading frame of everything after a dropped nucleotide.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quickdna::{DnaSequenceAmbiguous, DnaSequenceStrict};
+    ///
+    /// let dna: DnaSequenceAmbiguous = "GANTYCA".parse().unwrap();
+    /// let expected: DnaSequenceStrict = "GATCA".parse().unwrap();
+    /// assert_eq!(dna.strip_ambiguous(), expected);
+    /// ```
+    pub fn strip_ambiguous(&self) -> DnaSequenceStrict {
+        let dna = self
+            .dna
+            .iter()
+            .filter_map(|&nuc| Nucleotide::try_from(nuc).ok())
+            .collect();
+        DnaSequenceStrict::new(dna)
+    }
+
+    /// Trim leading and trailing ambiguous nucleotides, such as flanking `N`s, keeping any
+    /// ambiguous nucleotides in between.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quickdna::DnaSequenceAmbiguous;
+    ///
+    /// let dna: DnaSequenceAmbiguous = "NNGANTYCAR".parse().unwrap();
+    /// assert_eq!(dna.trim_ambiguous_ends().to_string(), "GANTYCA");
+    /// ```
+    pub fn trim_ambiguous_ends(&self) -> Self {
+        self.trim_ends(|n| n.is_ambiguous())
+    }
+
+    /// Return all unambiguous expansions, or an error if there would be more than `limit`.
+    ///
+    /// The count is checked before anything is allocated, so this is safe to call on
+    /// arbitrarily ambiguous input.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quickdna::{DnaSequenceAmbiguous, DnaSequenceStrict};
+    ///
+    /// let dna: DnaSequenceAmbiguous = "ACR".parse().unwrap();
+    /// let expected: Vec<DnaSequenceStrict> = ["ACA".parse().unwrap(), "ACG".parse().unwrap()].into();
+    /// assert_eq!(dna.try_expand(2).unwrap(), expected);
+    ///
+    /// let err = dna.try_expand(1).unwrap_err();
+    /// assert_eq!(err.count, Some(2));
+    /// ```
+    pub fn try_expand(&self, limit: usize) -> Result<Vec<DnaSequenceStrict>, TooManyExpansions> {
+        let count = self.expansion_count();
+        match count {
+            Some(n) if n <= limit as u128 => Ok(self.expansions().map(|e| e.to_dna()).collect()),
+            _ => Err(TooManyExpansions { count, limit }),
+        }
+    }
+
+    /// Return a single random unambiguous expansion.
+    ///
+    /// Each ambiguous nucleotide is resolved independently and uniformly among its
+    /// possibilities, so this is *O*(*N*) regardless of how many expansions there are.
+    #[cfg(feature = "rand")]
+    pub fn sample_expansion<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> DnaSequenceStrict {
+        use rand::seq::SliceRandom;
+
+        let dna = self
+            .dna
+            .iter()
+            .map(|nuc| *nuc.possibilities().choose(rng).unwrap())
+            .collect();
+        DnaSequenceStrict::new(dna)
+    }
+
+    /// Return `n` independent random unambiguous expansions.
+    ///
+    /// See [`Self::sample_expansion`].
+    #[cfg(feature = "rand")]
+    pub fn sample_expansions<R: rand::Rng + ?Sized>(
+        &self,
+        n: usize,
+        rng: &mut R,
+    ) -> Vec<DnaSequenceStrict> {
+        (0..n).map(|_| self.sample_expansion(rng)).collect()
+    }
+}
+
+impl DnaSequence<NucleotideGapped> {
+    /// Remove all gaps from this aligned sequence, keeping any ambiguity codes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use quickdna::{DnaSequenceAmbiguous, DnaSequenceGapped, DnaSequenceStrict};
+    ///
+    /// let aligned: DnaSequenceGapped = "GA--TN.ACA".parse().unwrap();
+    /// let expected: DnaSequenceAmbiguous = "GATNACA".parse().unwrap();
+    /// assert_eq!(aligned.degap(), expected);
     ///
-    /// We define "lexical minimum" in terms of the ordering of [`Nucleotide`] which is currently
-    ///  [`A`](Nucleotide::A) [`T`](Nucleotide::T) [`C`](Nucleotide::C) [`G`](Nucleotide::G),
-    /// _not_ alphabetical.
+    /// // Convert to a strict sequence if there should be no ambiguity codes.
+    /// assert!(DnaSequenceStrict::try_from(aligned.degap()).is_err());
+    /// ```
+    pub fn degap(&self) -> DnaSequenceAmbiguous {
+        let dna = self
+            .dna
+            .iter()
+            .filter_map(|&nuc| NucleotideAmbiguous::try_from(nuc).ok())
+            .collect();
+        DnaSequenceAmbiguous::new(dna)
+    }
+
+    /// Remove all gaps from this aligned sequence, failing on any ambiguity code.
+    ///
+    /// On error, the position is the index of the ambiguity code in this (gapped) sequence.
     ///
     /// # Examples
     ///
     /// ```
-    /// use quickdna::DnaSequenceStrict;
+    /// use quickdna::{DnaSequenceGapped, DnaSequenceStrict, TranslationError};
     ///
-    /// let dna: DnaSequenceStrict = "CATTAG".parse().unwrap();
-    /// let expected: DnaSequenceStrict = "ATCCTG".parse().unwrap();
-    /// assert_eq!(dna.canonical(), expected);
+    /// let aligned: DnaSequenceGapped = "GA--TT.ACA".parse().unwrap();
+    /// let expected: DnaSequenceStrict = "GATTACA".parse().unwrap();
+    /// assert_eq!(aligned.degap_strict(), Ok(expected));
     ///
-    /// let dna: DnaSequenceStrict = "TAGACGTACGTAGTACGTTAGCTGAGCTGAGTACG".parse().unwrap();
-    /// // Reverse complement does not change the canonical sequence,
-    /// // because by definition its reverse is isomorphic to the original.
-    /// assert_eq!(dna.canonical(), dna.reverse_complement().canonical());
+    /// let aligned: DnaSequenceGapped = "GA--TN.ACA".parse().unwrap();
+    /// let err = aligned.degap_strict().unwrap_err();
+    /// assert_eq!(err.position, 5);
+    /// assert_eq!(err.error, TranslationError::UnexpectedAmbiguousNucleotide('N'));
     /// ```
-    pub fn canonical(&self) -> Self {
-        let canonical = Canonical::new(self.as_slice().iter().copied()).collect();
-        Self::new(canonical)
+    pub fn degap_strict(&self) -> Result<DnaSequenceStrict, Positioned<TranslationError>> {
+        let dna = self
+            .dna
+            .iter()
+            .enumerate()
+            .filter(|(_, nuc)| !nuc.is_gap())
+            .map(|(position, &nuc)| {
+                Nucleotide::try_from(nuc).map_err(|error| Positioned { position, error })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(DnaSequenceStrict::new(dna))
     }
 }
 
-impl DnaSequence<NucleotideAmbiguous> {
-    /// Return all unambiguous expansions.
-    ///
-    /// Expansions are returned in lexicographic order based on the ordering of [`Nucleotide`]
-    /// (not currently alphabetical).
-    pub fn expansions(&self) -> Expansions {
-        Expansions::new(self.as_slice())
+fn check_lengths<T: NucleotideLike>(seqs: &[DnaSequence<T>]) -> Result<(), LengthMismatch> {
+    let Some(first) = seqs.first() else {
+        return Ok(());
+    };
+    match seqs.iter().position(|seq| seq.dna.len() != first.dna.len()) {
+        Some(i) => Err(LengthMismatch {
+            indices: (0, i),
+            lengths: (first.dna.len(), seqs[i].dna.len()),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Hamming distances from `seqs[i]` to each later sequence.
+fn hamming_row<T: NucleotideLike>(seqs: &[DnaSequence<T>], i: usize) -> Vec<usize> {
+    seqs[i + 1..]
+        .iter()
+        .map(|other| {
+            let pairs = seqs[i].dna.iter().zip(&other.dna);
+            pairs.filter(|(a, b)| a != b).count()
+        })
+        .collect()
+}
+
+/// Fill in a symmetric matrix with a zero diagonal from the rows of its upper triangle.
+fn symmetric_matrix(upper: Vec<Vec<usize>>) -> Vec<Vec<usize>> {
+    let n = upper.len();
+    let mut matrix = vec![vec![0; n]; n];
+    for (i, row) in upper.into_iter().enumerate() {
+        for (offset, distance) in row.into_iter().enumerate() {
+            let j = i + 1 + offset;
+            matrix[i][j] = distance;
+            matrix[j][i] = distance;
+        }
     }
+    matrix
+}
+
+/// The length of the longest palindrome arm around `center`: the largest `r` such that
+/// `dna[center - r..center + r]` equals its own reverse complement.
+fn palindrome_radius(dna: &[Nucleotide], center: usize) -> usize {
+    dna[..center]
+        .iter()
+        .rev()
+        .zip(&dna[center..])
+        .take_while(|&(&left, &right)| left == right.complement())
+        .count()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{Codon, CodonAmbiguous};
     use smallvec::smallvec;
     use std::hash::Hasher;
 
@@ -495,6 +2429,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_reading_frames_match_translate_self() {
+        let ncbi1 = TranslationTable::Ncbi1.to_fn();
+        for d in ["", "AA", "GGGG", "AAAGGGAAA", "TTRTTVNACGT"] {
+            let d = dna(d);
+            let frames: Vec<ProteinSequence> = d
+                .reading_frames()
+                .into_iter()
+                .map(|frame| ProteinSequence::new_unchecked(frame.map(ncbi1).collect()))
+                .collect();
+            assert_eq!(
+                frames,
+                d.translate_self_frames(TranslationTable::Ncbi1).to_vec()
+            );
+            assert_eq!(d.codons().len(), d.len() / 3);
+            let first_frame = d.reading_frames().into_iter().next();
+            if let Some(first_frame) = first_frame {
+                assert!(d.codons().eq(first_frame));
+            }
+        }
+    }
+
     #[test]
     fn test_short_translate_self() {
         assert_eq_smallvec!(
@@ -631,6 +2587,29 @@ mod tests {
             .is_empty());
     }
 
+    quickcheck::quickcheck! {
+        fn find_palindromes_matches_naive(dna: DnaSequenceStrict) -> bool {
+            let (min_len, max_len) = (3, 8);
+            let mut naive = vec![];
+            for start in 0..dna.len() {
+                for end in start + min_len..=(start + max_len).min(dna.len()) {
+                    if DnaSequenceStrict::new(dna.dna[start..end].to_vec()).is_palindrome() {
+                        naive.push((start, end));
+                    }
+                }
+            }
+            dna.find_palindromes(min_len, max_len) == naive
+        }
+
+        #[cfg(not(feature = "minimal-tables"))]
+        fn translate_all_frames_matches_owned_rc(dna: DnaSequenceAmbiguous) -> bool {
+            let table = TranslationTable::Ncbi11;
+            let mut expected = dna.translate_self_frames(table);
+            expected.append(&mut dna.reverse_complement().translate_self_frames(table));
+            dna.translate_all_frames(table) == expected
+        }
+    }
+
     #[test]
     fn test_dna_equality() {
         let d1 = dna("aaa");
@@ -661,6 +2640,84 @@ mod tests {
         assert_eq!(p1, p3);
     }
 
+    #[test]
+    fn test_protein_validation() {
+        assert!(protein("ACDEFGHIKLMNPQRSTVWY").validate_standard().is_ok());
+        assert!(protein("").validate_standard().is_ok());
+
+        let all_extras = ProteinValidation::new()
+            .allow_stop(true)
+            .allow_unknown(true)
+            .allow_ambiguous(true)
+            .allow_selenocysteine(true)
+            .allow_gap(true);
+        for extra in ["*", "X", "B", "Z", "J", "U", "-"] {
+            let p = protein(extra);
+            assert!(matches!(
+                p.validate_standard(),
+                Err(TranslationError::BadAminoAcid(_))
+            ));
+            assert!(p.validate(all_extras).is_ok());
+            assert!(ProteinSequence::from_str_strict(extra, ProteinValidation::new()).is_err());
+            assert!(ProteinSequence::from_str_strict(extra, all_extras).is_ok());
+        }
+        assert!(protein("O").validate(all_extras).is_err());
+        assert!(protein("O")
+            .validate(all_extras.allow_pyrrolysine(true))
+            .is_ok());
+        assert_eq!(
+            all_extras.allow_pyrrolysine(true),
+            ProteinValidation::iupac()
+        );
+        assert!(protein("MK-V")
+            .validate(all_extras.allow_gap(false))
+            .is_err());
+    }
+
+    #[test]
+    fn test_from_str_iupac() {
+        let alphabet = "ACDEFGHIKLMNPQRSTVWY*XBZJUO-";
+        let p = ProteinSequence::from_str_iupac(alphabet).unwrap();
+        assert_eq!(p.to_string(), alphabet);
+        assert_eq!(
+            ProteinSequence::from_str_iupac(&alphabet.to_lowercase()).unwrap(),
+            p
+        );
+        assert!(ProteinSequence::from_str_iupac("").unwrap().is_empty());
+        for bad in ["1", ".", " ", "MK~"] {
+            assert!(matches!(
+                ProteinSequence::from_str_iupac(bad),
+                Err(TranslationError::BadAminoAcid(_))
+            ));
+        }
+        assert!(matches!(
+            ProteinSequence::from_str_iupac("Mč"),
+            Err(TranslationError::NonAsciiByte(_))
+        ));
+    }
+
+    #[test]
+    fn test_typed_protein() {
+        let p = protein("mkv*");
+        let residues: Vec<_> = p.amino_acids().collect();
+        assert!(matches!(
+            residues[..],
+            [
+                Ok(AminoAcid::M),
+                Ok(AminoAcid::K),
+                Ok(AminoAcid::V),
+                Err(TranslationError::BadAminoAcid('*'))
+            ]
+        ));
+        assert!(TypedProtein::try_from(&p).is_err());
+
+        let p = protein("ACDEFGHIKLMNPQRSTVWY");
+        let typed = TypedProtein::try_from(&p).unwrap();
+        assert_eq!(typed.0, AminoAcid::ALL);
+        assert_eq!(typed.to_string(), p.to_string());
+        assert_eq!(ProteinSequence::from(typed), p);
+    }
+
     #[test]
     fn test_protein_case() {
         assert_eq!(dna("GGG").translate(TranslationTable::Ncbi1), protein("g"));
@@ -745,6 +2802,28 @@ mod tests {
         assert_eq!(dna_strict("actg").windows(10).next(), None);
     }
 
+    #[test]
+    fn test_indexed_windows() {
+        let d = dna("gcantacctaangtnattag");
+        let indexed: Vec<_> = d.indexed_windows(10).collect();
+        assert_eq!(indexed.len(), 11);
+        for (i, (start, window)) in indexed.into_iter().enumerate() {
+            assert_eq!(start, i);
+            assert_eq!(window.as_slice(), &d.as_slice()[i..i + 10]);
+        }
+        let mut slices = d.window_slices(10);
+        assert_eq!(slices.len(), 11);
+        assert_eq!(slices.next_back(), Some((10, &d.as_slice()[10..])));
+        assert_eq!(slices.next(), Some((0, &d.as_slice()[..10])));
+        assert!(d
+            .window_slices(10)
+            .zip(d.indexed_windows(10))
+            .all(|((i, slice), (j, window))| i == j && slice == window.as_slice()));
+
+        assert_eq!(dna("antg").indexed_windows(10).next(), None);
+        assert_eq!(dna("antg").window_slices(10).next(), None);
+    }
+
     #[test]
     fn test_protein_windows() {
         let p = protein("gcantacctaangtnattag");
@@ -768,6 +2847,575 @@ mod tests {
         assert_eq!(protein("antg").windows(10).next(), None);
     }
 
+    #[test]
+    fn test_canonical_key_groups_reverse_complements() {
+        use std::collections::HashMap;
+
+        let mut groups: HashMap<DnaSequenceStrict, Vec<String>> = HashMap::new();
+        for src in ["GGAC", "GTCC", "AAAT", "ATTT", "TTTA"] {
+            groups
+                .entry(dna_strict(src).canonical_key())
+                .or_default()
+                .push(src.to_string());
+        }
+        assert_eq!(groups.len(), 3);
+        assert_eq!(groups[&dna_strict("GTCC")], ["GGAC", "GTCC"]);
+        assert_eq!(groups[&dna_strict("AAAT")], ["AAAT", "ATTT"]);
+        assert_eq!(groups[&dna_strict("TAAA")], ["TTTA"]);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_par_translate() {
+        use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(0);
+        let long: Vec<_> = (0..300_002)
+            .map(|_| *NucleotideAmbiguous::ALL.choose(&mut rng).unwrap())
+            .collect();
+        for len in [0, 5, 300_000, 300_001, 300_002] {
+            let dna = DnaSequence::new(long[..len].to_vec());
+            let tables = [TranslationTable::Ncbi1, TranslationTable::Ncbi11];
+            for table in tables.into_iter().filter(|table| table.is_available()) {
+                assert_eq!(dna.par_translate(table), dna.translate(table));
+            }
+        }
+    }
+
+    #[test]
+    fn test_with_capacity() {
+        let mut dna = DnaSequenceStrict::with_capacity(6);
+        assert!(dna.is_empty());
+        assert!(dna.dna.capacity() >= 6);
+        dna.push(Nucleotide::A);
+        dna.reserve(100);
+        assert!(dna.dna.capacity() >= 101);
+        assert_eq!(dna.to_string(), "A");
+
+        let mut protein = ProteinSequence::with_capacity(2);
+        assert!(protein.amino_acids.capacity() >= 2);
+        protein.push(b'M');
+        protein.reserve(10);
+        assert!(protein.amino_acids.capacity() >= 11);
+        assert_eq!(protein.to_string(), "M");
+    }
+
+    #[test]
+    fn test_into_vec() {
+        let mut dna = DnaSequenceStrict::with_capacity(16);
+        dna.extend("ACGT".parse().unwrap());
+        let ptr = dna.as_ptr();
+        let vec = dna.into_vec();
+        assert_eq!(vec.as_ptr(), ptr);
+        assert!(vec.capacity() >= 16);
+        assert_eq!(DnaSequence::new(vec), dna_strict("ACGT"));
+
+        let p = protein("MSTOP*");
+        let ptr = p.as_ptr();
+        let bytes = p.into_bytes();
+        assert_eq!(bytes.as_ptr(), ptr);
+        assert_eq!(bytes, b"MSTOP*");
+    }
+
+    #[test]
+    fn test_gc3_content() {
+        assert_eq!(dna_strict("").gc3_content(0), 0.0);
+        assert_eq!(dna_strict("GC").gc3_content(0), 0.0);
+        assert_eq!(dna_strict("GCG").gc3_content(1), 0.0);
+        assert_eq!(dna_strict("AAGAACAAT").gc3_content(0), 2.0 / 3.0);
+        // Only the third position counts.
+        assert_eq!(dna_strict("GCAGCT").gc3_content(0), 0.0);
+        assert_eq!(dna_strict("TGCAGCT").gc3_content(1), 0.0);
+        assert_eq!(dna_strict("TGCAGCT").gc3_content(0), 1.0);
+    }
+
+    #[test]
+    fn test_translate_orf() {
+        let table = TranslationTable::Ncbi1;
+        assert!(dna("").translate_orf(table).is_empty());
+        assert!(dna("TAGTAATGA").translate_orf(table).is_empty());
+        assert_eq!(dna("ATGAA").translate_orf(table), [protein("M")]);
+        assert_eq!(
+            dna("TAAATGTRGTAGCCC").translate_orf(table),
+            [protein("MX"), protein("P")]
+        );
+        // Stops depend on the translation table.
+        #[cfg(not(feature = "minimal-tables"))]
+        assert_eq!(
+            dna("ATGTGAAAA").translate_orf(TranslationTable::Ncbi2),
+            [protein("MWK")]
+        );
+    }
+
+    #[test]
+    fn test_palindromes() {
+        let dna: DnaSequenceStrict = "AATTGCGCAATT".parse().unwrap();
+        assert!(dna.is_palindrome());
+        assert!(DnaSequenceStrict::default().is_palindrome());
+        assert_eq!(dna.find_palindromes(12, 100), [(0, 12)]);
+        assert_eq!(dna.find_palindromes(0, 3).len(), 5);
+        assert!(dna.find_palindromes(5, 4).is_empty());
+    }
+
+    #[test]
+    fn test_pairwise_hamming() {
+        assert!(DnaSequenceAmbiguous::pairwise_hamming(&[])
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            DnaSequenceAmbiguous::pairwise_hamming(&[dna("ACNT")]).unwrap(),
+            [[0]]
+        );
+
+        let seqs = [dna("ACNT"), dna("ACGT"), dna("TCGA"), dna("ACNT")];
+        let expected = [[0, 1, 3, 0], [1, 0, 2, 1], [3, 2, 0, 3], [0, 1, 3, 0]];
+        assert_eq!(DnaSequence::pairwise_hamming(&seqs).unwrap(), expected);
+        #[cfg(feature = "rayon")]
+        assert_eq!(DnaSequence::par_pairwise_hamming(&seqs).unwrap(), expected);
+
+        let err = DnaSequence::pairwise_hamming(&[dna("ACGT"), dna("ACGT"), dna("ACG")]);
+        assert_eq!(
+            err.unwrap_err(),
+            LengthMismatch {
+                indices: (0, 2),
+                lengths: (4, 3)
+            }
+        );
+    }
+
+    #[test]
+    fn test_gc_content_and_base_counts() {
+        assert_eq!(dna("").gc_content(), 0.0);
+        assert_eq!(dna("GCSS").gc_content(), 1.0);
+        assert_eq!(dna("ATBB").gc_content(), 1.0 / 3.0);
+        assert_eq!(dna_strict("ACGT").gc_content(), 0.5);
+
+        let counts = dna("anNcA").base_counts();
+        assert_eq!(
+            counts.into_iter().collect::<Vec<_>>(),
+            [
+                (NucleotideAmbiguous::A, 2),
+                (NucleotideAmbiguous::C, 1),
+                (NucleotideAmbiguous::N, 2),
+            ]
+        );
+        assert!(dna_strict("").base_counts().is_empty());
+    }
+
+    #[test]
+    fn test_replace_and_strip_ambiguous() {
+        let d = dna("NACRTN");
+        assert_eq!(d.replace_ambiguous(Nucleotide::G), dna_strict("GACGTG"));
+        assert_eq!(d.strip_ambiguous(), dna_strict("ACT"));
+        assert_eq!(d.strip_ambiguous().len(), d.len() - d.ambiguity_count());
+
+        let mut seen = vec![];
+        let replaced = d.replace_ambiguous_with(|n| {
+            seen.push(n);
+            *n.possibilities().last().unwrap()
+        });
+        assert_eq!(replaced, dna_strict("GACGTG"));
+        assert_eq!(
+            seen,
+            [
+                NucleotideAmbiguous::N,
+                NucleotideAmbiguous::R,
+                NucleotideAmbiguous::N
+            ]
+        );
+
+        assert_eq!(dna("").replace_ambiguous(Nucleotide::A), dna_strict(""));
+        assert_eq!(dna("").strip_ambiguous(), dna_strict(""));
+    }
+
+    #[test]
+    fn test_gapped() {
+        let aligned: DnaSequenceGapped = "ATG---AAA.CC".parse().unwrap();
+        assert_eq!(aligned.to_string(), "ATG---AAA-CC");
+        assert_eq!(aligned.degap(), dna("ATGAAACC"));
+        assert_eq!(aligned.degap_strict(), Ok(dna_strict("ATGAAACC")));
+        assert_eq!(aligned.reverse_complement().to_string(), "GG-TTT---CAT");
+        assert_eq!(aligned.translate(TranslationTable::Ncbi1), protein("M-K-"));
+        assert_eq!(aligned.gc_content(), 3.0 / 8.0);
+        assert!("ATU".parse::<DnaSequenceGapped>().is_err());
+
+        let aligned: DnaSequenceGapped = "ATN-RY.GCN".parse().unwrap();
+        assert_eq!(aligned.to_string(), "ATN-RY-GCN");
+        assert_eq!(aligned.degap(), dna("ATNRYGCN"));
+        assert_eq!(aligned.degap_strict().unwrap_err().position, 2);
+        assert_eq!(aligned.reverse_complement().to_string(), "NGC-RY-NAT");
+        assert_eq!(aligned.translate(TranslationTable::Ncbi1), protein("X--"));
+        assert_eq!(aligned.gc_content(), dna("ATNRYGCN").gc_content());
+        assert_eq!(
+            aligned.molecular_weight(),
+            dna("ATNRYGCN").molecular_weight()
+        );
+        assert_eq!(DnaSequenceGapped::default().gc_content(), 0.0);
+        assert_eq!(
+            "---"
+                .parse::<DnaSequenceGapped>()
+                .unwrap()
+                .molecular_weight(),
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_positions() {
+        let d = dna("NACRTNN");
+        assert!(d.positions_of(NucleotideAmbiguous::N).eq([0, 5, 6]));
+        assert!(d.positions_of(NucleotideAmbiguous::G).next().is_none());
+        assert!(d
+            .positions_matching(NucleotideAmbiguous::G)
+            .eq([0, 3, 5, 6]));
+        assert!(d
+            .positions_matching(NucleotideAmbiguous::W)
+            .eq([0, 1, 3, 4, 5, 6]));
+        assert!(dna_strict("").positions_of(Nucleotide::A).next().is_none());
+
+        let translated = dna_strict("ATGTAAGGGTGA").translate(TranslationTable::Ncbi1);
+        assert!(translated.positions_of(b'*').eq([1, 3]));
+    }
+
+    #[test]
+    fn test_runs() {
+        assert!(dna("").runs(NucleotideAmbiguous::N).is_empty());
+        assert!(dna("ACGT").runs(NucleotideAmbiguous::N).is_empty());
+        assert_eq!(dna("NNNN").runs(NucleotideAmbiguous::N), [(0, 4)]);
+        assert_eq!(
+            dna("NANNCN").runs(NucleotideAmbiguous::N),
+            [(0, 1), (2, 4), (5, 6)]
+        );
+        assert_eq!(
+            dna_strict("GGATGG").runs_where(|n| n == Nucleotide::G),
+            [(0, 2), (4, 6)]
+        );
+    }
+
+    #[test]
+    fn test_complexity() {
+        assert_eq!(dna("").shannon_entropy(), 0.0);
+        assert_eq!(dna("NNNNNN").shannon_entropy(), 0.0);
+        assert_eq!(dna("ACGTACGT").shannon_entropy(), 2.0);
+        assert_eq!(dna("ACGTNRSY").shannon_entropy(), 3.0);
+        assert_eq!(dna_strict("AATT").shannon_entropy(), 1.0);
+
+        assert_eq!(dna("").dust_score(), 0.0);
+        assert_eq!(dna("AAA").dust_score(), 0.0);
+        assert_eq!(dna("AAAA").dust_score(), 1.0);
+        let homopolymer = dna_strict(&"A".repeat(64));
+        assert_eq!(homopolymer.dust_score(), 31.0);
+        let varied = dna_strict("ATGGCGTACCTAGGTCAAAGCTTCGAGTTCAGCATGCGATTACAGCCGTATACTGGACCAGTGA");
+        assert!(varied.dust_score() < homopolymer.dust_score());
+        assert!(varied.dust_score() < 1.0, "{}", varied.dust_score());
+    }
+
+    #[test]
+    fn test_mixed_equality() {
+        assert_eq!(dna("GATTACA"), dna_strict("GATTACA"));
+        assert_eq!(dna_strict("GATTACA"), dna("gattaca"));
+        assert_ne!(dna("GATTACA"), dna_strict("GATTAC"));
+        assert_ne!(dna("GATTNCA"), dna_strict("GATTACA"));
+        assert_ne!(dna_strict("GATTACA"), dna("GATTNCA"));
+        assert_eq!(dna(""), dna_strict(""));
+
+        assert!(dna("GATTNCA").is_compatible_with(&dna_strict("GATTACA")));
+        assert!(dna("RYSWKMBDHVN").is_compatible_with(&dna_strict("GCCTGATAAAT")));
+        assert!(!dna("RYSWKMBDHVN").is_compatible_with(&dna_strict("CCCTGATAAAT")));
+        assert!(!dna("NN").is_compatible_with(&dna_strict("A")));
+    }
+
+    #[test]
+    fn test_strict_ambiguous_conversion() {
+        let strict = dna_strict("GATTACA");
+        let ambiguous = DnaSequenceAmbiguous::from(strict.clone());
+        assert_eq!(ambiguous, strict);
+        assert_eq!(DnaSequenceStrict::try_from(ambiguous).unwrap(), strict);
+        assert_eq!(
+            DnaSequenceAmbiguous::from(dna_strict("")),
+            DnaSequenceAmbiguous::default()
+        );
+
+        let err = DnaSequenceStrict::try_from(dna("GATTACR")).unwrap_err();
+        assert_eq!(err.position, 6);
+        assert!(matches!(
+            err.error,
+            TranslationError::UnexpectedAmbiguousNucleotide('R')
+        ));
+    }
+
+    #[test]
+    fn test_consensus() {
+        assert_eq!(
+            DnaSequenceAmbiguous::consensus::<Nucleotide>(&[]),
+            DnaSequenceAmbiguous::default()
+        );
+        assert_eq!(DnaSequenceAmbiguous::consensus(&[dna("ACRT")]), dna("ACRT"));
+        assert_eq!(
+            DnaSequenceAmbiguous::consensus(&[dna("ACRT"), dna("TCYT"), dna("ACAB")]),
+            dna("WCNB")
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_consensus_unaligned() {
+        DnaSequenceAmbiguous::consensus(&[dna_strict("ACGT"), dna_strict("ACG")]);
+    }
+
+    #[test]
+    fn test_codon_usage() {
+        let d = dna("AAANAAAAA");
+        let usage = d.codon_usage(0);
+        assert_eq!(usage.len(), 2);
+        assert_eq!(usage[&CodonAmbiguous::from_str("AAA").unwrap()], 2);
+        assert_eq!(usage[&CodonAmbiguous::from_str("NAA").unwrap()], 1);
+        assert_eq!(d.codon_usage(1).values().sum::<usize>(), 2);
+        assert!(dna("AA").codon_usage(2).is_empty());
+        assert!(dna("").codon_usage(2).is_empty());
+
+        let frequencies = dna_strict("ATGATGTAA").codon_frequencies(0);
+        assert_eq!(frequencies[&Codon::from_str("ATG").unwrap()], 2.0 / 3.0);
+        assert_eq!(frequencies[&Codon::from_str("TAA").unwrap()], 1.0 / 3.0);
+        assert!(dna_strict("").codon_frequencies(0).is_empty());
+    }
+
+    #[test]
+    fn test_protein_as_bytes() {
+        fn byte_len(bytes: impl AsRef<[u8]>) -> usize {
+            bytes.as_ref().len()
+        }
+
+        let p = protein("MSTOP*");
+        assert_eq!(byte_len(&p), 6);
+        assert!(p.starts_with(b"MST"));
+        assert_eq!(&*p, b"MSTOP*");
+        assert_eq!(p.get(1..3), Some(&b"ST"[..]));
+    }
+
+    #[test]
+    fn test_into_iter() {
+        let seq = dna("ACGTN");
+        let mut complement = vec![];
+        for n in &seq {
+            complement.push(n.complement());
+        }
+        assert!((&seq).into_iter().complement().eq(complement));
+        assert_eq!(seq.clone().into_iter().collect::<Vec<_>>(), seq.as_slice());
+
+        let p = protein("MSTOP*");
+        let aas: Vec<u8> = (&p).into_iter().collect();
+        assert_eq!(aas, b"MSTOP*");
+    }
+
+    #[test]
+    fn test_reverse_complement_iter() {
+        let dna = dna("ACGTNRYK");
+        assert_eq!(dna.reverse_complement_iter().len(), 8);
+        assert!(dna
+            .reverse_complement_iter()
+            .eq(crate::trans_table::reverse_complement(dna.as_slice())));
+        assert!(dna
+            .reverse_complement_iter()
+            .rev()
+            .eq(dna.as_slice().iter().map(|n| n.complement())));
+    }
+
+    #[test]
+    fn test_whitespace_policy() {
+        let bytes = b" A\tC\r\nG\x0cT ";
+        let skipped = DnaSequenceStrict::from_bytes_with(bytes, WhitespacePolicy::Skip).unwrap();
+        assert_eq!(skipped, dna_strict("ACGT"));
+        let err = DnaSequenceStrict::from_bytes_with(bytes, WhitespacePolicy::SkipSpacesTabs)
+            .unwrap_err();
+        assert_eq!(err.position, 4);
+        assert!(matches!(err.error, TranslationError::BadNucleotide('\r')));
+        let err = DnaSequenceStrict::from_bytes_with(bytes, WhitespacePolicy::Reject).unwrap_err();
+        assert_eq!(err.position, 0);
+        assert_eq!(
+            DnaSequenceAmbiguous::from_bytes_with(b"AC GN", WhitespacePolicy::default()).unwrap(),
+            dna("ACGN")
+        );
+    }
+
+    #[test]
+    fn test_case_policy() {
+        let bytes = b"AC gt";
+        let whitespace = WhitespacePolicy::default();
+        for case in [CasePolicy::AllowLowercase, CasePolicy::PreserveCase] {
+            let parsed = DnaSequenceStrict::from_bytes_with_case(bytes, whitespace, case).unwrap();
+            assert_eq!(parsed, dna_strict("ACGT"));
+        }
+        let err =
+            DnaSequenceStrict::from_bytes_with_case(bytes, whitespace, CasePolicy::RejectLowercase)
+                .unwrap_err();
+        assert_eq!(err.position, 3);
+        assert!(matches!(
+            err.error,
+            TranslationError::UnexpectedLowercase('g')
+        ));
+        assert_eq!(
+            DnaSequenceAmbiguous::from_bytes_with_case(
+                b"ACGN",
+                whitespace,
+                CasePolicy::RejectLowercase
+            )
+            .unwrap(),
+            dna("ACGN")
+        );
+    }
+
+    #[cfg(feature = "md5")]
+    #[test]
+    fn test_md5_hex() {
+        let empty = DnaSequenceStrict::default();
+        assert_eq!(empty.md5_hex(), "d41d8cd98f00b204e9800998ecf8427e");
+        assert_eq!(dna("AAAA").md5_hex(), "098890dde069e9abad63f19a0d9e1f32");
+    }
+
+    #[cfg(feature = "sha1")]
+    #[test]
+    fn test_seguid() {
+        let empty = DnaSequenceStrict::default();
+        assert_eq!(empty.seguid(), "2jmj7l5rSw0yVb/vlWAYkK/YBwk");
+        assert_eq!(dna("AAAA").seguid(), "4lEhcqv4zJ9n/dSetsrPLfcbutM");
+        assert_eq!(dna("AAAA").seguid(), dna_strict("aaaa").seguid());
+        assert_ne!(
+            dna_strict("AAAA").canonical_seguid(),
+            dna_strict("CCCC").canonical_seguid()
+        );
+        assert_eq!(
+            dna_strict("GATTACA").canonical_seguid(),
+            "YgkkrUbyuSFUWnB0Zh3r73MUxiE"
+        );
+    }
+
+    #[test]
+    fn test_trim_ends() {
+        assert_eq!(dna("NNACNGTNN").trim_ambiguous_ends(), dna("ACNGT"));
+        assert_eq!(dna("ACGT").trim_ambiguous_ends(), dna("ACGT"));
+        assert_eq!(dna("NRYN").trim_ambiguous_ends(), dna(""));
+        assert_eq!(dna("").trim_ambiguous_ends(), dna(""));
+        assert_eq!(dna("NA").trim_ambiguous_ends(), dna("A"));
+        let strict = dna_strict("GGATCC");
+        assert_eq!(
+            strict.trim_ends(|&n| n == Nucleotide::G),
+            dna_strict("ATCC")
+        );
+    }
+
+    #[test]
+    fn test_slice_codons() {
+        let seq = dna("ATGNNNTAAGC");
+        assert_eq!(seq.slice_codons(0, 0), dna(""));
+        assert_eq!(seq.slice_codons(1, 1), dna("NNN"));
+        assert_eq!(seq.slice_codons(0, 3), seq.trim_to_codon());
+        assert_eq!(
+            seq.slice_codons(1, 2).translate(TranslationTable::Ncbi1),
+            protein("X*")
+        );
+        for len in 0..seq.len() {
+            let prefix = DnaSequenceAmbiguous::new(seq.as_slice()[..len].to_vec());
+            assert!(prefix
+                .trim_to_codon()
+                .iter()
+                .eq((&prefix).into_iter().trimmed_to_codon()));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_slice_codons_out_of_bounds() {
+        dna("ATGNNNTAAGC").slice_codons(2, 2);
+    }
+
+    #[test]
+    fn test_find_all_mismatch() {
+        let dna = dna_strict("ACGTACGAACGT");
+        let pattern = dna_strict("ACGT");
+        assert_eq!(
+            dna.find_all_mismatch(pattern.as_slice(), 0),
+            [(0, 0), (8, 0)]
+        );
+        assert_eq!(
+            dna.find_all_mismatch(pattern.as_slice(), 1),
+            [(0, 0), (4, 1), (8, 0)]
+        );
+        let all = dna.find_all_mismatch(pattern.as_slice(), 4);
+        assert_eq!(all.len(), 9);
+        for (position, mismatches) in all {
+            let window = &dna.as_slice()[position..position + 4];
+            let expected = window
+                .iter()
+                .zip(pattern.as_slice())
+                .filter(|(a, b)| a != b);
+            assert_eq!(mismatches, expected.count());
+        }
+        assert_eq!(dna.find_all_mismatch(&[], 0).len(), 13);
+        assert!(pattern.find_all_mismatch(dna.as_slice(), 12).is_empty());
+    }
+
+    #[test]
+    fn test_optimize_codons() {
+        let usage = CodonUsageTable::from_dna(&dna_strict("GGGGGAGGA"));
+        let p = protein("GAG*");
+        let optimized = p.optimize_codons(&usage, TranslationTable::Ncbi1).unwrap();
+        assert_eq!(optimized, dna_strict("GGAGCAGGATAA"));
+        for &table in TranslationTable::AVAILABLE {
+            let stop = if table.stop_codons().is_empty() {
+                ""
+            } else {
+                "*"
+            };
+            let p = protein(&format!("ACDEFGHIKLMNPQRSTVWY{stop}"));
+            let optimized = p.optimize_codons(&usage, table).unwrap();
+            assert_eq!(optimized.translate(table), p);
+        }
+        assert!(matches!(
+            protein("MXK").optimize_codons(&usage, TranslationTable::Ncbi1),
+            Err(TranslationError::BadAminoAcid('X'))
+        ));
+        assert_eq!(
+            protein("")
+                .optimize_codons(&usage, TranslationTable::Ncbi1)
+                .unwrap(),
+            dna_strict("")
+        );
+    }
+
+    #[test]
+    fn test_windows_exact_size() {
+        let d = dna("gcantacctaangtnattag");
+        let mut windows = d.windows(10);
+        assert_eq!(windows.len(), 11);
+        assert_eq!(windows.len(), d.windows_count(10));
+        assert_eq!(windows.get(2), Some(dna("antacctaan")));
+        assert_eq!(windows.next_back(), Some(dna("angtnattag")));
+        assert_eq!(windows.next(), Some(dna("gcantaccta")));
+        assert_eq!(windows.len(), 9);
+        assert_eq!(windows.get(0), Some(dna("cantacctaa")));
+        assert_eq!(windows.get(9), None);
+        assert_eq!(dna("antg").windows(10).len(), 0);
+        assert_eq!(dna("antg").windows_count(10), 0);
+
+        let p = protein("gcantacctaangtnattag");
+        let mut windows = p.windows(10);
+        assert_eq!(windows.len(), 11);
+        assert_eq!(windows.len(), p.windows_count(10));
+        assert_eq!(windows.get(10), Some(protein("angtnattag")));
+        assert_eq!(windows.next_back(), Some(protein("angtnattag")));
+        assert_eq!(windows.len(), 10);
+        assert_eq!(windows.rev().count(), 10);
+        assert_eq!(protein("antg").windows_count(4), 1);
+        assert_eq!(protein("antg").windows_count(10), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "window length must be nonzero")]
+    fn test_windows_count_zero() {
+        dna("antg").windows_count(0);
+    }
+
     #[test]
     fn test_empty_spaces() {
         // this test will unwrap() if it cannot parse the DNA
@@ -785,12 +3433,72 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::op_ref)] // newer clippy flags `== &[..]`, but keep the test as written
     fn test_as_ref() {
         let sequence = dna("ac");
         let nucleotides: &[NucleotideAmbiguous] = sequence.as_ref();
         assert!(nucleotides == &[NucleotideAmbiguous::A, NucleotideAmbiguous::C]);
     }
 
+    #[test]
+    fn test_expansion_count() {
+        assert_eq!(dna("").expansion_count(), Some(1));
+        assert_eq!(dna("ATCG").expansion_count(), Some(1));
+        assert_eq!(dna("ATBCGYAC").expansion_count(), Some(6));
+        assert_eq!(dna("NNNNWB").expansion_count(), Some(4 * 4 * 4 * 4 * 2 * 3));
+
+        // Past usize::MAX, but still within u128
+        assert_eq!(dna(&"N".repeat(32)).expansion_count(), Some(1 << 64));
+        assert_eq!(dna(&"N".repeat(63)).expansion_count(), Some(1 << 126));
+        assert_eq!(dna(&"N".repeat(64)).expansion_count(), None);
+    }
+
+    #[test]
+    fn test_try_expand() {
+        assert_eq!(dna("ATCG").try_expand(1).unwrap(), vec![dna_strict("ATCG")]);
+        assert_eq!(
+            dna("AWC").try_expand(2).unwrap(),
+            vec![dna_strict("AAC"), dna_strict("ATC")]
+        );
+        assert_eq!(
+            dna("AWC").try_expand(1).unwrap_err(),
+            TooManyExpansions {
+                count: Some(2),
+                limit: 1
+            }
+        );
+        assert_eq!(
+            dna(&"N".repeat(64)).try_expand(usize::MAX).unwrap_err(),
+            TooManyExpansions {
+                count: None,
+                limit: usize::MAX
+            }
+        );
+        assert_eq!(
+            dna("NN").try_expand(4).unwrap_err().to_string(),
+            "too many expansions: 16 exceeds limit of 4"
+        );
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_sample_expansion() {
+        use rand::SeedableRng;
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+        let src = dna("ATBCGYACN");
+        let expansions: Vec<DnaSequenceStrict> = src.try_expand(100).unwrap();
+
+        let samples = src.sample_expansions(200, &mut rng);
+        assert_eq!(samples.len(), 200);
+        assert!(samples.iter().all(|s| expansions.contains(s)));
+        // With 24 possible expansions, 200 samples should find more than one.
+        assert!(samples.iter().any(|s| *s != samples[0]));
+
+        assert_eq!(dna("ATCG").sample_expansion(&mut rng), dna_strict("ATCG"));
+        assert_eq!(dna("").sample_expansion(&mut rng), dna_strict(""));
+    }
+
     #[cfg(feature = "serde")]
     #[test]
     fn test_serde_json() {
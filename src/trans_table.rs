@@ -1,9 +1,15 @@
 // Copyright 2021-2024 SecureDNA Stiftung (SecureDNA Foundation) <licensing@securedna.org>
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
+use alloc::borrow::ToOwned;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
 use crate::{
-    errors::TranslationError,
-    nucleotide::{Codon, CodonAmbiguous, NucleotideLike},
+    errors::{Positioned, TranslationError},
+    nucleotide::{Codon, CodonAmbiguous, Nucleotide, NucleotideLike},
 };
 
 /// Identifies a translation table for turning codons into amino acids.
@@ -72,6 +78,58 @@ pub enum TranslationTable {
     Ncbi33,
 }
 
+/// Whether an ambiguous codon codes for a stop, as returned by
+/// [`TranslationTable::classify_ambiguous_codon`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, core::hash::Hash)]
+pub enum CodonClass {
+    /// Every expansion of the codon is a stop codon.
+    DefinitelyStop,
+    /// No expansion of the codon is a stop codon. This holds what the codon translates to,
+    /// which is an ambiguity code like `X` if its expansions code for different amino acids.
+    DefinitelyResidue(u8),
+    /// Some expansions of the codon are stop codons, and some aren't.
+    Mixed,
+}
+
+/// The symbols [`TranslationTable::translate_dna_with_symbols`] writes for stop codons and
+/// for codons that can't be translated to a single amino acid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TranslationSymbols {
+    /// The symbol for a stop codon, `*` by default.
+    pub stop: u8,
+    /// The symbol for an ambiguous codon with no common amino acid, `X` by default.
+    pub unknown: u8,
+}
+
+impl TranslationSymbols {
+    /// Initializes symbols to their defaults, the ones [`TranslationTable::translate_dna`]
+    /// uses: `*` for stops and `X` for unknowns.
+    pub fn new() -> Self {
+        Self {
+            stop: b'*',
+            unknown: b'X',
+        }
+    }
+
+    /// Changes the symbol for [`Self::stop`]
+    pub fn stop(mut self, stop: u8) -> Self {
+        self.stop = stop;
+        self
+    }
+
+    /// Changes the symbol for [`Self::unknown`]
+    pub fn unknown(mut self, unknown: u8) -> Self {
+        self.unknown = unknown;
+        self
+    }
+}
+
+impl Default for TranslationSymbols {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[repr(transparent)]
 pub struct CodonIdx(usize);
 
@@ -108,9 +166,125 @@ impl TranslationTable {
     // Number of NCBI translation tables (they go up to 33, but there's gaps in the numbering)
     pub const N_TRANS_TABLES: usize = 27;
     pub const LOOKUP_SIZE: usize = Self::CODONS_PER_TABLE * Self::N_TRANS_TABLES;
+
+    /// Every supported table, in ascending order of NCBI number.
+    ///
+    /// This has more entries than [`Self::N_TRANS_TABLES`], since tables 7 and 8 are aliases.
+    ///
+    /// ```
+    /// use quickdna::TranslationTable;
+    ///
+    /// for table in TranslationTable::ALL {
+    ///     println!("{:>2}  {}", u8::from(table), table.description());
+    /// }
+    /// ```
+    pub const ALL: [Self; 29] = [
+        Self::Ncbi1,
+        Self::Ncbi2,
+        Self::Ncbi3,
+        Self::Ncbi4,
+        Self::Ncbi5,
+        Self::Ncbi6,
+        Self::Ncbi7,
+        Self::Ncbi8,
+        Self::Ncbi9,
+        Self::Ncbi10,
+        Self::Ncbi11,
+        Self::Ncbi12,
+        Self::Ncbi13,
+        Self::Ncbi14,
+        Self::Ncbi15,
+        Self::Ncbi16,
+        Self::Ncbi21,
+        Self::Ncbi22,
+        Self::Ncbi23,
+        Self::Ncbi24,
+        Self::Ncbi25,
+        Self::Ncbi26,
+        Self::Ncbi27,
+        Self::Ncbi28,
+        Self::Ncbi29,
+        Self::Ncbi30,
+        Self::Ncbi31,
+        Self::Ncbi32,
+        Self::Ncbi33,
+    ];
     /// Generated by bin/gen_table.rs, layout is all codons for table 1, then all codons for table 2, etc.
-    const TRANSLATION_TABLES: &'static [u8; Self::LOOKUP_SIZE] = include_bytes!("tables.dat");
+    #[cfg(not(feature = "minimal-tables"))]
+    const TRANSLATION_TABLES: &'static [u8] = include_bytes!("tables.dat");
+    #[cfg(feature = "minimal-tables")]
+    const TRANSLATION_TABLES: &'static [u8] = &Self::NCBI1_TABLE;
 
+    /// The lookup table for the standard code ([`Self::Ncbi1`]), indexed by the 4-bit
+    /// [`NucleotideLike::bits`] of each nucleotide of a codon, most significant first.
+    ///
+    /// This is copied out of the full table data at compile time, so only these 4096 bytes end
+    /// up in the binary when the `minimal-tables` feature is enabled.
+    pub const NCBI1_TABLE: [u8; Self::CODONS_PER_TABLE] = {
+        let all: &[u8; Self::LOOKUP_SIZE] = include_bytes!("tables.dat");
+        let mut table = [0; Self::CODONS_PER_TABLE];
+        let mut i = 0;
+        while i < Self::CODONS_PER_TABLE {
+            table[i] = all[i];
+            i += 1;
+        }
+        table
+    };
+
+    /// Translate `codon` with the standard code ([`Self::Ncbi1`]). Unlike
+    /// [`to_fn`](Self::to_fn), this can be used in `const` contexts.
+    ///
+    /// ```
+    /// use quickdna::{Codon, Nucleotide::*, TranslationTable};
+    ///
+    /// const MET: u8 = TranslationTable::translate_ncbi1(Codon([A, T, G]));
+    /// assert_eq!(MET, b'M');
+    /// ```
+    pub const fn translate_ncbi1(codon: Codon) -> u8 {
+        let [a, b, c] = codon.0;
+        Self::NCBI1_TABLE[((a as usize) << 8) | ((b as usize) << 4) | c as usize]
+    }
+
+    /// Translate an ambiguous `codon` with the standard code ([`Self::Ncbi1`]), like
+    /// [`translate_ncbi1`](Self::translate_ncbi1).
+    ///
+    /// ```
+    /// use quickdna::{CodonAmbiguous, NucleotideAmbiguous::*, TranslationTable};
+    ///
+    /// const ALA: u8 = TranslationTable::translate_ambiguous_ncbi1(CodonAmbiguous([G, C, N]));
+    /// assert_eq!(ALA, b'A');
+    /// ```
+    pub const fn translate_ambiguous_ncbi1(codon: CodonAmbiguous) -> u8 {
+        let [a, b, c] = codon.0;
+        Self::NCBI1_TABLE[((a as usize) << 8) | ((b as usize) << 4) | c as usize]
+    }
+
+    /// Every table that can translate: [`Self::ALL`], or only [`Self::Ncbi1`] and its alias
+    /// [`Self::Ncbi8`] with the `minimal-tables` feature.
+    #[cfg(not(feature = "minimal-tables"))]
+    pub const AVAILABLE: &'static [Self] = &Self::ALL;
+    /// Every table that can translate: [`Self::ALL`], or only [`Self::Ncbi1`] and its alias
+    /// [`Self::Ncbi8`] with the `minimal-tables` feature.
+    #[cfg(feature = "minimal-tables")]
+    pub const AVAILABLE: &'static [Self] = &[Self::Ncbi1, Self::Ncbi8];
+
+    /// Whether this table is in [`Self::AVAILABLE`], so that translating with it won't panic.
+    pub fn is_available(self) -> bool {
+        Self::AVAILABLE.contains(&self)
+    }
+
+    #[cfg(feature = "minimal-tables")]
+    fn table_index(self) -> usize {
+        match self {
+            Self::Ncbi1 | Self::Ncbi8 => 0,
+            _ => panic!(
+                "translation table {} is unavailable with the minimal-tables feature",
+                u8::from(self)
+            ),
+        }
+    }
+
+    #[cfg(not(feature = "minimal-tables"))]
     fn table_index(self) -> usize {
         match self {
             // table 8 is an alias for table 1
@@ -145,6 +319,41 @@ impl TranslationTable {
         }
     }
 
+    /// A short description of this table, e.g. `"The standard code"` for [`Self::Ncbi1`].
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::Ncbi1 => "The standard code",
+            Self::Ncbi2 => "The vertebrate mitochondrial code",
+            Self::Ncbi3 => "The yeast mitochondrial code",
+            Self::Ncbi4 => "The mold, protozoan, and coelenterate mitochondrial code and the mycoplasma/spiroplasma code",
+            Self::Ncbi5 => "The invertebrate mitochondrial code",
+            Self::Ncbi6 => "The ciliate, dasycladacean and hexamita nuclear code",
+            Self::Ncbi7 => "The kinetoplast code; cf. table 4.",
+            Self::Ncbi8 => "Same as table 1.",
+            Self::Ncbi9 => "The echinoderm and flatworm mitochondrial code",
+            Self::Ncbi10 => "The euplotid nuclear code",
+            Self::Ncbi11 => "The bacterial, archaeal and plant plastid code",
+            Self::Ncbi12 => "The alternative yeast nuclear code",
+            Self::Ncbi13 => "The ascidian mitochondrial code",
+            Self::Ncbi14 => "The alternative flatworm mitochondrial code",
+            Self::Ncbi15 => "The Blepharisma nuclear code",
+            Self::Ncbi16 => "The chlorophycean mitochondrial code",
+            Self::Ncbi21 => "The trematode mitochondrial code",
+            Self::Ncbi22 => "The Scenedesmus obliquus mitochondrial code",
+            Self::Ncbi23 => "The Thraustochytrium mitochondrial code",
+            Self::Ncbi24 => "The Pterobranchia mitochondrial code",
+            Self::Ncbi25 => "The candidate division SR1 and gracilibacteria code",
+            Self::Ncbi26 => "The Pachysolen tannophilus nuclear code",
+            Self::Ncbi27 => "The karyorelict nuclear code",
+            Self::Ncbi28 => "The Condylostoma nuclear code",
+            Self::Ncbi29 => "The Mesodinium nuclear code",
+            Self::Ncbi30 => "The Peritrich nuclear code",
+            Self::Ncbi31 => "The Blastocrithidia nuclear code",
+            Self::Ncbi32 => "The Balanophoraceae plastid code",
+            Self::Ncbi33 => "The Cephalodiscidae mitochondrial code",
+        }
+    }
+
     /// Convert this table to a callable that maps codons to amino acids
     ///
     /// Currently, amino acids are represented as [`u8`]s containing the ascii
@@ -162,10 +371,14 @@ impl TranslationTable {
     /// let aas = dna.iter().codons().map(ncbi1);
     /// assert!(aas.eq([b'I', b'D']));
     /// ```
+    ///
+    /// # Panics
+    ///
+    /// With the `minimal-tables` feature, panics for any table but [`Self::Ncbi1`] (or its
+    /// alias [`Self::Ncbi8`]). The same goes for the other methods that translate codons.
+    /// Check [`Self::is_available`] first, or get the table from [`TryFrom<u8>`], to avoid this.
     pub fn to_fn<N: NucleotideLike, C: Into<[N; 3]>>(self) -> impl Copy + Fn(C) -> u8 {
-        let start = self.table_index() * Self::CODONS_PER_TABLE;
-        let end = start + Self::CODONS_PER_TABLE;
-        let table = &Self::TRANSLATION_TABLES[start..end];
+        let table = self.codon_lookup();
         |codon| {
             let nucleotides: [N; 3] = codon.into();
             let CodonIdx(i) = nucleotides.into();
@@ -173,33 +386,218 @@ impl TranslationTable {
         }
     }
 
+    /// Translate a single `codon` to the ASCII code of its amino acid.
+    ///
+    /// ```
+    /// use quickdna::{Codon, Nucleotide::*, TranslationTable};
+    ///
+    /// assert_eq!(TranslationTable::Ncbi1.translate_codon(Codon([T, G, A])), b'*');
+    /// # #[cfg(not(feature = "minimal-tables"))]
+    /// assert_eq!(TranslationTable::Ncbi2.translate_codon(Codon([T, G, A])), b'W');
+    /// ```
+    pub fn translate_codon(self, codon: Codon) -> u8 {
+        self.lookup(codon.into())
+    }
+
+    /// Translate a single ambiguous `codon` to the ASCII code of its amino acid, which is
+    /// an ambiguity code like `X` if its expansions code for different amino acids.
+    ///
+    /// ```
+    /// use quickdna::{CodonAmbiguous, NucleotideAmbiguous::*, TranslationTable};
+    ///
+    /// let table = TranslationTable::Ncbi1;
+    /// assert_eq!(table.translate_codon_ambiguous(CodonAmbiguous([G, C, N])), b'A');
+    /// assert_eq!(table.translate_codon_ambiguous(CodonAmbiguous([N, N, N])), b'X');
+    /// ```
+    pub fn translate_codon_ambiguous(self, codon: CodonAmbiguous) -> u8 {
+        self.lookup(codon.into())
+    }
+
+    /// This table's amino acids, indexed by the bits of a codon's nucleotides, as in
+    /// [`CodonIdx`].
+    pub(crate) fn codon_lookup(self) -> &'static [u8] {
+        let start = self.table_index() * Self::CODONS_PER_TABLE;
+        &Self::TRANSLATION_TABLES[start..start + Self::CODONS_PER_TABLE]
+    }
+
+    fn lookup(self, CodonIdx(i): CodonIdx) -> u8 {
+        Self::TRANSLATION_TABLES[self.table_index() * Self::CODONS_PER_TABLE + i]
+    }
+
+    /// Whether `codon` is a stop codon in this table.
+    ///
+    /// ```
+    /// use quickdna::{Codon, TranslationTable};
+    ///
+    /// let tga: Codon = "TGA".parse().unwrap();
+    /// assert!(TranslationTable::Ncbi1.is_stop_codon(tga));
+    /// // TGA codes for tryptophan in vertebrate mitochondria
+    /// # #[cfg(not(feature = "minimal-tables"))]
+    /// assert!(!TranslationTable::Ncbi2.is_stop_codon(tga));
+    /// ```
+    pub fn is_stop_codon(self, codon: Codon) -> bool {
+        self.to_fn()(codon) == b'*'
+    }
+
+    /// All stop codons in this table, in the order of [`Nucleotide::ALL`].
+    ///
+    /// ```
+    /// use quickdna::TranslationTable;
+    ///
+    /// let stops: Vec<String> = TranslationTable::Ncbi1
+    ///     .stop_codons()
+    ///     .iter()
+    ///     .map(|codon| codon.to_string())
+    ///     .collect();
+    /// assert_eq!(stops, ["TAA", "TAG", "TGA"]);
+    /// ```
+    pub fn stop_codons(self) -> Vec<Codon> {
+        let mut stops = vec![];
+        for a in Nucleotide::ALL {
+            for b in Nucleotide::ALL {
+                for c in Nucleotide::ALL {
+                    let codon = Codon([a, b, c]);
+                    if self.is_stop_codon(codon) {
+                        stops.push(codon);
+                    }
+                }
+            }
+        }
+        stops
+    }
+
+    /// The codons that translate differently in this table and `other`, in the order of
+    /// [`Nucleotide::ALL`], each with its amino acid in this table and then in `other`.
+    ///
+    /// ```
+    /// use quickdna::TranslationTable;
+    ///
+    /// # #[cfg(not(feature = "minimal-tables"))] {
+    /// let diff: Vec<(String, char, char)> = TranslationTable::Ncbi1
+    ///     .diff(TranslationTable::Ncbi2)
+    ///     .into_iter()
+    ///     .map(|(codon, this, other)| (codon.to_string(), this as char, other as char))
+    ///     .collect();
+    /// assert_eq!(
+    ///     diff,
+    ///     [
+    ///         ("ATA".to_owned(), 'I', 'M'),
+    ///         ("AGA".to_owned(), 'R', '*'),
+    ///         ("AGG".to_owned(), 'R', '*'),
+    ///         ("TGA".to_owned(), '*', 'W'),
+    ///     ]
+    /// );
+    /// # }
+    /// ```
+    pub fn diff(self, other: Self) -> Vec<(Codon, u8, u8)> {
+        let (this_fn, other_fn) = (self.to_fn(), other.to_fn());
+        let mut diff = vec![];
+        for a in Nucleotide::ALL {
+            for b in Nucleotide::ALL {
+                for c in Nucleotide::ALL {
+                    let codon = Codon([a, b, c]);
+                    let (this, other) = (this_fn(codon), other_fn(codon));
+                    if this != other {
+                        diff.push((codon, this, other));
+                    }
+                }
+            }
+        }
+        diff
+    }
+
+    /// Whether any expansion of `codon` is a stop codon in this table.
+    ///
+    /// ```
+    /// use quickdna::{CodonAmbiguous, TranslationTable};
+    ///
+    /// // TAA is a stop codon, but TCA isn't
+    /// let codon: CodonAmbiguous = "TMA".parse().unwrap();
+    /// assert!(TranslationTable::Ncbi1.might_be_stop(codon));
+    /// assert!(TranslationTable::Ncbi1.translate_dna(&codon.0) != b"*");
+    /// ```
+    pub fn might_be_stop(self, codon: CodonAmbiguous) -> bool {
+        codon.possibilities().any(|codon| self.is_stop_codon(codon))
+    }
+
+    /// Classify `codon` by whether all, none, or only some of its expansions are stop
+    /// codons in this table.
+    ///
+    /// Translation collapses a codon like `TRA` (`TAA` or `TGA`) to `*` and a codon like
+    /// `TRG` (`TAG` or `TGG`) to `X`, so screening that must treat "might be a stop"
+    /// differently from "is a stop" should use this instead.
+    ///
+    /// ```
+    /// use quickdna::{CodonClass, TranslationTable};
+    ///
+    /// let table = TranslationTable::Ncbi1;
+    /// let classify = |codon: &str| table.classify_ambiguous_codon(codon.parse().unwrap());
+    /// assert_eq!(classify("TRA"), CodonClass::DefinitelyStop);
+    /// assert_eq!(classify("TRG"), CodonClass::Mixed);
+    /// assert_eq!(classify("GCN"), CodonClass::DefinitelyResidue(b'A'));
+    /// assert_eq!(classify("RAY"), CodonClass::DefinitelyResidue(b'B'));
+    /// ```
+    pub fn classify_ambiguous_codon(self, codon: CodonAmbiguous) -> CodonClass {
+        let (mut stops, mut residues) = (0, 0);
+        for codon in codon.possibilities() {
+            if self.is_stop_codon(codon) {
+                stops += 1;
+            } else {
+                residues += 1;
+            }
+        }
+        match (stops, residues) {
+            (_, 0) => CodonClass::DefinitelyStop,
+            (0, _) => CodonClass::DefinitelyResidue(self.to_fn()(codon)),
+            _ => CodonClass::Mixed,
+        }
+    }
+
     pub fn translate_dna_bytes<T: NucleotideLike>(
         self,
         dna: &[u8],
     ) -> Result<Vec<u8>, TranslationError> {
+        self.translate_dna_bytes_positioned::<T>(dna)
+            .map_err(|err| err.error)
+    }
+
+    /// Like [`translate_dna_bytes`](Self::translate_dna_bytes), but a failure also reports
+    /// the byte offset of the first bad nucleotide. The index of the codon containing it is
+    /// `position / 3`.
+    ///
+    /// ```
+    /// use quickdna::{Nucleotide, Positioned, TranslationError, TranslationTable};
+    ///
+    /// let table = TranslationTable::Ncbi1;
+    /// let err = table
+    ///     .translate_dna_bytes_positioned::<Nucleotide>(b"GATTACANCC")
+    ///     .unwrap_err();
+    /// assert_eq!(
+    ///     err,
+    ///     Positioned {
+    ///         position: 7,
+    ///         error: TranslationError::UnexpectedAmbiguousNucleotide('N'),
+    ///     }
+    /// );
+    /// assert_eq!(err.position / 3, 2);
+    /// assert_eq!(err.to_string(), "at column 8: unexpected ambiguous nucleotide: 'N'");
+    /// // The trailing partial codon is still ignored.
+    /// assert_eq!(
+    ///     table.translate_dna_bytes_positioned::<Nucleotide>(b"GATTACAN"),
+    ///     Ok(b"DY".to_vec())
+    /// );
+    /// ```
+    pub fn translate_dna_bytes_positioned<T: NucleotideLike>(
+        self,
+        dna: &[u8],
+    ) -> Result<Vec<u8>, Positioned<TranslationError>> {
         if dna.is_empty() {
             return Ok(Vec::new());
         }
 
-        let table_idx = self.table_index();
-
-        let mut result = Vec::with_capacity(dna.len() / 3);
-
-        // this will truncate any trailing non-multiple-of-3 chunk
-        // biopython also truncates, but warns -- generally I don't think we care,
-        // so I just made it silently truncate
-        for chunk in dna.chunks_exact(3) {
-            let a: T = chunk[0].try_into()?;
-            let b: T = chunk[1].try_into()?;
-            let c: T = chunk[2].try_into()?;
-            let codon_idx = CodonIdx::from([a, b, c]);
-            result.push(
-                Self::TRANSLATION_TABLES
-                    [table_idx * TranslationTable::CODONS_PER_TABLE + usize::from(codon_idx)],
-            );
-        }
-
-        Ok(result)
+        // this will truncate any trailing non-multiple-of-3 chunk, without validating it
+        let dna = T::try_pack_ascii(&dna[..dna.len() - dna.len() % 3])?;
+        Ok(self.translate_dna(&dna))
     }
 
     pub fn translate_dna<T: NucleotideLike>(self, dna: &[T]) -> Vec<u8> {
@@ -225,8 +623,29 @@ impl TranslationTable {
 
         result
     }
+
+    /// Like [`Self::translate_dna`], but writes `symbols.stop` instead of `*` for stop
+    /// codons and `symbols.unknown` instead of `X`, for tools that expect e.g. `.` for stops.
+    pub fn translate_dna_with_symbols<T: NucleotideLike>(
+        self,
+        dna: &[T],
+        symbols: TranslationSymbols,
+    ) -> Vec<u8> {
+        let mut result = self.translate_dna(dna);
+        for aa in &mut result {
+            match *aa {
+                b'*' => *aa = symbols.stop,
+                b'X' => *aa = symbols.unknown,
+                _ => {}
+            }
+        }
+        result
+    }
 }
 
+/// Converts an NCBI table number. With the `minimal-tables` feature, this fails for tables
+/// that aren't [`TranslationTable::AVAILABLE`], so a table chosen at runtime can be rejected
+/// up front rather than panicking on use.
 impl TryFrom<u8> for TranslationTable {
     type Error = TranslationError;
 
@@ -263,6 +682,85 @@ impl TryFrom<u8> for TranslationTable {
             33 => Ok(Self::Ncbi33),
             _ => Err(TranslationError::BadTranslationTable(value)),
         }
+        .and_then(|table| {
+            if table.is_available() {
+                Ok(table)
+            } else {
+                Err(TranslationError::UnavailableTranslationTable(value))
+            }
+        })
+    }
+}
+
+impl From<TranslationTable> for u8 {
+    /// The NCBI number of the table.
+    fn from(table: TranslationTable) -> Self {
+        match table {
+            TranslationTable::Ncbi1 => 1,
+            TranslationTable::Ncbi2 => 2,
+            TranslationTable::Ncbi3 => 3,
+            TranslationTable::Ncbi4 => 4,
+            TranslationTable::Ncbi5 => 5,
+            TranslationTable::Ncbi6 => 6,
+            TranslationTable::Ncbi7 => 7,
+            TranslationTable::Ncbi8 => 8,
+            TranslationTable::Ncbi9 => 9,
+            TranslationTable::Ncbi10 => 10,
+            TranslationTable::Ncbi11 => 11,
+            TranslationTable::Ncbi12 => 12,
+            TranslationTable::Ncbi13 => 13,
+            TranslationTable::Ncbi14 => 14,
+            TranslationTable::Ncbi15 => 15,
+            TranslationTable::Ncbi16 => 16,
+            TranslationTable::Ncbi21 => 21,
+            TranslationTable::Ncbi22 => 22,
+            TranslationTable::Ncbi23 => 23,
+            TranslationTable::Ncbi24 => 24,
+            TranslationTable::Ncbi25 => 25,
+            TranslationTable::Ncbi26 => 26,
+            TranslationTable::Ncbi27 => 27,
+            TranslationTable::Ncbi28 => 28,
+            TranslationTable::Ncbi29 => 29,
+            TranslationTable::Ncbi30 => 30,
+            TranslationTable::Ncbi31 => 31,
+            TranslationTable::Ncbi32 => 32,
+            TranslationTable::Ncbi33 => 33,
+        }
+    }
+}
+
+/// Displays the name of the table's variant, e.g. `Ncbi11`.
+impl fmt::Display for TranslationTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Ncbi{}", u8::from(*self))
+    }
+}
+
+/// Parses either a variant name (ignoring case) or a bare NCBI number.
+///
+/// ```
+/// use quickdna::TranslationTable;
+///
+/// # #[cfg(not(feature = "minimal-tables"))] {
+/// assert_eq!("Ncbi11".parse::<TranslationTable>().unwrap(), TranslationTable::Ncbi11);
+/// assert_eq!("ncbi2".parse::<TranslationTable>().unwrap(), TranslationTable::Ncbi2);
+/// assert_eq!("11".parse::<TranslationTable>().unwrap(), TranslationTable::Ncbi11);
+/// # }
+/// assert!("Ncbi17".parse::<TranslationTable>().is_err());
+/// assert!("standard".parse::<TranslationTable>().is_err());
+/// ```
+impl FromStr for TranslationTable {
+    type Err = TranslationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let number = match s.get(..4) {
+            Some(prefix) if prefix.eq_ignore_ascii_case("ncbi") => &s[4..],
+            _ => s,
+        };
+        let number: u8 = number
+            .parse()
+            .map_err(|_| TranslationError::BadTranslationTableName(s.to_owned()))?;
+        Self::try_from(number)
     }
 }
 
@@ -284,3 +782,239 @@ pub fn reverse_complement_bytes<T: NucleotideLike>(
 pub fn reverse_complement<T: NucleotideLike>(dna: &[T]) -> Vec<T> {
     dna.iter().rev().map(|n| n.complement()).collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::NucleotideAmbiguous;
+
+    #[test]
+    fn test_all_tables() {
+        let numbers: Vec<u8> = TranslationTable::AVAILABLE
+            .iter()
+            .copied()
+            .map(u8::from)
+            .collect();
+        let valid: Vec<u8> = (0..=u8::MAX)
+            .filter(|&n| TranslationTable::try_from(n).is_ok())
+            .collect();
+        assert_eq!(numbers, valid);
+    }
+
+    #[test]
+    fn test_ncbi1_table() {
+        let ncbi1 = TranslationTable::Ncbi1.to_fn();
+        for a in NucleotideAmbiguous::ALL {
+            for b in NucleotideAmbiguous::ALL {
+                for c in NucleotideAmbiguous::ALL {
+                    let codon = CodonAmbiguous([a, b, c]);
+                    assert_eq!(
+                        TranslationTable::translate_ambiguous_ncbi1(codon),
+                        ncbi1(codon)
+                    );
+                }
+            }
+        }
+        for codon in TranslationTable::Ncbi1.stop_codons() {
+            assert_eq!(TranslationTable::translate_ncbi1(codon), b'*');
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "minimal-tables")]
+    #[should_panic(expected = "minimal-tables")]
+    fn test_minimal_tables_rejects_other_tables() {
+        TranslationTable::Ncbi11.translate_dna(&[Nucleotide::A; 3]);
+    }
+
+    #[test]
+    #[cfg(feature = "minimal-tables")]
+    fn test_minimal_tables_try_from() {
+        assert!(!TranslationTable::Ncbi11.is_available());
+        assert_eq!(
+            TranslationTable::try_from(11),
+            Err(TranslationError::UnavailableTranslationTable(11))
+        );
+        assert!("Ncbi11".parse::<TranslationTable>().is_err());
+        assert_eq!(TranslationTable::try_from(8), Ok(TranslationTable::Ncbi8));
+    }
+
+    #[test]
+    fn test_stop_codons() {
+        for &table in TranslationTable::AVAILABLE {
+            let stops = table.stop_codons();
+            // these tables' stop codons also code for amino acids, depending on context
+            let ambiguous_stops = [
+                TranslationTable::Ncbi27,
+                TranslationTable::Ncbi28,
+                TranslationTable::Ncbi31,
+            ];
+            assert_eq!(stops.is_empty(), ambiguous_stops.contains(&table));
+            for codon in stops {
+                assert_eq!(table.translate_dna(&codon.0), b"*");
+                assert!(table.might_be_stop(codon.into()));
+            }
+        }
+        let nnn: CodonAmbiguous = "NNN".parse().unwrap();
+        assert!(TranslationTable::AVAILABLE
+            .iter()
+            .filter(|table| !table.stop_codons().is_empty())
+            .all(|table| table.might_be_stop(nnn)));
+        assert!(!TranslationTable::Ncbi1.might_be_stop("CNN".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_classify_ambiguous_codon() {
+        for &table in TranslationTable::AVAILABLE {
+            for codon in NucleotideAmbiguous::ALL.iter().flat_map(|&a| {
+                NucleotideAmbiguous::ALL.iter().flat_map(move |&b| {
+                    NucleotideAmbiguous::ALL
+                        .iter()
+                        .map(move |&c| CodonAmbiguous([a, b, c]))
+                })
+            }) {
+                let class = table.classify_ambiguous_codon(codon);
+                assert_ne!(class, CodonClass::DefinitelyResidue(b'*'));
+                let all_stop = codon.possibilities().all(|c| table.is_stop_codon(c));
+                assert_eq!(class == CodonClass::DefinitelyStop, all_stop);
+                assert_eq!(
+                    matches!(class, CodonClass::DefinitelyResidue(_)),
+                    !table.might_be_stop(codon)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_names_round_trip() {
+        for number in 0..=u8::MAX {
+            let Ok(table) = TranslationTable::try_from(number) else {
+                assert!(number.to_string().parse::<TranslationTable>().is_err());
+                continue;
+            };
+            assert_eq!(u8::from(table), number);
+            assert_eq!(table.to_string(), format!("Ncbi{number}"));
+            assert_eq!(
+                table.to_string().parse::<TranslationTable>().unwrap(),
+                table
+            );
+            assert_eq!(
+                table
+                    .to_string()
+                    .to_uppercase()
+                    .parse::<TranslationTable>()
+                    .unwrap(),
+                table
+            );
+            assert_eq!(
+                number.to_string().parse::<TranslationTable>().unwrap(),
+                table
+            );
+            assert!(!table.description().is_empty());
+        }
+        assert_eq!(TranslationTable::Ncbi1.description(), "The standard code");
+        assert!("".parse::<TranslationTable>().is_err());
+        assert!("Ncbi".parse::<TranslationTable>().is_err());
+        assert!("Ncbi-1".parse::<TranslationTable>().is_err());
+        assert!("Ncbi256".parse::<TranslationTable>().is_err());
+    }
+
+    #[test]
+    fn test_translate_codon() {
+        for &table in TranslationTable::AVAILABLE {
+            let translate = table.to_fn();
+            for a in NucleotideAmbiguous::ALL {
+                for b in NucleotideAmbiguous::ALL {
+                    for c in NucleotideAmbiguous::ALL {
+                        let codon = CodonAmbiguous([a, b, c]);
+                        let expected = translate(codon);
+                        assert_eq!(table.translate_codon_ambiguous(codon), expected);
+                        assert_eq!(codon.translate(table), expected);
+                        if let Ok(codon) = Codon::try_from(codon) {
+                            assert_eq!(table.translate_codon(codon), expected);
+                            assert_eq!(codon.translate(table), expected);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_translate_dna_with_symbols() {
+        let dna = NucleotideAmbiguous::try_pack_ascii(b"ATGNNNTAAGCN").unwrap();
+        let table = TranslationTable::Ncbi1;
+        assert_eq!(table.translate_dna(&dna), b"MX*A");
+        assert_eq!(
+            table.translate_dna_with_symbols(&dna, TranslationSymbols::default()),
+            b"MX*A"
+        );
+        let symbols = TranslationSymbols::new().stop(b'.').unknown(b'?');
+        assert_eq!(table.translate_dna_with_symbols(&dna, symbols), b"M?.A");
+    }
+
+    #[test]
+    fn test_diff() {
+        for &table in TranslationTable::AVAILABLE {
+            assert_eq!(table.diff(table), []);
+            for &other in TranslationTable::AVAILABLE {
+                let diff = table.diff(other);
+                let reversed: Vec<_> = other
+                    .diff(table)
+                    .into_iter()
+                    .map(|(codon, a, b)| (codon, b, a))
+                    .collect();
+                assert_eq!(diff, reversed);
+                for (codon, a, b) in diff {
+                    assert_eq!(table.translate_codon(codon), a);
+                    assert_eq!(other.translate_codon(codon), b);
+                }
+            }
+        }
+        // Ncbi8 is an alias for Ncbi1, and Ncbi11 only differs in its start codons.
+        assert_eq!(TranslationTable::Ncbi1.diff(TranslationTable::Ncbi8), []);
+        #[cfg(not(feature = "minimal-tables"))]
+        {
+            assert_eq!(TranslationTable::Ncbi1.diff(TranslationTable::Ncbi11), []);
+            assert_eq!(
+                TranslationTable::Ncbi1.diff(TranslationTable::Ncbi2).len(),
+                4
+            );
+        }
+    }
+
+    #[test]
+    fn test_translate_dna_bytes_positioned() {
+        let table = TranslationTable::Ncbi1;
+        let translate = |dna: &[u8]| table.translate_dna_bytes_positioned::<Nucleotide>(dna);
+        assert_eq!(translate(b""), Ok(vec![]));
+        assert_eq!(translate(b"ATGTAAGC"), Ok(b"M*".to_vec()));
+        assert_eq!(
+            translate(b"ATGTAAGCN"),
+            Err(Positioned {
+                position: 8,
+                error: TranslationError::UnexpectedAmbiguousNucleotide('N')
+            })
+        );
+        assert_eq!(
+            translate(b"XTGTAA"),
+            Err(Positioned {
+                position: 0,
+                error: TranslationError::BadNucleotide('X')
+            })
+        );
+        // Only the first bad nucleotide is reported.
+        assert_eq!(
+            translate(b"ATGTAZGCN"),
+            Err(Positioned {
+                position: 5,
+                error: TranslationError::BadNucleotide('Z')
+            })
+        );
+        assert_eq!(
+            table.translate_dna_bytes_positioned::<NucleotideAmbiguous>(b"ATGNNN"),
+            Ok(b"MX".to_vec())
+        );
+    }
+}
@@ -0,0 +1,245 @@
+// Copyright 2021-2024 SecureDNA Stiftung (SecureDNA Foundation) <licensing@securedna.org>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Unambiguous DNA packed 2 bits per nucleotide into [`u64`] words.
+//!
+//! Nucleotides are encoded as `A` = 0, `C` = 1, `G` = 2, `T` = 3, so that complementary
+//! nucleotides are bitwise inverses: `complement(x) = x ^ 0b11`. The first nucleotide is in
+//! the most significant bits of the first word, and any unused bits at the end of the last
+//! word are zero.
+//!
+//! Note that this differs from the encoding used by [`kmer`](crate::kmer).
+
+use crate::{DnaSequenceStrict, Nucleotide};
+
+const NUCLEOTIDES_PER_WORD: usize = 32;
+
+/// Pack a nucleotide into the low 2 bits of a word.
+fn encode(nucleotide: Nucleotide) -> u64 {
+    match nucleotide {
+        Nucleotide::A => 0b00,
+        Nucleotide::C => 0b01,
+        Nucleotide::G => 0b10,
+        Nucleotide::T => 0b11,
+    }
+}
+
+/// Unpack a nucleotide from the low 2 bits of `bits`.
+fn decode(bits: u64) -> Nucleotide {
+    [Nucleotide::A, Nucleotide::C, Nucleotide::G, Nucleotide::T][(bits & 0b11) as usize]
+}
+
+/// A sequence of unambiguous DNA, packed 2 bits per nucleotide.
+///
+/// This takes a quarter of the memory of a [`DnaSequenceStrict`], and supports
+/// operations like [`Self::reverse_complement`] 32 nucleotides at a time.
+///
+/// # Examples
+///
+/// ```
+/// use quickdna::{twobit::PackedDnaSequence, DnaSequenceStrict};
+///
+/// let dna: DnaSequenceStrict = "GATTACA".parse().unwrap();
+/// let packed = PackedDnaSequence::from(&dna);
+/// assert_eq!(packed.len(), 7);
+/// assert_eq!(packed.reverse_complement().to_dna(), dna.reverse_complement());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, std::hash::Hash)]
+pub struct PackedDnaSequence {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl PackedDnaSequence {
+    /// Number of nucleotides in this sequence.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether this sequence has no nucleotides.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The packed words of this sequence.
+    pub fn words(&self) -> &[u64] {
+        &self.words
+    }
+
+    /// The nucleotide at `index`, or `None` if it's out of bounds.
+    pub fn get(&self, index: usize) -> Option<Nucleotide> {
+        if index >= self.len {
+            return None;
+        }
+        let word = self.words[index / NUCLEOTIDES_PER_WORD];
+        Some(decode(word >> shift(index)))
+    }
+
+    /// Returns an iterator over the nucleotides of this sequence.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = Nucleotide> + '_ {
+        (0..self.len).map(|i| decode(self.words[i / NUCLEOTIDES_PER_WORD] >> shift(i)))
+    }
+
+    /// Unpack this sequence into a [`DnaSequenceStrict`].
+    pub fn to_dna(&self) -> DnaSequenceStrict {
+        DnaSequenceStrict::new(self.iter().collect())
+    }
+
+    /// Complements every nucleotide of this sequence in place, without reversing it.
+    ///
+    /// With this encoding, complementing is a single NOT (an XOR with all ones) per word.
+    ///
+    /// ```
+    /// use quickdna::{twobit::PackedDnaSequence, DnaSequenceStrict};
+    ///
+    /// let dna: DnaSequenceStrict = "GATTACA".parse().unwrap();
+    /// let mut packed = PackedDnaSequence::from(&dna);
+    /// packed.complement_in_place();
+    /// assert_eq!(packed.to_dna().to_string(), "CTAATGT");
+    /// ```
+    pub fn complement_in_place(&mut self) {
+        for word in &mut self.words {
+            *word = !*word;
+        }
+        // Keep the padding at the end of the last word zeroed.
+        let used = self.len % NUCLEOTIDES_PER_WORD;
+        if let (Some(last), true) = (self.words.last_mut(), used > 0) {
+            *last &= !(u64::MAX >> (2 * used));
+        }
+    }
+
+    /// Takes the reverse complement of this sequence, a whole word at a time.
+    pub fn reverse_complement(&self) -> Self {
+        // Reversing and complementing every word, then reversing the order of the words,
+        // reverse complements the sequence but moves the end-of-sequence padding to the start.
+        let mut words: Vec<u64> = self
+            .words
+            .iter()
+            .rev()
+            .map(|&word| !reverse_nucleotides(word))
+            .collect();
+
+        // Shift the whole sequence towards the start to drop the (now complemented) padding,
+        // which also refills the end of the last word with zeros.
+        let padding = 2 * (NUCLEOTIDES_PER_WORD * words.len() - self.len);
+        if padding > 0 {
+            for i in 0..words.len() {
+                let next = words.get(i + 1).map_or(0, |&next| next >> (64 - padding));
+                words[i] = (words[i] << padding) | next;
+            }
+        }
+
+        Self {
+            words,
+            len: self.len,
+        }
+    }
+}
+
+/// Bit shift of the nucleotide at `index` within its word.
+fn shift(index: usize) -> usize {
+    2 * (NUCLEOTIDES_PER_WORD - 1 - index % NUCLEOTIDES_PER_WORD)
+}
+
+/// Reverse the order of the 32 2-bit nucleotides in `word`.
+fn reverse_nucleotides(word: u64) -> u64 {
+    let word = ((word >> 2) & 0x3333_3333_3333_3333) | ((word & 0x3333_3333_3333_3333) << 2);
+    let word = ((word >> 4) & 0x0f0f_0f0f_0f0f_0f0f) | ((word & 0x0f0f_0f0f_0f0f_0f0f) << 4);
+    word.swap_bytes()
+}
+
+impl FromIterator<Nucleotide> for PackedDnaSequence {
+    fn from_iter<I: IntoIterator<Item = Nucleotide>>(iter: I) -> Self {
+        let mut words = vec![];
+        let mut len = 0;
+        for nucleotide in iter {
+            if len % NUCLEOTIDES_PER_WORD == 0 {
+                words.push(0);
+            }
+            *words.last_mut().unwrap() |= encode(nucleotide) << shift(len);
+            len += 1;
+        }
+        Self { words, len }
+    }
+}
+
+impl From<&DnaSequenceStrict> for PackedDnaSequence {
+    fn from(value: &DnaSequenceStrict) -> Self {
+        value.as_ref().iter().copied().collect()
+    }
+}
+
+impl From<&PackedDnaSequence> for DnaSequenceStrict {
+    fn from(value: &PackedDnaSequence) -> Self {
+        value.to_dna()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use quickcheck::quickcheck;
+
+    use crate::NucleotideLike;
+
+    fn dna(dna: &str) -> DnaSequenceStrict {
+        dna.parse().unwrap()
+    }
+
+    #[test]
+    fn packs_first_nucleotide_in_high_bits() {
+        let packed = PackedDnaSequence::from(&dna("TCG"));
+        assert_eq!(packed.words(), [0b11_01_10 << 58]);
+        assert_eq!(packed.get(2), Some(Nucleotide::G));
+        assert_eq!(packed.get(3), None);
+    }
+
+    #[test]
+    fn complements_are_bitwise_inverses() {
+        for n in Nucleotide::ALL {
+            assert_eq!(encode(n.complement()), encode(n) ^ 0b11);
+            assert_eq!(decode(encode(n)), n);
+        }
+    }
+
+    #[test]
+    fn reverse_complement_across_words() {
+        for len in [0, 1, 31, 32, 33, 64, 65, 100] {
+            let src = dna(&"GATTACA".repeat(15)[..len]);
+            let packed = PackedDnaSequence::from(&src);
+            let rc = packed.reverse_complement();
+            assert_eq!(rc.to_dna(), src.reverse_complement());
+            assert_eq!(rc, PackedDnaSequence::from(&src.reverse_complement()));
+        }
+    }
+
+    #[test]
+    fn complement_across_words() {
+        for len in [0, 1, 31, 32, 33, 64, 65, 100] {
+            let src = dna(&"GATTACA".repeat(15)[..len]);
+            let mut packed = PackedDnaSequence::from(&src);
+            packed.complement_in_place();
+            let complement = DnaSequenceStrict::new(src.iter().map(|n| n.complement()).collect());
+            assert_eq!(packed, PackedDnaSequence::from(&complement));
+        }
+    }
+
+    quickcheck! {
+        fn packing_round_trips(dna: DnaSequenceStrict) -> bool {
+            let packed = PackedDnaSequence::from(&dna);
+            packed.len() == dna.as_ref().len() && packed.to_dna() == dna
+        }
+
+        fn reverse_complement_matches_unpacked(dna: DnaSequenceStrict) -> bool {
+            let rc = PackedDnaSequence::from(&dna).reverse_complement();
+            rc == PackedDnaSequence::from(&dna.reverse_complement())
+        }
+
+        fn complement_matches_unpacked(dna: DnaSequenceStrict) -> bool {
+            let mut packed = PackedDnaSequence::from(&dna);
+            packed.complement_in_place();
+            packed.iter().eq(dna.iter().map(|n| n.complement()))
+        }
+    }
+}
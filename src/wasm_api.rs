@@ -0,0 +1,33 @@
+// Copyright 2021-2024 SecureDNA Stiftung (SecureDNA Foundation) <licensing@securedna.org>
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! JavaScript bindings, for building with `--target wasm32-unknown-unknown`.
+
+use wasm_bindgen::prelude::*;
+
+use crate::{trans_table::reverse_complement_bytes, NucleotideAmbiguous, TranslationTable};
+
+/// Translate a string of DNA nucleotides into a string of amino acids, using the NCBI
+/// translation table with the given number.
+///
+/// The input string is allowed to contain IUPAC ambiguity codes; ambiguous
+/// codons are represented by `X` in the output.
+///
+/// * `translate(1, "CCNTACACKCATNCNAAT")` returns `"PYTHXN"`
+#[wasm_bindgen]
+pub fn translate(table: u8, dna: &str) -> Result<String, JsError> {
+    let table = TranslationTable::try_from(table)?;
+    let bytes = table.translate_dna_bytes::<NucleotideAmbiguous>(dna.as_bytes())?;
+    Ok(String::from_utf8(bytes).expect("amino acids are ASCII"))
+}
+
+/// Get the reverse complement of a string of DNA nucleotides.
+///
+/// The input string is allowed to contain IUPAC ambiguity codes.
+///
+/// * `reverseComplement("AAAAABCCC")` returns `"GGGVTTTTT"`
+#[wasm_bindgen(js_name = reverseComplement)]
+pub fn reverse_complement(dna: &str) -> Result<String, JsError> {
+    let bytes = reverse_complement_bytes::<NucleotideAmbiguous>(dna.as_bytes())?;
+    Ok(String::from_utf8(bytes).expect("nucleotides are ASCII"))
+}
@@ -3,7 +3,7 @@
 
 import pytest
 
-from quickdna import DnaSequence, ProteinSequence
+from quickdna import DnaSequence, FastaRecord, ProteinSequence, parse_fasta
 
 
 def test_translate():
@@ -155,3 +155,37 @@ def test_strict():
     )
     with pytest.raises(ValueError):
         DnaSequence("AAABBBAAA").translate(table=1, strict=True)
+    with pytest.raises(ValueError):
+        DnaSequence("AAABBBAAA").translate_self_frames(table=1, strict=True)
+    with pytest.raises(ValueError):
+        DnaSequence("AAABBBAAA").translate_all_frames(table=1, strict=True)
+
+
+def test_gc_content():
+    assert DnaSequence("GATN").gc_content() == 0.375
+    assert DnaSequence("gcgc").gc_content() == 1.0
+    assert DnaSequence("").gc_content() == 0.0
+    with pytest.raises(ValueError):
+        DnaSequence("GAQ").gc_content()
+
+
+def test_base_counts():
+    assert DnaSequence("GATTACA").base_counts() == {"A": 3, "C": 1, "G": 1, "T": 2}
+    assert DnaSequence("ann").base_counts() == {"A": 1, "N": 2}
+    assert DnaSequence("").base_counts() == {}
+
+
+def test_parse_fasta():
+    assert parse_fasta(">a\nAC\nGT\n>b\n>c\nTT") == [
+        FastaRecord("a", b"ACGT", (1, 4)),
+        FastaRecord("b\nc", b"TT", (4, 7)),
+    ]
+    assert parse_fasta(b"AC\n>a\nGT", allow_preceding_comment=True) == [
+        FastaRecord("a", b"GT", (2, 4)),
+    ]
+    assert parse_fasta(">a\n>b\nGT", concatenate_headers=False) == [
+        FastaRecord("a", b"", (1, 2)),
+        FastaRecord("b", b"GT", (2, 4)),
+    ]
+    with pytest.raises(ValueError):
+        parse_fasta(b">a\n\xff")